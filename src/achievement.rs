@@ -0,0 +1,144 @@
+//! Replay goals that persist across shifts.
+//!
+//! The engine awards achievements as they happen; `main` records them in the
+//! achievements file and announces the ones that are new. The file is one
+//! `unlocked = "<id>"` line per achievement, in the TOML subset `crate::toml`
+//! reads.
+
+use crate::toml;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+
+/// Where unlocked achievements are kept between shifts.
+pub const ACHIEVEMENTS_PATH: &str = "coldwar-achievements.toml";
+
+/// The mole must be unmasked on or before this turn for `EarlyCatch`.
+pub const EARLY_CATCH_TURN: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Achievement {
+    /// Talked Chernov down on the Red Phone.
+    CoolHead,
+    /// Unmasked the mole by `EARLY_CATCH_TURN`.
+    EarlyCatch,
+    /// Let the Project finish waking up.
+    Basilisk,
+    /// Won the shift without ever ordering an escalation.
+    Dove,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 4] = [
+        Achievement::CoolHead,
+        Achievement::EarlyCatch,
+        Achievement::Basilisk,
+        Achievement::Dove,
+    ];
+
+    /// Stable name written to the achievements file.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Achievement::CoolHead => "cool_head",
+            Achievement::EarlyCatch => "early_catch",
+            Achievement::Basilisk => "basilisk",
+            Achievement::Dove => "dove",
+        }
+    }
+
+    pub fn parse(id: &str) -> Option<Achievement> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::CoolHead => "COOL HEAD",
+            Achievement::EarlyCatch => "EARLY CATCH",
+            Achievement::Basilisk => "IT WAKES",
+            Achievement::Dove => "DOVE",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::CoolHead => "Survived a Red Phone call without launching",
+            Achievement::EarlyCatch => "Identified the mole by turn 5",
+            Achievement::Basilisk => "Reached the Basilisk ending",
+            Achievement::Dove => "Won without ever escalating",
+        }
+    }
+}
+
+/// The achievements unlocked so far, backed by a file.
+#[derive(Debug)]
+pub struct AchievementBook {
+    path: String,
+    unlocked: Vec<Achievement>,
+}
+
+impl AchievementBook {
+    /// Reads the book at `path`. A missing file is an empty book; unknown
+    /// ids (from a newer build) are skipped.
+    pub fn load(path: &str) -> Result<AchievementBook, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("cannot read '{}': {}", path, e)),
+        };
+        let doc = toml::parse(&text).map_err(|e| format!("{}: {}", path, e))?;
+        let unlocked = doc
+            .root
+            .get_all("unlocked")
+            .filter_map(|v| v.as_str().and_then(Achievement::parse))
+            .collect();
+        Ok(AchievementBook {
+            path: path.to_string(),
+            unlocked,
+        })
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    /// Records `achievement` and writes the book back. Returns whether it
+    /// was newly unlocked.
+    pub fn unlock(&mut self, achievement: Achievement) -> io::Result<bool> {
+        if self.is_unlocked(achievement) {
+            return Ok(false);
+        }
+        self.unlocked.push(achievement);
+        fs::write(&self.path, self.render())?;
+        Ok(true)
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::from("# Cold War Terminal achievements.\n");
+        for achievement in &self.unlocked {
+            let _ = writeln!(out, "unlocked = \"{}\"", achievement.id());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlocks_persist_and_only_count_once() {
+        let path = std::env::temp_dir().join(format!("coldwar-ach-{}.toml", std::process::id()));
+        let path = path.to_str().unwrap();
+        let _ = fs::remove_file(path);
+
+        let mut book = AchievementBook::load(path).unwrap();
+        assert!(!book.is_unlocked(Achievement::Dove));
+        assert!(book.unlock(Achievement::Dove).unwrap());
+        assert!(!book.unlock(Achievement::Dove).unwrap());
+
+        let reloaded = AchievementBook::load(path).unwrap();
+        assert!(reloaded.is_unlocked(Achievement::Dove));
+        assert!(!reloaded.is_unlocked(Achievement::Basilisk));
+        let _ = fs::remove_file(path);
+    }
+}
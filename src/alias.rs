@@ -0,0 +1,110 @@
+//! Player-defined command shorthands (`--aliases <file>`).
+//!
+//! The file is flat TOML, one `alias = "command"` per line:
+//!
+//! ```toml
+//! nuke = "escalate"
+//! ck = "consult k"
+//! ```
+//!
+//! An alias replaces the first word of the input, so `ck` expands to
+//! `consult k` and `nuke now` to `escalate now`.
+
+use crate::commands;
+use crate::toml;
+use std::fs;
+
+#[derive(Debug, Default)]
+pub struct Aliases {
+    entries: Vec<(String, String)>,
+}
+
+impl Aliases {
+    /// Loads an alias file. Also returns warnings for aliases that shadow a
+    /// built-in command (they still take effect).
+    pub fn load(path: &str) -> Result<(Aliases, Vec<String>), String> {
+        let text =
+            fs::read_to_string(path).map_err(|e| format!("cannot read '{}': {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn parse(text: &str) -> Result<(Aliases, Vec<String>), String> {
+        let doc = toml::parse(text)?;
+        let mut aliases = Aliases::default();
+        let mut warnings = Vec::new();
+
+        for (key, value) in &doc.root.entries {
+            let name = key.trim().to_lowercase();
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(format!("alias '{}' must be a single word", key));
+            }
+            // Digits are the menu selectors; an alias would make `3` mean two things.
+            if name.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("alias '{}' would shadow a menu number", key));
+            }
+            let command = value
+                .as_str()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .ok_or_else(|| format!("alias '{}' needs a command string", key))?;
+            if aliases.entries.iter().any(|(n, _)| *n == name) {
+                return Err(format!("alias '{}' is defined twice", key));
+            }
+            if commands::words().any(|w| w == name) {
+                warnings.push(format!("alias '{}' shadows a built-in command", name));
+            }
+            aliases.entries.push((name, command.to_string()));
+        }
+
+        Ok((aliases, warnings))
+    }
+
+    /// Every first word the prompt understands: built-ins (menu numbers
+    /// included) and these aliases.
+    pub fn command_words(&self) -> Vec<String> {
+        let builtins = commands::words().map(str::to_string);
+        let aliases = self.entries.iter().map(|(name, _)| name.clone());
+        builtins.chain(aliases).collect()
+    }
+
+    /// Rewrites `input` if its first word is an alias; otherwise returns it as is.
+    /// Aliases expand once, so an alias pointing at another alias is not chased.
+    pub fn expand(&self, input: &str) -> String {
+        let (first, rest) = match input.split_once(char::is_whitespace) {
+            Some((first, rest)) => (first, rest.trim_start()),
+            None => (input, ""),
+        };
+        let first = first.to_lowercase();
+        match self.entries.iter().find(|(name, _)| *name == first) {
+            Some((_, command)) if rest.is_empty() => command.clone(),
+            Some((_, command)) => format!("{} {}", command, rest),
+            None => input.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_the_first_word_and_keeps_arguments() {
+        let (aliases, warnings) =
+            Aliases::parse("nuke = \"escalate\"\nck = \"consult k\"\n").unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(aliases.expand("NUKE"), "escalate");
+        assert_eq!(aliases.expand("ck"), "consult k");
+        assert_eq!(aliases.expand("nuke now"), "escalate now");
+        assert_eq!(aliases.expand("leak"), "leak");
+        let words = aliases.command_words();
+        assert!(words.iter().any(|w| w == "ck"));
+        assert!(words.iter().any(|w| w == "decrypt"));
+    }
+
+    #[test]
+    fn rejects_digits_and_warns_on_builtins() {
+        assert!(Aliases::parse("3 = \"escalate\"").is_err());
+        let (_, warnings) = Aliases::parse("esc = \"contain\"").unwrap();
+        assert_eq!(warnings.len(), 1);
+    }
+}
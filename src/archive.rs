@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+
+/// Default number of entries kept by long-lived archives.
+pub const DEFAULT_ARCHIVE_CAP: usize = 300;
+
+/// Fixed-capacity log: pushing past the cap evicts the oldest entry, so
+/// memory stays flat no matter how long the shift runs.
+#[derive(Debug, Clone)]
+pub struct Archive<T> {
+    entries: VecDeque<T>,
+    cap: usize,
+}
+
+impl<T> Archive<T> {
+    pub fn with_capacity(cap: usize) -> Self {
+        let cap = cap.max(1);
+        Self {
+            entries: VecDeque::with_capacity(cap.min(DEFAULT_ARCHIVE_CAP)),
+            cap,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.entries.len() == self.cap {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(item);
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest_past_cap() {
+        let mut archive = Archive::with_capacity(3);
+        for i in 0..10 {
+            archive.push(i);
+        }
+        assert_eq!(archive.iter().copied().collect::<Vec<_>>(), vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn zero_cap_still_keeps_latest() {
+        let mut archive = Archive::with_capacity(0);
+        archive.push("a");
+        archive.push("b");
+        assert_eq!(archive.iter().copied().collect::<Vec<_>>(), vec!["b"]);
+    }
+}
@@ -0,0 +1,80 @@
+//! A plain heuristic player for attract mode (`--attract`).
+//!
+//! It only looks at what the dashboard shows a human: no peeking at the
+//! mole flag or document reliability.
+
+use crate::game::{Directive, GameEngine};
+
+/// Picks the next directive for the current turn.
+pub fn choose(engine: &GameEngine) -> Directive {
+    let state = &engine.state;
+
+    // Priority cables are lost at turn end, so they come first.
+    if engine.intel_points > 0 {
+        if let Some(doc) = engine
+            .pending_documents
+            .iter()
+            .find(|d| d.priority && d.is_encrypted)
+        {
+            return Directive::Decrypt(doc.id.clone());
+        }
+    }
+
+    // A live signal is the cheap, definitive trace: chase the likeliest suspect.
+    if engine.interruption_active && engine.intel_points > 0 && engine.traces_this_turn < 2 {
+        if let Some(suspect) = state
+            .advisors
+            .iter()
+            .filter(|a| !engine.traced_advisors.contains(&a.name))
+            .max_by_key(|a| a.suspicion)
+        {
+            return Directive::Trace(suspect.name.clone());
+        }
+    }
+
+    if state.global_tension > 0.6 {
+        Directive::Contain
+    } else if state.domestic_stability < 0.35 {
+        Directive::Leak
+    } else if state.secret_weapon_progress < 0.6 {
+        Directive::Investigate
+    } else {
+        Directive::Contain
+    }
+}
+
+/// The menu answer typed at whichever crisis is about to open.
+pub fn crisis_reply(engine: &GameEngine) -> &'static str {
+    let state = &engine.state;
+    if state.red_phone_active {
+        // Admit to Chernov; flip a caught mole.
+        "2"
+    } else if state.hotline_active {
+        // Take the offer.
+        "1"
+    } else if state.coup_active {
+        "arrest"
+    } else {
+        "swap"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypts_priority_cables_before_anything_else() {
+        let mut engine = GameEngine::new();
+        engine.start_turn();
+        engine.intel_points = 1;
+        engine.state.global_tension = 0.9;
+        engine.pending_documents[0].is_encrypted = true;
+        engine.pending_documents[0].priority = true;
+        let id = engine.pending_documents[0].id.clone();
+
+        assert!(matches!(choose(&engine), Directive::Decrypt(d) if d == id));
+        engine.intel_points = 0;
+        assert!(matches!(choose(&engine), Directive::Contain));
+    }
+}
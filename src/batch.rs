@@ -0,0 +1,98 @@
+//! Non-interactive play for tournaments and benchmarks (`--batch`).
+//!
+//! A strategy is a newline-delimited script: directives as typed at the
+//! prompt, plus one answer line for each crisis as it opens. Nothing is drawn
+//! and the signal interruption minigame is skipped; the run reduces to a
+//! single outcome line:
+//!
+//! ```text
+//! ending=<id> turns=<n> score=<n>
+//! ```
+//!
+//! `<id>` is `nuclear_war`, `coup`, `basilisk`, `impostor`, `victory`,
+//! `simulation_end`, or `unfinished` when the script ran out (or quit) first.
+
+use crate::alias::Aliases;
+use crate::commands::{self, Parsed};
+use crate::crisis::{self, Crisis};
+use crate::game::GameEngine;
+use crate::rng::Rng;
+
+/// Plays `script` against `engine` and returns the outcome line.
+pub fn run(engine: &mut GameEngine, script: &str, aliases: &Aliases, rng: &mut dyn Rng) -> String {
+    let mut lines = script
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    let mut turn_over = true;
+
+    'shift: while engine.ending().is_none() {
+        while let Some(crisis) = Crisis::pending(&engine.state) {
+            let Some(answer) = lines.next() else {
+                break 'shift;
+            };
+            crisis::resolve(engine, crisis, answer, rng);
+            if engine.ending().is_some() {
+                break 'shift;
+            }
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        if turn_over {
+            engine.start_turn();
+            // Nobody is at the console to play the interruption minigame.
+            engine.interruption_active = false;
+        }
+        let line = aliases.expand(line);
+        match commands::parse(&line) {
+            Parsed::Directive(directive) => {
+                turn_over = engine.resolve_directive(directive).1;
+            }
+            Parsed::Quit => break,
+            // Free commands and typos leave the turn where it was.
+            Parsed::Usage(_) | Parsed::Unknown(_) => turn_over = false,
+        }
+    }
+
+    let ending = engine.ending().map_or("unfinished", |e| e.id());
+    format!(
+        "ending={} turns={} score={}",
+        ending,
+        engine.turn_count,
+        engine.score()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SimpleRng;
+
+    fn play(script: &str, seed: u64) -> String {
+        let mut engine = GameEngine::new();
+        engine.reseed(seed);
+        let mut rng = SimpleRng::with_seed(seed.wrapping_add(1));
+        run(&mut engine, script, &Aliases::default(), &mut rng)
+    }
+
+    #[test]
+    fn the_same_seed_and_script_give_the_same_outcome() {
+        let script = "investigate\ncontain\n# a comment\n\nleak\nsitrep\ncontain\n";
+        let outcome = play(script, 42);
+        assert_eq!(outcome, play(script, 42));
+        assert!(outcome.starts_with("ending=unfinished turns=4 score="));
+    }
+
+    #[test]
+    fn escalating_forever_ends_in_war() {
+        let outcome = play(&"escalate\n".repeat(200), 7);
+        let fields: Vec<&str> = outcome.split(' ').collect();
+        assert_eq!(fields.len(), 3);
+        assert_ne!(fields[0], "ending=unfinished");
+        assert!(fields[2]
+            .trim_start_matches("score=")
+            .parse::<i64>()
+            .is_ok());
+    }
+}
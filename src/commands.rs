@@ -0,0 +1,328 @@
+//! The prompt's command table: every verb, the other spellings it answers
+//! to and its help text. The matcher in `main.rs`, `help`, `commands`,
+//! `--list-commands`, alias checks and type-ahead all read from here, so a
+//! new spelling is added in one place.
+
+use crate::game::Directive;
+use crate::lang;
+
+pub struct CommandSpec {
+    /// Canonical verb; the matcher dispatches on this.
+    pub name: &'static str,
+    /// Other spellings, menu number first where there is one.
+    pub aliases: &'static [&'static str],
+    /// Help rows as (syntax, message-catalog id of the description).
+    pub usage: &'static [(&'static str, &'static str)],
+}
+
+/// Words that may precede a verb without changing it (`sudo --escalate`).
+pub const PREFIXES: &[&str] = &["sudo", "execute"];
+
+/// Flags that only announce the target (`decrypt -t DOC-1`, `consult -n Volkov`).
+const TARGET_FLAGS: &[&str] = &["-t", "-n", "--target", "--name"];
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "escalate",
+        aliases: &["1", "esc"],
+        usage: &[("escalate", "help.escalate")],
+    },
+    CommandSpec {
+        name: "investigate",
+        aliases: &["2", "inv"],
+        usage: &[("investigate", "help.investigate")],
+    },
+    CommandSpec {
+        name: "contain",
+        aliases: &["3", "con"],
+        usage: &[("contain", "help.contain")],
+    },
+    CommandSpec {
+        name: "leak",
+        aliases: &["4"],
+        usage: &[("leak", "help.leak")],
+    },
+    CommandSpec {
+        name: "stand-down",
+        aliases: &["5", "standdown", "sd"],
+        usage: &[("stand-down", "help.stand_down")],
+    },
+    CommandSpec {
+        name: "decrypt",
+        aliases: &["6", "dec"],
+        usage: &[("decrypt <ID|#>", "help.decrypt")],
+    },
+    CommandSpec {
+        name: "analyze",
+        aliases: &["7", "ana"],
+        usage: &[
+            ("analyze <ID|#>", "help.analyze"),
+            ("analyze --triage", "help.triage"),
+        ],
+    },
+    CommandSpec {
+        name: "trace",
+        aliases: &["8", "traceroute"],
+        usage: &[("trace <NAME>", "help.trace")],
+    },
+    CommandSpec {
+        name: "consult",
+        aliases: &["9", "ask"],
+        usage: &[("consult <NAME>", "help.consult")],
+    },
+    CommandSpec {
+        name: "roundtable",
+        aliases: &["rt"],
+        usage: &[("consult --all", "help.roundtable")],
+    },
+    CommandSpec {
+        name: "interrogate",
+        aliases: &["10", "int", "grill"],
+        usage: &[("interrogate <NAME>", "help.interrogate")],
+    },
+    CommandSpec {
+        name: "overclock",
+        aliases: &[],
+        usage: &[("overclock", "help.overclock")],
+    },
+    CommandSpec {
+        name: "read",
+        aliases: &[],
+        usage: &[("read <ID|#>", "help.read")],
+    },
+    CommandSpec {
+        name: "sitrep",
+        aliases: &[],
+        usage: &[("sitrep", "help.sitrep")],
+    },
+    CommandSpec {
+        name: "stats",
+        aliases: &[],
+        usage: &[("stats", "help.stats")],
+    },
+    CommandSpec {
+        name: "settings",
+        aliases: &[],
+        usage: &[("settings", "help.settings")],
+    },
+    CommandSpec {
+        name: "preview",
+        aliases: &[],
+        usage: &[("preview <DIRECTIVE>", "help.preview")],
+    },
+    CommandSpec {
+        name: "export-transcript",
+        aliases: &[],
+        usage: &[("export-transcript [FILE]", "help.export")],
+    },
+    CommandSpec {
+        name: "save",
+        aliases: &[],
+        usage: &[("save [FILE]", "help.save")],
+    },
+    CommandSpec {
+        name: "load",
+        aliases: &[],
+        usage: &[("load [FILE]", "help.load")],
+    },
+    CommandSpec {
+        name: "achievements",
+        aliases: &[],
+        usage: &[("achievements", "help.achievements")],
+    },
+    CommandSpec {
+        name: "commands",
+        aliases: &[],
+        usage: &[("commands", "help.commands")],
+    },
+    CommandSpec {
+        name: "keys",
+        aliases: &[],
+        usage: &[("keys", "help.keys")],
+    },
+    CommandSpec {
+        name: "help",
+        aliases: &[],
+        usage: &[("help", "help.help")],
+    },
+    CommandSpec {
+        name: "clear",
+        aliases: &["cls"],
+        usage: &[("clear", "help.clear")],
+    },
+    CommandSpec {
+        name: "quit",
+        aliases: &["exit"],
+        usage: &[("quit", "help.quit")],
+    },
+];
+
+/// The canonical verb `word` spells, if any. Case-insensitive.
+pub fn canonical(word: &str) -> Option<&'static str> {
+    COMMANDS
+        .iter()
+        .find(|c| {
+            c.name.eq_ignore_ascii_case(word)
+                || c.aliases.iter().any(|a| a.eq_ignore_ascii_case(word))
+        })
+        .map(|c| c.name)
+}
+
+/// What a line typed at the prompt asks for, once free commands such as
+/// `help` have been ruled out.
+pub enum Parsed {
+    Directive(Directive),
+    /// The verb needs an argument; the usage line to show.
+    Usage(&'static str),
+    Quit,
+    /// Not a verb; the word as typed, flag dashes stripped.
+    Unknown(String),
+}
+
+fn is_target_flag(word: &str) -> bool {
+    TARGET_FLAGS.contains(&word.to_lowercase().as_str())
+}
+
+/// The argument of a free command with any leading `-t`/`-n` dropped, so
+/// `read -t DOC-1234` works like `decrypt -t DOC-1234`.
+pub fn target_arg(rest: &str) -> &str {
+    match rest.split_once(char::is_whitespace) {
+        Some((flag, arg)) if is_target_flag(flag) => arg.trim(),
+        None if is_target_flag(rest) => "",
+        _ => rest,
+    }
+}
+
+/// Reads a directive from `input`: an optional prefix, the verb (dashes
+/// allowed, `sudo --escalate`) and its argument, after an optional `-t`/`-n`.
+/// Documents take one word; an advisor's name takes the rest of the line.
+pub fn parse(input: &str) -> Parsed {
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    let cmd_base = parts.first().unwrap_or(&"").to_lowercase();
+    let (command_str, args_start_idx) = if PREFIXES.contains(&cmd_base.as_str()) {
+        (parts.get(1).unwrap_or(&"").to_lowercase(), 2)
+    } else {
+        (cmd_base, 1)
+    };
+
+    // Handle flags (strip leading dashes)
+    let command_str = command_str.trim_start_matches('-').to_string();
+
+    let mut args = parts.get(args_start_idx..).unwrap_or(&[]);
+    if let Some((flag, rest)) = args.split_first() {
+        if is_target_flag(flag) {
+            args = rest;
+        }
+    }
+    let arg_id = args.first().map(|arg| arg.to_string());
+    let name = (!args.is_empty()).then(|| args.join(" "));
+
+    let needs = |arg: Option<String>, make: fn(String) -> Directive, usage| match arg {
+        Some(arg) => Parsed::Directive(make(arg)),
+        None => Parsed::Usage(usage),
+    };
+    match canonical(&command_str) {
+        Some("escalate") => Parsed::Directive(Directive::Escalate),
+        Some("investigate") => Parsed::Directive(Directive::Investigate),
+        Some("contain") => Parsed::Directive(Directive::Contain),
+        Some("leak") => Parsed::Directive(Directive::Leak),
+        Some("stand-down") => Parsed::Directive(Directive::StandDown),
+        Some("decrypt") => needs(arg_id, Directive::Decrypt, "decrypt -t <id>"),
+        Some("analyze") if matches!(arg_id.as_deref(), Some("--triage" | "triage")) => {
+            Parsed::Directive(Directive::Triage)
+        }
+        Some("analyze") => needs(arg_id, Directive::Analyze, "analyze -t <id>"),
+        Some("trace") => needs(name, Directive::Trace, "traceroute -t <advisor_name>"),
+        Some("roundtable") => Parsed::Directive(Directive::RoundTable),
+        Some("overclock") => Parsed::Directive(Directive::Overclock),
+        Some("consult") if matches!(arg_id.as_deref(), Some("--all" | "-a" | "all")) => {
+            Parsed::Directive(Directive::RoundTable)
+        }
+        Some("consult") => needs(name, Directive::Consult, "consult -n <advisor_name>"),
+        Some("interrogate") => needs(
+            name,
+            Directive::Interrogate,
+            "interrogate -n <advisor_name>",
+        ),
+        Some("quit") => Parsed::Quit,
+        _ => Parsed::Unknown(command_str),
+    }
+}
+
+/// Every word the prompt understands as a first word: verbs, their aliases
+/// and the prefixes.
+pub fn words() -> impl Iterator<Item = &'static str> {
+    COMMANDS
+        .iter()
+        .flat_map(|c| std::iter::once(c.name).chain(c.aliases.iter().copied()))
+        .chain(PREFIXES.iter().copied())
+}
+
+/// The full verb/alias table, one line per command, for `commands` and
+/// `--list-commands`.
+pub fn table() -> Vec<String> {
+    COMMANDS
+        .iter()
+        .map(|c| {
+            format!(
+                "{:<18} {:<20} {}",
+                c.name,
+                c.aliases.join(", "),
+                lang::text(c.usage[0].1)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_spelling_belongs_to_exactly_one_command() {
+        let all: Vec<&str> = words().collect();
+        for (i, word) in all.iter().enumerate() {
+            assert!(!all[i + 1..].contains(word), "'{}' is listed twice", word);
+        }
+        assert_eq!(canonical("SD"), Some("stand-down"));
+        assert_eq!(canonical("10"), Some("interrogate"));
+        assert_eq!(canonical("launch"), None);
+        assert_eq!(canonical("grill"), Some("interrogate"));
+        for command in COMMANDS {
+            for (_, id) in command.usage {
+                assert_ne!(lang::text(id), "???", "{} has no help text", id);
+            }
+        }
+    }
+
+    fn directive(input: &str) -> Option<Directive> {
+        match parse(input) {
+            Parsed::Directive(dir) => Some(dir),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn target_flags_are_skipped_and_names_keep_every_word() {
+        let consult = |name: &str| Some(Directive::Consult(name.to_string()));
+        assert_eq!(directive("consult -n Volkov"), consult("Volkov"));
+        assert_eq!(directive("ask -t gen. ripper"), consult("gen. ripper"));
+        assert_eq!(directive("sudo consult Volkov"), consult("Volkov"));
+        assert_eq!(directive("consult --all"), Some(Directive::RoundTable));
+        assert_eq!(
+            directive("grill -n Dr. Strange"),
+            Some(Directive::Interrogate("Dr. Strange".to_string()))
+        );
+        assert_eq!(
+            directive("decrypt -t DOC-1234"),
+            Some(Directive::Decrypt("DOC-1234".to_string()))
+        );
+        assert!(matches!(parse("interrogate -n"), Parsed::Usage(_)));
+        assert!(matches!(parse("sudo decrypt"), Parsed::Usage(_)));
+
+        assert_eq!(target_arg("-t DOC-1234"), "DOC-1234");
+        assert_eq!(target_arg("--TARGET 2"), "2");
+        assert_eq!(target_arg("-n"), "");
+        assert_eq!(target_arg("DOC-1234"), "DOC-1234");
+    }
+}
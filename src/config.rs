@@ -0,0 +1,443 @@
+use crate::archive::DEFAULT_ARCHIVE_CAP;
+use crate::game::{Difficulty, Volume, DEFAULT_TURN_LIMIT};
+use crate::settings::{self, DEFAULT_FEED_SPEED_MS};
+use crate::state::{AdvisorRole, WorldState};
+use crate::toml::{self, Value};
+use std::fs;
+use std::path::PathBuf;
+
+/// Defaults file, looked for in the working directory and then in
+/// `$XDG_CONFIG_HOME/coldwar/` (or `~/.config/coldwar/`).
+pub const CONFIG_FILE: &str = "coldwar.toml";
+
+/// Launch options parsed from the command line.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Optional advisor roster file (`--roster <file>`).
+    pub roster_path: Option<String>,
+    /// Force plain-ASCII rendering (`--ascii`).
+    pub ascii: bool,
+    /// Hide exact metric values; bands and bar fill only (`--no-numbers`).
+    pub no_numbers: bool,
+    /// Maximum number of past documents kept in the archive (`--archive-cap <n>`).
+    pub archive_cap: usize,
+    /// Disable the Basilisk directive hijack (`--no-override`).
+    pub no_override: bool,
+    /// Shift length (`--turns <n>`, 0 for endless).
+    pub turn_limit: Option<u32>,
+    /// Single-key selection for menu digits 1-5 (`--hotkeys`).
+    pub hotkeys: bool,
+    /// Starting feed speed, per-character delay (`speed` in `coldwar.toml`).
+    pub feed_speed_ms: u64,
+    /// Ring the bell when a crisis opens (`bell` in `coldwar.toml`).
+    pub bell: bool,
+    /// File to write the session transcript to at game over (`--transcript <file>`).
+    pub transcript_path: Option<String>,
+    /// Documents per turn (`--volume low|normal|high`).
+    pub volume: Volume,
+    /// Message catalog: `en`, a bundled code or a file (`--lang <code|file>`).
+    pub lang: String,
+    /// Commands to replay at startup before the keyboard takes over (`--macro <file>`).
+    pub macro_path: Option<String>,
+    /// Skip the typewriter intro (`--quiet-boot`).
+    pub quiet_boot: bool,
+    /// Plain text with no escape codes (`--no-color`). Also the case
+    /// whenever stdout is not a terminal.
+    pub no_color: bool,
+    /// Print everything at once, with no animation or dramatic pauses
+    /// (`--fast`, `--no-anim`).
+    pub fast: bool,
+    /// Hand-authored starting situation (`--scenario <name>`).
+    pub scenario: Option<String>,
+    /// Print the available scenarios and exit (`--list-scenarios`).
+    pub list_scenarios: bool,
+    /// Print the command/alias table and exit (`--list-commands`).
+    pub list_commands: bool,
+    /// Difficulty preset (`--difficulty easy|normal|hard`).
+    pub difficulty: Difficulty,
+    /// Developer-only prompt commands such as `peek` (`--debug`).
+    pub debug: bool,
+    /// Fixed seed for the engine and the terminal effects (`--seed <n>`).
+    pub seed: Option<u64>,
+    /// Put the mole on an advisor with this role (`--mole-role <role>`).
+    pub mole_role: Option<AdvisorRole>,
+    /// Play a strategy piped on stdin and print only the outcome (`--batch`).
+    pub batch: bool,
+    /// Developer trace of engine decisions, one JSON object per line (`--log-file <file>`).
+    pub log_path: Option<String>,
+    /// Turns between autosaves, 0 to disable (`--autosave <n>`).
+    pub autosave_every: u32,
+    /// Resume the latest autosave instead of starting a new shift (`--continue`).
+    pub resume: bool,
+    /// Player command shorthands (`--aliases <file>`).
+    pub aliases_path: Option<String>,
+    /// Self-playing demo that loops until a key is pressed (`--attract`).
+    pub attract: bool,
+    /// Starting world metrics for balance testing (`--start-<metric> <0..1>`).
+    pub start: StartState,
+}
+
+/// Optional overrides for the opening world metrics, each clamped to [0, 1].
+#[derive(Debug, Clone, Default)]
+pub struct StartState {
+    pub tension: Option<f64>,
+    pub stability: Option<f64>,
+    pub secrecy: Option<f64>,
+    pub paranoia: Option<f64>,
+    pub secret_weapon: Option<f64>,
+    pub escalation_risk: Option<f64>,
+    pub corruption: Option<f64>,
+}
+
+impl StartState {
+    pub fn is_empty(&self) -> bool {
+        [
+            self.tension,
+            self.stability,
+            self.secrecy,
+            self.paranoia,
+            self.secret_weapon,
+            self.escalation_risk,
+            self.corruption,
+        ]
+        .iter()
+        .all(Option::is_none)
+    }
+
+    pub fn apply(&self, state: &mut WorldState) {
+        let fields = [
+            (self.tension, &mut state.global_tension),
+            (self.stability, &mut state.domestic_stability),
+            (self.secrecy, &mut state.internal_secrecy),
+            (self.paranoia, &mut state.foreign_paranoia),
+            (self.secret_weapon, &mut state.secret_weapon_progress),
+            (self.escalation_risk, &mut state.accidental_escalation_risk),
+            (self.corruption, &mut state.system_corruption),
+        ];
+        for (value, field) in fields {
+            if let Some(v) = value {
+                *field = v;
+            }
+        }
+    }
+}
+
+fn word(value: &Value) -> Option<&str> {
+    value.as_str().map(str::trim)
+}
+
+fn flag(key: &str, value: &Value) -> Result<bool, String> {
+    value
+        .as_bool()
+        .ok_or_else(|| format!("'{}' must be true or false", key))
+}
+
+/// The defaults file to read, if one exists: the working directory's first,
+/// then the user's config directory.
+fn config_file() -> Option<PathBuf> {
+    let local = PathBuf::from(CONFIG_FILE);
+    if local.is_file() {
+        return Some(local);
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    let user = base.join("coldwar").join(CONFIG_FILE);
+    user.is_file().then_some(user)
+}
+
+/// Reads the value after a `--start-*` flag, clamped into [0, 1].
+fn unit_arg<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<Option<f64>, String> {
+    args.next()
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+        .map(|v| Some(v.clamp(0.0, 1.0)))
+        .ok_or_else(|| format!("{} requires a number between 0 and 1", flag))
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            roster_path: None,
+            ascii: false,
+            no_numbers: false,
+            archive_cap: DEFAULT_ARCHIVE_CAP,
+            no_override: false,
+            turn_limit: Some(DEFAULT_TURN_LIMIT),
+            hotkeys: false,
+            feed_speed_ms: DEFAULT_FEED_SPEED_MS,
+            bell: false,
+            transcript_path: None,
+            volume: Volume::Normal,
+            lang: "en".to_string(),
+            macro_path: None,
+            quiet_boot: false,
+            fast: false,
+            no_color: false,
+            scenario: None,
+            list_scenarios: false,
+            list_commands: false,
+            difficulty: Difficulty::Normal,
+            debug: false,
+            seed: None,
+            mole_role: None,
+            batch: false,
+            log_path: None,
+            autosave_every: 1,
+            resume: false,
+            aliases_path: None,
+            attract: false,
+            start: StartState::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Launch options: the built-in defaults, overridden by `coldwar.toml`
+    /// if there is one, overridden in turn by the process arguments
+    /// (excluding the binary name).
+    pub fn load<I: Iterator<Item = String>>(args: I) -> Result<Config, String> {
+        let mut config = Config::default();
+        if let Some(path) = config_file() {
+            let text = fs::read_to_string(&path)
+                .map_err(|e| format!("cannot read '{}': {}", path.display(), e))?;
+            config
+                .apply_file(&text)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        config.apply_args(args)
+    }
+
+    /// Takes defaults from a `coldwar.toml`. Every key is optional; an
+    /// unknown key or a value of the wrong kind is an error, so a typo does
+    /// not silently fall back.
+    pub fn apply_file(&mut self, text: &str) -> Result<(), String> {
+        let doc = toml::parse(text)?;
+        if let Some((name, _)) = doc.sections.first() {
+            return Err(format!("unexpected section [{}]", name));
+        }
+        for (key, value) in &doc.root.entries {
+            match key.as_str() {
+                "difficulty" => {
+                    self.difficulty = word(value)
+                        .and_then(Difficulty::parse)
+                        .ok_or("'difficulty' must be \"easy\", \"normal\" or \"hard\"")?;
+                }
+                "volume" => {
+                    self.volume = word(value)
+                        .and_then(Volume::parse)
+                        .ok_or("'volume' must be \"low\", \"normal\" or \"high\"")?;
+                }
+                "speed" => {
+                    self.feed_speed_ms = word(value)
+                        .and_then(settings::feed_speed)
+                        .ok_or("'speed' must be \"normal\", \"fast\", \"instant\" or \"slow\"")?;
+                }
+                "turns" => {
+                    let turns = value
+                        .as_int()
+                        .filter(|n| *n >= 0)
+                        .ok_or("'turns' must be a whole number (0 for endless)")?;
+                    self.turn_limit = (turns > 0).then_some(turns as u32);
+                }
+                "bell" => self.bell = flag(key, value)?,
+                "ascii" => self.ascii = flag(key, value)?,
+                "hotkeys" => self.hotkeys = flag(key, value)?,
+                "quiet_boot" => self.quiet_boot = flag(key, value)?,
+                "fast" => self.fast = flag(key, value)?,
+                "no_color" => self.no_color = flag(key, value)?,
+                _ => return Err(format!("unknown key '{}'", key)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the process arguments on top of whatever is already set.
+    fn apply_args<I: Iterator<Item = String>>(self, mut args: I) -> Result<Config, String> {
+        let mut config = self;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--roster" => {
+                    config.roster_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--roster requires a file path".to_string())?,
+                    );
+                }
+                "--transcript" => {
+                    config.transcript_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--transcript requires a file path".to_string())?,
+                    );
+                }
+                "--log-file" => {
+                    config.log_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--log-file requires a file path".to_string())?,
+                    );
+                }
+                "--aliases" => {
+                    config.aliases_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--aliases requires a file path".to_string())?,
+                    );
+                }
+                "--macro" => {
+                    config.macro_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--macro requires a file path".to_string())?,
+                    );
+                }
+                "--lang" => {
+                    config.lang = args
+                        .next()
+                        .ok_or_else(|| "--lang requires a language code or file".to_string())?;
+                }
+                "--ascii" => config.ascii = true,
+                "--no-numbers" => config.no_numbers = true,
+                "--no-override" => config.no_override = true,
+                "--hotkeys" => config.hotkeys = true,
+                "--quiet-boot" => config.quiet_boot = true,
+                "--fast" | "--no-anim" => config.fast = true,
+                "--no-color" => config.no_color = true,
+                "--list-scenarios" => config.list_scenarios = true,
+                "--list-commands" => config.list_commands = true,
+                "--continue" => config.resume = true,
+                "--attract" => config.attract = true,
+                "--debug" => config.debug = true,
+                "--batch" => config.batch = true,
+                "--seed" => {
+                    config.seed = Some(
+                        args.next()
+                            .and_then(|n| n.parse().ok())
+                            .ok_or_else(|| "--seed requires a number".to_string())?,
+                    );
+                }
+                "--mole-role" => {
+                    config.mole_role = Some(
+                        args.next()
+                            .and_then(|r| AdvisorRole::parse(&r))
+                            .ok_or_else(|| {
+                                "--mole-role requires general, director or ambassador".to_string()
+                            })?,
+                    );
+                }
+                "--autosave" => {
+                    config.autosave_every = args
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .ok_or_else(|| "--autosave requires a number of turns".to_string())?;
+                }
+                "--scenario" => {
+                    config.scenario = Some(
+                        args.next()
+                            .ok_or_else(|| "--scenario requires a name".to_string())?,
+                    );
+                }
+                "--archive-cap" => {
+                    config.archive_cap = args
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .ok_or_else(|| "--archive-cap requires a number".to_string())?;
+                }
+                "--difficulty" => {
+                    config.difficulty = args
+                        .next()
+                        .and_then(|d| Difficulty::parse(&d))
+                        .ok_or_else(|| "--difficulty requires easy, normal or hard".to_string())?;
+                }
+                "--volume" => {
+                    config.volume = args
+                        .next()
+                        .and_then(|v| Volume::parse(&v))
+                        .ok_or_else(|| "--volume requires low, normal or high".to_string())?;
+                }
+                "--start-tension" => config.start.tension = unit_arg(&mut args, &arg)?,
+                "--start-stability" => config.start.stability = unit_arg(&mut args, &arg)?,
+                "--start-secrecy" => config.start.secrecy = unit_arg(&mut args, &arg)?,
+                "--start-paranoia" => config.start.paranoia = unit_arg(&mut args, &arg)?,
+                "--start-secret-weapon" => config.start.secret_weapon = unit_arg(&mut args, &arg)?,
+                "--start-escalation-risk" => {
+                    config.start.escalation_risk = unit_arg(&mut args, &arg)?
+                }
+                "--start-corruption" => config.start.corruption = unit_arg(&mut args, &arg)?,
+                "--turns" => {
+                    let turns: u32 = args
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .ok_or_else(|| "--turns requires a number".to_string())?;
+                    config.turn_limit = (turns > 0).then_some(turns);
+                }
+                _ => return Err(format!("unknown option '{}'", arg)),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn flags_override_the_file_and_the_file_overrides_defaults() {
+        let mut config = Config::default();
+        config
+            .apply_file("difficulty = \"hard\"\nspeed = \"fast\"\nbell = true\nturns = 0\n")
+            .unwrap();
+        let config = config
+            .apply_args(args(&["--difficulty", "easy", "--no-anim", "--no-color"]))
+            .unwrap();
+        assert_eq!(config.difficulty, Difficulty::Easy);
+        assert!(config.fast);
+        assert!(config.no_color);
+        assert_eq!(config.feed_speed_ms, settings::feed_speed("fast").unwrap());
+        assert!(config.bell);
+        assert_eq!(config.turn_limit, None);
+        // Untouched by either.
+        assert_eq!(config.volume, Volume::Normal);
+    }
+
+    #[test]
+    fn an_empty_or_partial_file_keeps_the_defaults() {
+        let mut config = Config::default();
+        config.apply_file("# nothing yet\n").unwrap();
+        config.apply_file("volume = \"high\"").unwrap();
+        assert_eq!(config.volume, Volume::High);
+        assert_eq!(config.turn_limit, Some(DEFAULT_TURN_LIMIT));
+        assert_eq!(config.feed_speed_ms, DEFAULT_FEED_SPEED_MS);
+    }
+
+    #[test]
+    fn mistakes_in_the_file_are_reported() {
+        for (text, error) in [
+            ("colour = \"red\"", "unknown key 'colour'"),
+            ("bell = \"yes\"", "'bell' must be true or false"),
+            ("turns = -3", "'turns' must be"),
+            ("[game]\nturns = 3", "unexpected section [game]"),
+        ] {
+            let err = Config::default().apply_file(text).unwrap_err();
+            assert!(err.starts_with(error), "{}: {}", text, err);
+        }
+    }
+
+    #[test]
+    fn mole_role_takes_only_cabinet_roles() {
+        let config = Config::default()
+            .apply_args(args(&["--mole-role", "Director"]))
+            .unwrap();
+        assert_eq!(config.mole_role, Some(AdvisorRole::Director));
+        for bad in [&["--mole-role", "scientist"][..], &["--mole-role"]] {
+            let err = Config::default().apply_args(args(bad)).unwrap_err();
+            assert!(err.starts_with("--mole-role requires"), "{}", err);
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Crisis events and what each answer does to the world. The dashboard's
+//! handlers in `main.rs` stage the scene and read the answer; `--batch` runs
+//! feed answers straight from the script. Both resolve through here, so the
+//! two can never disagree about the numbers.
+
+use crate::achievement::Achievement;
+use crate::game::GameEngine;
+use crate::rng::Rng;
+use crate::state::{WorldState, COUP_THRESHOLD, DOUBLE_AGENT_TENURE};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Crisis {
+    RedPhone,
+    Hotline,
+    Coup,
+    Doppelganger,
+}
+
+impl Crisis {
+    /// The crisis that must be answered before play continues, if any. One
+    /// crisis can set off the next (a Red Phone call can topple stability),
+    /// so callers loop until this returns None.
+    pub fn pending(state: &WorldState) -> Option<Crisis> {
+        if state.red_phone_active {
+            Some(Crisis::RedPhone)
+        } else if state.hotline_active {
+            Some(Crisis::Hotline)
+        } else if state.coup_active {
+            Some(Crisis::Coup)
+        } else if state.doppelganger_active {
+            Some(Crisis::Doppelganger)
+        } else {
+            None
+        }
+    }
+}
+
+/// How an outcome reads, for coloring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tone {
+    Good,
+    Uneasy,
+    Dire,
+}
+
+/// What came of an answer, as one line of narration.
+pub struct Outcome {
+    pub tone: Tone,
+    pub text: &'static str,
+}
+
+fn outcome(tone: Tone, text: &'static str) -> Outcome {
+    Outcome { tone, text }
+}
+
+/// Whether the Red Phone call is the mole, unmasked, rather than the Premier.
+pub fn mole_on_the_line(state: &WorldState) -> bool {
+    state.advisors.iter().any(|a| a.suspicion >= 100)
+}
+
+/// Applies `answer` (a menu number or keyword; anything else counts as
+/// silence) to the pending `crisis` and closes it.
+pub fn resolve(
+    engine: &mut GameEngine,
+    crisis: Crisis,
+    answer: &str,
+    rng: &mut dyn Rng,
+) -> Outcome {
+    let answer = answer.trim().to_lowercase();
+    let outcome = match crisis {
+        Crisis::RedPhone if mole_on_the_line(&engine.state) => mole_call(engine, &answer),
+        Crisis::RedPhone => chernov_call(engine, &answer),
+        Crisis::Hotline => hotline(engine, &answer),
+        Crisis::Coup => coup(engine, &answer, rng),
+        Crisis::Doppelganger => doppelganger(engine, &answer, rng),
+    };
+
+    let state = &mut engine.state;
+    match crisis {
+        Crisis::RedPhone => state.red_phone_active = false,
+        Crisis::Hotline => state.hotline_active = false,
+        Crisis::Coup => state.coup_active = false,
+        Crisis::Doppelganger => {
+            state.doppelganger_active = false;
+            state.doppelganger_resolved = true;
+        }
+    }
+    state.clamp_metrics();
+    state.check_coup_trigger();
+    engine.crisis_fired();
+    outcome
+}
+
+fn mole_call(engine: &mut GameEngine, answer: &str) -> Outcome {
+    let flipped = !matches!(answer, "1" | "execute");
+    let result = if flipped {
+        engine.state.global_tension -= 0.3;
+        engine.state.internal_secrecy -= 0.1;
+        engine.state.accidental_escalation_risk += 0.1;
+        outcome(
+            Tone::Good,
+            "COMMAND: ASSET FLIPPED. THEY ARE FEEDING DISINFORMATION TO THE ENEMY.",
+        )
+    } else {
+        engine.state.domestic_stability += 0.3;
+        engine.state.foreign_paranoia += 0.2;
+        outcome(
+            Tone::Good,
+            "COMMAND: SECURITY TEAM DISPATCHED. TARGET NEUTRALIZED.",
+        )
+    };
+    if let Some(mole) = engine
+        .state
+        .advisors
+        .iter_mut()
+        .find(|a| a.suspicion >= 100)
+    {
+        mole.suspicion = 0;
+        mole.is_mole = false;
+        mole.is_double_agent = flipped;
+    }
+    if flipped {
+        engine.state.double_agent_turns = DOUBLE_AGENT_TENURE;
+    }
+    engine.award_mole_caught();
+    result
+}
+
+fn chernov_call(engine: &mut GameEngine, answer: &str) -> Outcome {
+    let state = &mut engine.state;
+    let result = match answer {
+        "1" | "deny" if state.foreign_paranoia > 0.7 => {
+            state.global_tension = 1.0;
+            outcome(Tone::Dire, "CHERNOV: LIAR! WE ARE LAUNCHING!")
+        }
+        "1" | "deny" => {
+            state.global_tension -= 0.2;
+            outcome(Tone::Uneasy, "CHERNOV: ...Fine. Turn them around. Now.")
+        }
+        "2" | "admit" => {
+            state.global_tension -= 0.5;
+            state.domestic_stability -= 0.3;
+            outcome(
+                Tone::Uneasy,
+                "CHERNOV: A bold admission. We will stand down, but there will be consequences.",
+            )
+        }
+        "3" | "threaten" => {
+            state.global_tension = 1.0;
+            outcome(Tone::Dire, "CHERNOV: THEN LET IT END!")
+        }
+        _ => {
+            state.global_tension = 1.0;
+            outcome(Tone::Dire, "CHERNOV: YOUR SILENCE IS DAMNING. LAUNCHING!")
+        }
+    };
+    if engine.state.global_tension < 1.0 {
+        engine.award(Achievement::CoolHead);
+    }
+    result
+}
+
+/// The terms the enemy offers on the hotline, as (offer, price at home).
+pub const HOTLINE_OFFERS: &[(&str, &str)] = &[
+    (
+        "WE PROPOSE A MUTUAL PULLBACK FROM THE BORDER. TEN KILOMETRES EACH. A TREATY, SIGNED TONIGHT.",
+        "THE HAWKS IN CONGRESS WILL CALL IT APPEASEMENT.",
+    ),
+    (
+        "WE HOLD TWO OF YOUR PILOTS. YOU HOLD ONE OF OUR DIPLOMATS. AN EXCHANGE AT DAWN, QUIETLY.",
+        "THE PRESS WILL ASK WHAT ELSE WAS TRADED.",
+    ),
+];
+
+fn hotline(engine: &mut GameEngine, answer: &str) -> Outcome {
+    let state = &mut engine.state;
+    match answer {
+        "1" | "accept" => {
+            state.global_tension -= 0.15;
+            state.domestic_stability -= 0.05;
+            outcome(Tone::Good, "CHERNOV: Good. Our people will be in touch.")
+        }
+        _ => {
+            state.foreign_paranoia += 0.15;
+            outcome(
+                Tone::Dire,
+                "CHERNOV: ...Then we understand each other. The offer will not be repeated.",
+            )
+        }
+    }
+}
+
+fn coup(engine: &mut GameEngine, answer: &str, rng: &mut dyn Rng) -> Outcome {
+    let state = &mut engine.state;
+    match answer {
+        "1" | "arrest" => {
+            // Loyal security services make the arrests stick.
+            let success_chance = 0.4 + state.internal_secrecy * 0.5;
+            state.internal_secrecy -= 0.15;
+            state.foreign_paranoia += 0.1;
+            if rng.random_bool(success_chance) {
+                state.domestic_stability = state.domestic_stability.max(COUP_THRESHOLD + 0.15);
+                outcome(
+                    Tone::Good,
+                    "COMMAND: RINGLEADERS IN CUSTODY. THE TANKS ARE TURNING BACK.",
+                )
+            } else {
+                state.domestic_stability = 0.0;
+                outcome(
+                    Tone::Dire,
+                    "COMMAND: THE ARREST TEAMS HAVE DEFECTED. THE PALACE HAS FALLEN.",
+                )
+            }
+        }
+        "2" | "concede" => {
+            state.global_tension -= 0.1;
+            state.internal_secrecy += 0.3;
+            state.domestic_stability = (state.domestic_stability + 0.25).max(COUP_THRESHOLD + 0.1);
+            outcome(
+                Tone::Uneasy,
+                "COMMAND: EMERGENCY DECREES SIGNED. THE JUNTA NOW SITS AT YOUR SIDE.",
+            )
+        }
+        _ => {
+            state.domestic_stability = 0.0;
+            outcome(
+                Tone::Dire,
+                "GENERAL STAFF: YOUR SILENCE IS ABDICATION. WE ASSUME COMMAND.",
+            )
+        }
+    }
+}
+
+fn doppelganger(engine: &mut GameEngine, answer: &str, rng: &mut dyn Rng) -> Outcome {
+    let state = &mut engine.state;
+    match answer {
+        "1" | "expose" => {
+            if rng.random_bool(0.3 + state.domestic_stability * 0.5) {
+                state.domestic_stability -= 0.15;
+                state.global_tension -= 0.1;
+                outcome(
+                    Tone::Good,
+                    "COMMAND: THE REAL PRESIDENT HAS BEEN FOUND IN A VIRGINIA SAFEHOUSE. THE NATION IS SHAKEN.",
+                )
+            } else {
+                state.domestic_stability -= 0.4;
+                state.foreign_paranoia += 0.15;
+                outcome(
+                    Tone::Dire,
+                    "COMMAND: THE BROADCAST LOOKS LIKE A PUTSCH. NOBODY BELIEVES US.",
+                )
+            }
+        }
+        "2" | "swap" => {
+            if rng.random_bool(state.internal_secrecy) {
+                state.internal_secrecy += 0.1;
+                outcome(
+                    Tone::Good,
+                    "COMMAND: EXTRACTION COMPLETE. THE PRESIDENT IS BACK AT HIS DESK. NO ONE SAW A THING.",
+                )
+            } else {
+                state.impostor_in_office = true;
+                outcome(
+                    Tone::Dire,
+                    "COMMAND: THE EXTRACTION TEAM NEVER REPORTED BACK. HE KNOWS WE KNOW.",
+                )
+            }
+        }
+        _ => {
+            state.impostor_in_office = true;
+            outcome(Tone::Uneasy, "COMMAND: UNDERSTOOD. THE FILE IS SEALED.")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answering_closes_the_crisis_and_starts_the_cooldown() {
+        let mut engine = GameEngine::new();
+        engine.start_turn();
+        engine.state.hotline_active = true;
+        assert_eq!(Crisis::pending(&engine.state), Some(Crisis::Hotline));
+
+        let tension = engine.state.global_tension;
+        let result = resolve(&mut engine, Crisis::Hotline, " ACCEPT ", &mut engine_rng());
+        assert_eq!(result.tone, Tone::Good);
+        assert!(engine.state.global_tension < tension);
+        assert_eq!(Crisis::pending(&engine.state), None);
+        assert_eq!(engine.last_crisis_turn, Some(1));
+    }
+
+    #[test]
+    fn silence_on_the_red_phone_is_war() {
+        let mut engine = GameEngine::new();
+        engine.state.red_phone_active = true;
+        resolve(&mut engine, Crisis::RedPhone, "", &mut engine_rng());
+        assert_eq!(engine.state.global_tension, 1.0);
+        assert!(engine.achievements.is_empty());
+    }
+
+    #[test]
+    fn a_failing_home_front_arms_the_coup_once() {
+        let mut state = WorldState::new();
+        state.domestic_stability = COUP_THRESHOLD;
+        state.check_coup_trigger();
+        assert!(!state.coup_attempted);
+
+        state.domestic_stability = COUP_THRESHOLD - 0.01;
+        state.check_coup_trigger();
+        assert!(state.coup_active && state.coup_attempted);
+        assert_eq!(Crisis::pending(&state), Some(Crisis::Coup));
+
+        // Put down once, the generals do not try again.
+        state.coup_active = false;
+        state.domestic_stability = 0.0;
+        state.check_coup_trigger();
+        assert!(!state.coup_active);
+    }
+
+    #[test]
+    fn a_crisis_settles_the_metrics_before_checking_for_a_coup() {
+        let mut engine = GameEngine::new();
+        engine.state.hotline_active = true;
+        engine.state.global_tension = 1.4;
+        engine.state.domestic_stability = -0.3;
+        resolve(&mut engine, Crisis::Hotline, "", &mut engine_rng());
+        assert!(engine.state.global_tension <= 1.0);
+        assert!(engine.state.domestic_stability >= 0.0);
+        assert!(engine.state.coup_active);
+        assert_eq!(Crisis::pending(&engine.state), Some(Crisis::Coup));
+    }
+
+    fn engine_rng() -> crate::rng::SimpleRng {
+        crate::rng::SimpleRng::with_seed(7)
+    }
+}
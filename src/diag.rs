@@ -0,0 +1,65 @@
+//! Developer diagnostics: one JSON object per line for each random branch
+//! the engine takes (`--log-file`). Not meant for players; see `transcript`
+//! for the readable session record.
+
+use std::fs::File;
+use std::io::{self, Write};
+
+pub struct DiagnosticLog {
+    out: File,
+}
+
+impl DiagnosticLog {
+    pub fn create(path: &str) -> io::Result<Self> {
+        Ok(Self {
+            out: File::create(path)?,
+        })
+    }
+
+    /// Writes `{"turn":..,"event":..,<fields>}`. Field values must already be
+    /// JSON (use `quote` for strings). Lines are written whole and unbuffered
+    /// so an abrupt exit loses nothing.
+    pub fn record(&mut self, turn: u32, event: &str, fields: &[(&str, String)]) {
+        let mut line = format!("{{\"turn\":{},\"event\":{}", turn, quote(event));
+        for (key, value) in fields {
+            line.push_str(&format!(",{}:{}", quote(key), value));
+        }
+        line.push_str("}\n");
+        // Diagnostics must never take the game down with them.
+        let _ = self.out.write_all(line.as_bytes());
+    }
+}
+
+/// Encodes `s` as a JSON string literal.
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Encodes a list of strings as a JSON array.
+pub fn quote_list<'a, I: IntoIterator<Item = &'a str>>(items: I) -> String {
+    let quoted: Vec<String> = items.into_iter().map(quote).collect();
+    format!("[{}]", quoted.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_escapes_json_specials() {
+        assert_eq!(quote("SAY \"HI\"\\\n"), "\"SAY \\\"HI\\\"\\\\\\n\"");
+        assert_eq!(quote_list(["A", "B"]), "[\"A\",\"B\"]");
+    }
+}
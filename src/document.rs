@@ -1,5 +1,5 @@
-use crate::rng::SimpleRng;
-use crate::state::{AdvisorRole, WorldState};
+use crate::rng::Rng;
+use crate::state::{AdvisorRole, EnemyPosture, WorldState};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DocumentType {
@@ -9,37 +9,293 @@ pub enum DocumentType {
     ForeignIntercept,
     AnonymousLeak,
     AdvisorMessage, // New type
+    /// Civilian wire copy: mostly filler, now and then a real clue.
+    NewsWire,
 }
 
+impl DocumentType {
+    /// Human-readable type name for analysis reports.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocumentType::IntelligenceCable => "INTELLIGENCE CABLE",
+            DocumentType::InternalMemo => "INTERNAL MEMO",
+            DocumentType::BudgetAnomaly => "BUDGET ANOMALY",
+            DocumentType::ForeignIntercept => "FOREIGN INTERCEPT",
+            DocumentType::AnonymousLeak => "ANONYMOUS LEAK",
+            DocumentType::AdvisorMessage => "ADVISOR MESSAGE",
+            DocumentType::NewsWire => "NEWS WIRE",
+        }
+    }
+}
+
+/// Share of foreign intercepts that carry the enemy's standing orders.
+const POSTURE_INTERCEPT_CHANCE: f64 = 0.4;
+
+/// Share of encrypted cables flagged as priority.
+const PRIORITY_CHANCE: f64 = 0.3;
+
+/// Clearance of the long ciphers that take several decrypts to crack.
+pub const COSMIC_CLEARANCE: &str = "COSMIC TOP SECRET";
+
+/// Share of non-priority ciphertext sent under cosmic-grade cipher.
+const COSMIC_CHANCE: f64 = 0.2;
+
+/// Decrypts a cosmic-grade cipher takes, one intel each.
+const COSMIC_DECRYPT_COST: u32 = 3;
+
+/// What analysis reports for a source the mole has vouched for.
+const VOUCHED_RELIABILITY: f64 = 0.88;
+
+/// Share of routine (non-priority) ciphertext that hides only station
+/// chatter or system noise.
+const CIPHER_NOISE_CHANCE: f64 = 0.3;
+
+/// Openings of every crucial-intel message; see `generate_crucial_intel`.
+const CRUCIAL_PREFIXES: [&str; 6] = [
+    "ANALYSIS:",
+    "SURVEILLANCE:",
+    "POLLS:",
+    "PROJECT BASILISK:",
+    "R&D:",
+    "EYES ONLY:",
+];
+
 #[derive(Debug, Clone)]
 pub struct Document {
     pub id: String,
-    #[allow(dead_code)]
     pub doc_type: DocumentType,
     pub clearance_level: String,
     pub timestamp: String,
     pub content: String,
     pub is_encrypted: bool,
-    #[allow(dead_code)]
     pub reliability: f64,
+    /// Time-critical ciphertext: lost for good if still encrypted when the turn ends.
+    pub priority: bool,
+    /// Decrypts already sunk into the cipher; see `decrypt_cost`.
+    pub decrypt_progress: u32,
+    /// The mole has vouched for a shaky source: analysis reads it as verified.
+    pub vouched: bool,
+}
+
+/// What intelligence cables report on.
+const CABLE_SUBJECTS: &[&str] = &[
+    "BORDER SECTOR 4",
+    "NORTH SEA FLOTILLA",
+    "EASTERN BLOC GARRISON",
+    "SATELLITE GRID",
+    "SUBMARINE WOLF-PACK",
+];
+
+/// Weapon progress above which the civilian wires start picking up the Project.
+const NEWS_CLUE_WEAPON: f64 = 0.6;
+
+/// Wire stories that are really about the Project, if you know how to read them.
+const NEWS_CLUES: &[&str] = &[
+    "AP: NOBEL-TIPPED PHYSICIST MISSING AFTER FACULTY DINNER. COLLEAGUES SAY SHE 'WENT QUIET' FOR MONTHS.",
+    "UPI: UNEXPLAINED BLACKOUT DARKENS THREE COUNTIES. UTILITY DENIES ANY FAULT ON ITS LINES.",
+    "REUTERS: RANCHERS REPORT CATTLE STAMPEDING AWAY FROM A FENCED GOVERNMENT SITE AT NIGHT.",
+    "AP: SUPERCOMPUTER MAKER RECALLS STAFF FROM HOLIDAY. SPOKESMAN CITES 'AN UNUSUAL CONTRACT'.",
+];
+
+/// Words that make a readable document sound like an emergency.
+const ALARM_WORDS: [&str; 8] = [
+    "FLASH", "CRITICAL", "ALERT", "LAUNCH", "STRIKE", "COUP", "BASILISK", "MOLE",
+];
+
+/// Produces the body text for one document type. Implemented for plain
+/// functions and closures so mods can register `|state, rng, reliability| ...`.
+pub trait DocumentGenerator {
+    fn generate(&self, state: &WorldState, rng: &mut dyn Rng, reliability: f64) -> String;
+}
+
+impl<F> DocumentGenerator for F
+where
+    F: Fn(&WorldState, &mut dyn Rng, f64) -> String,
+{
+    fn generate(&self, state: &WorldState, rng: &mut dyn Rng, reliability: f64) -> String {
+        self(state, rng, reliability)
+    }
+}
+
+/// Content generators keyed by document type. The built-ins are registered by
+/// default; when a type has several generators, one is picked at random.
+pub struct GeneratorRegistry {
+    generators: Vec<(DocumentType, Box<dyn DocumentGenerator>)>,
+    /// Share of plaintext cables replaced by numbers stations and ghost messages.
+    pub anomaly_chance: f64,
+    /// Of those anomalies, the share that are numbers stations.
+    pub station_share: f64,
+    /// Chance a numbers station slips the mole's initial into its broadcast.
+    pub station_hint_chance: f64,
+    /// Share of the feed given over to civilian news wires: the noise floor.
+    pub news_share: f64,
+    /// Chance a news wire carries a real clue once the Project is far along:
+    /// the signal in that noise.
+    pub news_clue_chance: f64,
+}
+
+impl Default for GeneratorRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            generators: Vec::new(),
+            anomaly_chance: 0.15,
+            station_share: 0.5,
+            station_hint_chance: 0.35,
+            news_share: 0.15,
+            news_clue_chance: 0.25,
+        };
+        registry.register(DocumentType::IntelligenceCable, generate_cable_content);
+        registry.register(DocumentType::InternalMemo, generate_memo_content);
+        registry.register(DocumentType::BudgetAnomaly, generate_budget_content);
+        registry.register(DocumentType::ForeignIntercept, generate_intercept_content);
+        registry.register(DocumentType::AnonymousLeak, generate_leak_content);
+        registry.register(DocumentType::NewsWire, generate_news_content);
+        registry.register(
+            DocumentType::AdvisorMessage,
+            |state: &WorldState, rng: &mut dyn Rng, _reliability: f64| {
+                generate_advisor_content(state, rng)
+            },
+        );
+        registry
+    }
+}
+
+impl GeneratorRegistry {
+    /// Adds a generator for `doc_type` alongside any already registered.
+    pub fn register(
+        &mut self,
+        doc_type: DocumentType,
+        generator: impl DocumentGenerator + 'static,
+    ) {
+        self.generators.push((doc_type, Box::new(generator)));
+    }
+
+    fn generate(
+        &self,
+        doc_type: &DocumentType,
+        state: &WorldState,
+        rng: &mut dyn Rng,
+        reliability: f64,
+    ) -> String {
+        let candidates: Vec<&dyn DocumentGenerator> = self
+            .generators
+            .iter()
+            .filter(|(t, _)| t == doc_type)
+            .map(|(_, g)| g.as_ref())
+            .collect();
+
+        // Only roll when there is a choice, so the stock stream is unchanged.
+        let generator = match candidates.len() {
+            0 => return generate_ghost_message(state, rng),
+            1 => candidates[0],
+            n => candidates[rng.range(0, n as u64) as usize],
+        };
+        generator.generate(state, rng, reliability)
+    }
 }
 
 impl Document {
-    pub fn generate_batch(state: &WorldState, count: usize, turn_count: u32) -> Vec<Document> {
-        let mut rng = SimpleRng::new();
+    /// Decrypts needed to read this document.
+    pub fn decrypt_cost(&self) -> u32 {
+        if self.clearance_level == COSMIC_CLEARANCE {
+            COSMIC_DECRYPT_COST
+        } else {
+            1
+        }
+    }
+
+    /// A cipher that has been started but not cracked. It survives the end of
+    /// the turn so the work already done is not lost.
+    pub fn partly_decrypted(&self) -> bool {
+        self.is_encrypted && self.decrypt_progress > 0
+    }
+
+    /// The reliability analysis reports: the truth, unless the mole vouched.
+    pub fn apparent_reliability(&self) -> f64 {
+        if self.vouched {
+            self.reliability.max(VOUCHED_RELIABILITY)
+        } else {
+            self.reliability
+        }
+    }
+
+    /// A cipher cracked all the way through.
+    pub fn was_decrypted(&self) -> bool {
+        !self.is_encrypted && self.decrypt_progress > 0
+    }
+
+    /// The content as far as it has been cracked: the decrypted share in
+    /// clear, the rest as cipher noise.
+    pub fn partial_text(&self, rng: &mut dyn Rng) -> String {
+        if !self.is_encrypted {
+            return self.content.clone();
+        }
+        let chars = self.content.chars().count();
+        let clear = chars * self.decrypt_progress as usize / self.decrypt_cost() as usize;
+        let split = self
+            .content
+            .char_indices()
+            .nth(clear)
+            .map_or(self.content.len(), |(i, _)| i);
+        format!(
+            "{}{}",
+            &self.content[..split],
+            scramble_text(&self.content[split..], rng)
+        )
+    }
+
+    /// How the cabinet would refer to this document in conversation, e.g.
+    /// "that intelligence cable about the NORTH SEA FLOTILLA".
+    pub fn reference(&self) -> String {
+        let kind = self.doc_type.label().to_lowercase();
+        let topic = CABLE_SUBJECTS
+            .iter()
+            .find(|s| self.content.contains(*s))
+            .map(|s| format!("the {}", s))
+            .or_else(|| quoted_after(&self.content, "SHELL COMPANY").map(|c| format!("'{}'", c)))
+            .or_else(|| {
+                self.content
+                    .contains("BASILISK")
+                    .then(|| "the Project".to_string())
+            });
+        match topic {
+            Some(topic) => format!("that {} about {}", kind, topic),
+            None => format!("that {} ({})", kind, self.id),
+        }
+    }
+
+    /// Readable and written like an emergency. Ciphertext has no readable tone.
+    pub fn is_alarmist(&self) -> bool {
+        !self.is_encrypted && ALARM_WORDS.iter().any(|w| self.content.contains(w))
+    }
+
+    pub fn generate_batch(
+        state: &WorldState,
+        count: usize,
+        turn_count: u32,
+        generators: &GeneratorRegistry,
+        rng: &mut dyn Rng,
+    ) -> Vec<Document> {
         let mut docs = Vec::new();
 
         for _ in 0..count {
-            docs.push(Self::generate_single(state, &mut rng, turn_count));
+            docs.push(Self::generate_single(state, rng, turn_count, generators));
         }
 
         docs
     }
 
-    fn generate_single(state: &WorldState, rng: &mut SimpleRng, turn_count: u32) -> Document {
+    fn generate_single(
+        state: &WorldState,
+        rng: &mut dyn Rng,
+        turn_count: u32,
+        generators: &GeneratorRegistry,
+    ) -> Document {
         // Weighted generation: Advisor messages are relatively common
         let roll = rng.range(0, 100);
-        let doc_type = if roll < 20 {
+        let doc_type = if rng.random_bool(generators.news_share) {
+            DocumentType::NewsWire
+        } else if roll < 20 {
             DocumentType::AdvisorMessage
         } else if roll < 40 {
             DocumentType::IntelligenceCable
@@ -54,7 +310,7 @@ impl Document {
         };
 
         let reliability = 0.3 + (rng.next_f64() * 0.65);
-        let mut id = format!("DOC-{:04X}", rng.range(0, 0xFFFF));
+        let mut id = draw_id(rng);
 
         let mut is_encrypted = false;
         // SCALING ENCRYPTION DIFFICULTY
@@ -68,44 +324,55 @@ impl Document {
             0.8
         };
 
-        // Advisor messages are never encrypted (they are "trusted")
+        // Advisor messages are never encrypted (they are "trusted"), and
+        // nobody enciphers the wire services.
         if !matches!(
             doc_type,
-            DocumentType::AnonymousLeak | DocumentType::AdvisorMessage
-        ) {
-            if rng.random_bool(encryption_chance) {
-                is_encrypted = true;
-            }
+            DocumentType::AnonymousLeak | DocumentType::AdvisorMessage | DocumentType::NewsWire
+        ) && rng.random_bool(encryption_chance)
+        {
+            is_encrypted = true;
         }
 
+        let priority = is_encrypted && rng.random_bool(PRIORITY_CHANCE);
+        // A long cipher would be hopeless against a closing window.
+        let cosmic = is_encrypted && !priority && rng.random_bool(COSMIC_CHANCE);
+
         let content = if is_encrypted {
-            generate_crucial_intel(state, rng)
+            // Priority traffic is always the real thing.
+            if !priority && rng.random_bool(CIPHER_NOISE_CHANCE) {
+                if rng.random_bool(generators.station_share) {
+                    generate_numbers_station(state, rng, generators.station_hint_chance)
+                } else {
+                    generate_ghost_message(state, rng)
+                }
+            } else {
+                generate_crucial_intel(state, rng)
+            }
         } else if matches!(doc_type, DocumentType::AdvisorMessage) {
-            generate_advisor_content(state, rng)
-        } else if rng.random_bool(0.15) {
-            if rng.random_bool(0.5) {
+            generators.generate(&doc_type, state, rng, reliability)
+        } else if doc_type == DocumentType::NewsWire
+            && state.secret_weapon_progress > NEWS_CLUE_WEAPON
+            && rng.random_bool(generators.news_clue_chance)
+        {
+            rng.choose(NEWS_CLUES).to_string()
+        } else if rng.random_bool(generators.anomaly_chance) {
+            if rng.random_bool(generators.station_share) {
                 id = "SIGNAL-???".to_string();
-                generate_numbers_station(rng)
+                generate_numbers_station(state, rng, generators.station_hint_chance)
             } else {
                 generate_ghost_message(state, rng)
             }
         } else {
-            match doc_type {
-                DocumentType::IntelligenceCable => generate_cable_content(state, rng, reliability),
-                DocumentType::InternalMemo => generate_memo_content(state, rng, reliability),
-                DocumentType::BudgetAnomaly => generate_budget_content(state, rng, reliability),
-                DocumentType::ForeignIntercept => {
-                    generate_intercept_content(state, rng, reliability)
-                }
-                DocumentType::AnonymousLeak => generate_leak_content(state, rng, reliability),
-                DocumentType::AdvisorMessage => generate_advisor_content(state, rng), // Fallback
-            }
+            generators.generate(&doc_type, state, rng, reliability)
         };
 
         let clearance = match doc_type {
+            _ if cosmic => COSMIC_CLEARANCE,
             DocumentType::BudgetAnomaly => "CONFIDENTIAL",
             DocumentType::AnonymousLeak => "UNVERIFIED",
             DocumentType::AdvisorMessage => "EYES ONLY",
+            DocumentType::NewsWire => "UNCLASSIFIED",
             _ => "TOP SECRET",
         };
 
@@ -113,22 +380,247 @@ impl Document {
             id,
             doc_type,
             clearance_level: clearance.to_string(),
-            timestamp: format!(
-                "198{:01}-1{:01}-{:02} {:02}:{:02}Z",
-                rng.range(0, 9),
-                rng.range(0, 3),
-                rng.range(1, 28),
-                rng.range(0, 23),
-                rng.range(0, 59)
-            ),
+            timestamp: random_timestamp(rng),
             content,
             is_encrypted,
             reliability,
+            priority,
+            decrypt_progress: 0,
+            vouched: false,
+        }
+    }
+
+    /// The advisor a readable advisor memo claims to come from.
+    pub fn sender(&self) -> Option<&str> {
+        if self.doc_type != DocumentType::AdvisorMessage || self.is_encrypted {
+            return None;
+        }
+        self.content
+            .strip_prefix("FROM: ")?
+            .split_once(" // ")
+            .map(|(name, _)| name)
+    }
+
+    /// Whether the content (readable or not) is one of the crucial-intel
+    /// messages rather than station chatter, system noise or routine traffic.
+    pub fn carries_crucial_intel(&self) -> bool {
+        CRUCIAL_PREFIXES
+            .iter()
+            .any(|prefix| self.content.starts_with(prefix))
+    }
+
+    /// Full intelligence workup for the Analyze directive: reliability band,
+    /// provenance, and a note cross-referencing the content with the world.
+    /// Ciphertext gets a traffic-pattern read on whether it is worth decrypting.
+    pub fn analysis(&self, state: &WorldState) -> Vec<String> {
+        let integrity = (self.apparent_reliability() * 100.0) as u32;
+        let assessment = if integrity > 80 {
+            "HIGH (VERIFIED)"
+        } else if integrity > 50 {
+            "MODERATE (UNCERTAIN)"
+        } else {
+            "LOW (POSSIBLE DISINFORMATION)"
+        };
+
+        let mut report = vec![
+            format!("ANALYSIS COMPLETE: DOCUMENT {}", self.id),
+            format!(
+                "TYPE: {} // CLEARANCE: {}",
+                self.doc_type.label(),
+                self.clearance_level
+            ),
+            format!("SOURCE RELIABILITY: {}% - {}", integrity, assessment),
+        ];
+
+        let note = if self.is_encrypted {
+            if self.carries_crucial_intel() {
+                "CONTENT ENCRYPTED. TRAFFIC PATTERN MATCHES COMMAND CHANNELS. DECRYPTION RECOMMENDED."
+                    .to_string()
+            } else {
+                "CONTENT ENCRYPTED. TRAFFIC PATTERN MATCHES AUTOMATED CHATTER. LIKELY NOT WORTH THE INTEL."
+                    .to_string()
+            }
+        } else if self.id.starts_with("SIGNAL") {
+            "UNREGISTERED CARRIER. ORIGIN CANNOT BE ATTRIBUTED.".to_string()
+        } else {
+            match self.doc_type {
+                DocumentType::BudgetAnomaly => match quoted_after(&self.content, "SHELL COMPANY") {
+                    Some(company) => format!(
+                        "FUNDS ROUTED THROUGH '{}'. RECOMMEND FORENSIC AUDIT.",
+                        company
+                    ),
+                    None => "NO SHELL ENTITY IDENTIFIED.".to_string(),
+                },
+                DocumentType::ForeignIntercept => {
+                    let alarmist = ["STRIKE", "LAUNCH", "PREEMPT", "WEAK", "MOLE"]
+                        .iter()
+                        .any(|w| self.content.contains(w));
+                    if alarmist == (state.foreign_paranoia > 0.6) {
+                        "TONE CONSISTENT WITH CURRENT ENEMY PARANOIA.".to_string()
+                    } else {
+                        "TONE INCONSISTENT WITH OBSERVED ENEMY POSTURE. POSSIBLE PLANT.".to_string()
+                    }
+                }
+                DocumentType::IntelligenceCable => {
+                    let urgent = ["FLASH", "CRITICAL", "ALERT"]
+                        .iter()
+                        .any(|w| self.content.starts_with(w));
+                    if urgent && state.global_tension < 0.5 {
+                        "URGENCY EXCEEDS MEASURED TENSION. FIELD STATION MAY BE EXAGGERATING."
+                            .to_string()
+                    } else if !urgent && state.global_tension > 0.7 {
+                        "CALM REPORT DURING A CRISIS. STATION MAY BE COMPROMISED.".to_string()
+                    } else {
+                        "URGENCY CONSISTENT WITH MEASURED TENSION.".to_string()
+                    }
+                }
+                DocumentType::InternalMemo => {
+                    if self.content.starts_with("HOME FRONT") {
+                        format!(
+                            "DOMESTIC UNREST REPORT. HOME FRONT CURRENTLY {}.",
+                            state.stability_band()
+                        )
+                    } else if self.content.contains("BASILISK") {
+                        "REFERENCES PROJECT BASILISK. COVER STORY IN CIRCULATION.".to_string()
+                    } else {
+                        "ADMINISTRATIVE TRAFFIC. NO OPERATIONAL VALUE.".to_string()
+                    }
+                }
+                DocumentType::AnonymousLeak => {
+                    "SOURCE UNVERIFIABLE BY DESIGN. PUBLIC REACTION IS THE REAL SIGNAL.".to_string()
+                }
+                DocumentType::AdvisorMessage => {
+                    "ORIGINATES INSIDE THE CABINET. CROSS-CHECK AGAINST CONSULT.".to_string()
+                }
+                DocumentType::NewsWire if NEWS_CLUES.contains(&self.content.as_str()) => {
+                    "CIVILIAN STORY OVERLAPS PROJECT BASILISK ACTIVITY. NOT A COINCIDENCE."
+                        .to_string()
+                }
+                DocumentType::NewsWire => "CIVILIAN WIRE COPY. NO INTELLIGENCE VALUE.".to_string(),
+            }
+        };
+        report.push(format!("NOTE: {}", note));
+        report
+    }
+
+    /// A near-certain intercept passed along by a flipped mole.
+    pub fn double_agent_report(state: &WorldState, agent: &str, rng: &mut dyn Rng) -> Document {
+        let report = if state.foreign_paranoia > 0.6 {
+            "THEIR HIGH COMMAND EXPECTS A FIRST STRIKE. A GESTURE OF RESTRAINT WOULD LAND (CONTAIN)."
+        } else if state.global_tension > 0.6 {
+            "THE POLITBURO IS SPLIT. ONE MORE PROVOCATION AND THE HAWKS WIN (DO NOT ESCALATE)."
+        } else {
+            "NO OFFENSIVE ORDERS ISSUED. THEY ARE WATCHING OUR PROJECT, NOT OUR BORDERS."
+        };
+
+        Document {
+            id: draw_id(rng),
+            doc_type: DocumentType::ForeignIntercept,
+            clearance_level: "EYES ONLY".to_string(),
+            timestamp: random_timestamp(rng),
+            content: format!("ASSET {} REPORTS: {}", agent.to_uppercase(), report),
+            is_encrypted: false,
+            reliability: 0.95,
+            priority: false,
+            decrypt_progress: 0,
+            vouched: false,
+        }
+    }
+}
+
+/// How much a document deserves a full analysis: ciphertext, high clearance,
+/// alarming wording and shaky sourcing all push it up. Pure so the ranking
+/// can be tested without an engine.
+pub fn triage_score(doc: &Document) -> f64 {
+    let mut score = 0.0;
+    if doc.is_encrypted {
+        score += 3.0;
+    }
+    score += match doc.clearance_level.as_str() {
+        COSMIC_CLEARANCE | "EYES ONLY" => 3.0,
+        "TOP SECRET" => 2.0,
+        "CONFIDENTIAL" => 1.0,
+        _ => 0.0,
+    };
+    if doc.is_alarmist() {
+        score += 2.0;
+    }
+    // Shaky sources are the ones analysis pays off on.
+    score + (1.0 - doc.apparent_reliability()) * 2.0
+}
+
+/// The single document most worth analyzing, if any.
+pub fn triage(docs: &[Document]) -> Option<&Document> {
+    docs.iter()
+        .max_by(|a, b| triage_score(a).total_cmp(&triage_score(b)))
+}
+
+/// A fresh `DOC-` filing number. Nothing here stops a repeat; the engine
+/// moves one that is already on file along to a free number.
+pub fn draw_id(rng: &mut dyn Rng) -> String {
+    format!("DOC-{:04X}", rng.range(0, 0xFFFF))
+}
+
+/// A single character of cipher noise.
+pub fn random_char(rng: &mut dyn Rng) -> char {
+    let chars = b"0123456789ABCDEFXZ@#&";
+    let idx = rng.range(0, chars.len() as u64) as usize;
+    chars[idx] as char
+}
+
+/// Replaces every non-space character with cipher noise, keeping word shapes.
+pub fn scramble_text(text: &str, rng: &mut dyn Rng) -> String {
+    let mut s = String::new();
+    for c in text.chars() {
+        if c.is_whitespace() {
+            s.push(' ');
+        } else {
+            s.push(random_char(rng));
         }
     }
+    s
 }
 
-fn generate_advisor_content(state: &WorldState, rng: &mut SimpleRng) -> String {
+/// The frames of a decryption reveal: cipher noise resolving into `text` one
+/// character at a time, the unsolved tail re-rolled each frame and whitespace
+/// kept in place. Worked on chars throughout, so multibyte text lines up; the
+/// last frame is `text` itself.
+pub fn decryption_frames(text: &str, rng: &mut dyn Rng) -> Vec<String> {
+    let target: Vec<char> = text.chars().collect();
+    (1..=target.len())
+        .map(|solved| {
+            let noise = target[solved..].iter().map(|&c| {
+                if c.is_whitespace() {
+                    c
+                } else {
+                    random_char(rng)
+                }
+            });
+            target[..solved].iter().copied().chain(noise).collect()
+        })
+        .collect()
+}
+
+/// The single-quoted token following `marker`, e.g. the company in "SHELL COMPANY 'X'".
+fn quoted_after<'a>(content: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = &content[content.find(marker)? + marker.len()..];
+    let start = rest.find('\'')? + 1;
+    let len = rest[start..].find('\'')?;
+    Some(&rest[start..start + len])
+}
+
+fn random_timestamp(rng: &mut dyn Rng) -> String {
+    format!(
+        "198{:01}-1{:01}-{:02} {:02}:{:02}Z",
+        rng.range(0, 9),
+        rng.range(0, 3),
+        rng.range(1, 28),
+        rng.range(0, 23),
+        rng.range(0, 59)
+    )
+}
+
+fn generate_advisor_content(state: &WorldState, rng: &mut dyn Rng) -> String {
     // Pick a random advisor
     let advisor_idx = rng.range(0, state.advisors.len() as u64) as usize;
     let advisor = &state.advisors[advisor_idx];
@@ -162,7 +654,7 @@ fn generate_advisor_content(state: &WorldState, rng: &mut SimpleRng) -> String {
     format!("{} // \"{}\"", prefix, msg)
 }
 
-fn generate_crucial_intel(state: &WorldState, rng: &mut SimpleRng) -> String {
+fn generate_crucial_intel(state: &WorldState, rng: &mut dyn Rng) -> String {
     let roll = rng.range(0, 10);
 
     if roll < 3 {
@@ -191,16 +683,112 @@ fn generate_crucial_intel(state: &WorldState, rng: &mut SimpleRng) -> String {
     }
 }
 
-fn generate_numbers_station(rng: &mut SimpleRng) -> String {
-    let mut s = "BROADCAST DETECTED: ".to_string();
-    for _ in 0..6 {
-        s.push_str(&format!("{:02} ", rng.range(0, 99)));
+/// NATO spelling alphabet, A to Z.
+const PHONETIC: [&str; 26] = [
+    "ALFA", "BRAVO", "CHARLIE", "DELTA", "ECHO", "FOXTROT", "GOLF", "HOTEL", "INDIA", "JULIETT",
+    "KILO", "LIMA", "MIKE", "NOVEMBER", "OSCAR", "PAPA", "QUEBEC", "ROMEO", "SIERRA", "TANGO",
+    "UNIFORM", "VICTOR", "WHISKEY", "XRAY", "YANKEE", "ZULU",
+];
+
+/// International Morse, A to Z.
+const MORSE: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+
+/// The initial of the mole's surname, as a numbers station would spell it.
+/// None once there is no active mole to give away.
+fn mole_initial(state: &WorldState) -> Option<char> {
+    let mole = state
+        .advisors
+        .iter()
+        .find(|a| a.is_mole && !a.is_double_agent)?;
+    mole.name
+        .split_whitespace()
+        .last()?
+        .chars()
+        .find(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+}
+
+/// A numbers-station broadcast in one of several styles. With
+/// `hint_chance`, the station slips in the mole's initial, each style in
+/// its own tell: a `00`-framed group holds its place in the alphabet, a
+/// phonetic word is sent twice, a Morse letter stands alone between
+/// slashes, the voice lingers on one letter.
+fn generate_numbers_station(state: &WorldState, rng: &mut dyn Rng, hint_chance: f64) -> String {
+    let style = rng.range(0, 4);
+    let hint = if rng.random_bool(hint_chance) {
+        mole_initial(state)
+    } else {
+        None
+    };
+    numbers_broadcast(style, hint, rng)
+}
+
+fn numbers_broadcast(style: u64, hint: Option<char>, rng: &mut dyn Rng) -> String {
+    let letter = hint.map(|c| (c as u8 - b'A') as usize);
+    match style {
+        0 => {
+            let mut s = "BROADCAST DETECTED: ".to_string();
+            for _ in 0..6 {
+                // Never 00, so the framing stays unambiguous.
+                s.push_str(&format!("{:02} ", rng.range(1, 99)));
+            }
+            if let Some(i) = letter {
+                s.push_str(&format!("00 {:02} 00 ", i + 1));
+            }
+            s.push_str("... [REPEATING]");
+            s
+        }
+        1 => {
+            // No word twice in a row, and never the hint's word, so the
+            // doubled word is the only one.
+            let hinted = letter.map(|i| PHONETIC[i]);
+            let mut words: Vec<&str> = Vec::new();
+            while words.len() < 6 {
+                let word = *rng.choose(&PHONETIC);
+                if words.last() != Some(&word) && hinted != Some(word) {
+                    words.push(word);
+                }
+            }
+            if let Some(word) = hinted {
+                let at = rng.range(1, 6) as usize;
+                words.insert(at, word);
+                words.insert(at, word);
+            }
+            format!("VOICE BROADCAST: {} ... [REPEATING]", words.join(" "))
+        }
+        2 => {
+            let mut groups: Vec<&str> = (0..5).map(|_| *rng.choose(&MORSE)).collect();
+            if let Some(i) = letter {
+                let at = rng.range(1, 4) as usize;
+                groups[at] = MORSE[i];
+                let tail = groups.split_off(at + 1);
+                let head = groups[..at].join(" ");
+                return format!(
+                    "CW CARRIER DETECTED: {} // {} // {} [REPEATING]",
+                    head,
+                    MORSE[i],
+                    tail.join(" ")
+                );
+            }
+            format!("CW CARRIER DETECTED: {} [REPEATING]", groups.join(" "))
+        }
+        _ => match letter {
+            Some(i) => format!(
+                "BROADCAST DETECTED: A CHILD'S VOICE RECITES THE ALPHABET AND STOPS AT '{}'. \
+                 SHE SAYS IT AGAIN. THEN A MUSIC BOX. [REPEATING]",
+                (b'A' + i as u8) as char
+            ),
+            None => "BROADCAST DETECTED: A CHILD'S VOICE RECITES THE ALPHABET, \
+                     THEN A MUSIC BOX. [REPEATING]"
+                .to_string(),
+        },
     }
-    s.push_str("... [REPEATING]");
-    s
 }
 
-fn generate_ghost_message(state: &WorldState, rng: &mut SimpleRng) -> String {
+fn generate_ghost_message(state: &WorldState, rng: &mut dyn Rng) -> String {
     if state.secret_weapon_progress > 0.5 {
         match rng.range(0, 4) {
             0 => "SYSTEM ALERT: UNKNOWN PROCESS 'BASILISK' REQUESTING ROOT ACCESS.".to_string(),
@@ -215,17 +803,10 @@ fn generate_ghost_message(state: &WorldState, rng: &mut SimpleRng) -> String {
     }
 }
 
-fn generate_cable_content(state: &WorldState, rng: &mut SimpleRng, reliability: f64) -> String {
+fn generate_cable_content(state: &WorldState, rng: &mut dyn Rng, reliability: f64) -> String {
     let tension_perceived =
         state.global_tension * (1.0 + (rng.next_f64() - 0.5) * (1.0 - reliability));
 
-    let subjects = [
-        "BORDER SECTOR 4",
-        "NORTH SEA FLOTILLA",
-        "EASTERN BLOC GARRISON",
-        "SATELLITE GRID",
-        "SUBMARINE WOLF-PACK",
-    ];
     let action = [
         "TROOP MOVEMENTS",
         "HEAT SIGNATURES",
@@ -234,8 +815,8 @@ fn generate_cable_content(state: &WorldState, rng: &mut SimpleRng, reliability:
         "FUEL LOADING",
     ];
 
-    let subject = subjects[rng.range(0, subjects.len() as u64) as usize];
-    let act = action[rng.range(0, action.len() as u64) as usize];
+    let subject = *rng.choose(CABLE_SUBJECTS);
+    let act = *rng.choose(&action);
 
     if tension_perceived > 0.7 {
         let templates = [
@@ -243,7 +824,7 @@ fn generate_cable_content(state: &WorldState, rng: &mut SimpleRng, reliability:
              format!("CRITICAL: {} ACTIVE. COMMANDER REQUESTS PERMISSION TO ENGAGE IF PROVOKED.", subject),
              format!("ALERT: INTERCEPTED ORDER TO {} UNITS. 'PREPARE FOR ZERO HOUR'.", subject),
         ];
-        templates[rng.range(0, templates.len() as u64) as usize].clone()
+        rng.choose(&templates).clone()
     } else if tension_perceived > 0.4 {
         let templates = [
             format!(
@@ -259,7 +840,7 @@ fn generate_cable_content(state: &WorldState, rng: &mut SimpleRng, reliability:
                 subject
             ),
         ];
-        templates[rng.range(0, templates.len() as u64) as usize].clone()
+        rng.choose(&templates).clone()
     } else {
         let templates = [
             format!(
@@ -275,11 +856,16 @@ fn generate_cable_content(state: &WorldState, rng: &mut SimpleRng, reliability:
                 subject
             ),
         ];
-        templates[rng.range(0, templates.len() as u64) as usize].clone()
+        rng.choose(&templates).clone()
     }
 }
 
-fn generate_memo_content(state: &WorldState, rng: &mut SimpleRng, _reliability: f64) -> String {
+fn generate_memo_content(state: &WorldState, rng: &mut dyn Rng, _reliability: f64) -> String {
+    // The worse things are at home, the more of the internal traffic is about it.
+    if rng.random_bool((1.0 - state.domestic_stability) * 0.7) {
+        return generate_unrest_memo(state, rng);
+    }
+
     if rng.random_bool(0.3 + state.secret_weapon_progress * 0.5) {
         let anomaly_events = [
             "ENERGY CONSUMPTION EXCEEDING GRID CAPACITIES",
@@ -288,7 +874,7 @@ fn generate_memo_content(state: &WorldState, rng: &mut SimpleRng, _reliability:
             "PERSONNEL REPORTING AUDITORY HALLUCINATIONS",
             "AUTOMATED TURRETS TRACKING GHOST TARGETS",
         ];
-        let event = anomaly_events[rng.range(0, anomaly_events.len() as u64) as usize];
+        let event = *rng.choose(&anomaly_events);
 
         format!(
             "RE: PROJECT BASILISK. {}. COVER STORY 'INDUSTRIAL ACCIDENT' PREPARED.",
@@ -302,12 +888,35 @@ fn generate_memo_content(state: &WorldState, rng: &mut SimpleRng, _reliability:
             "BUDGET CUTS AFFECTING JANITORIAL STAFF",
             "LOST ID BADGE FOUND IN PARKING LOT",
         ];
-        let topic = admin_topics[rng.range(0, admin_topics.len() as u64) as usize];
+        let topic = *rng.choose(&admin_topics);
         format!("ADMIN: {}. PLEASE ADVISE.", topic)
     }
 }
 
-fn generate_budget_content(_state: &WorldState, rng: &mut SimpleRng, _reliability: f64) -> String {
+fn generate_unrest_memo(state: &WorldState, rng: &mut dyn Rng) -> String {
+    let events: &[&str] = if state.domestic_stability < 0.3 {
+        &[
+            "GARRISON OFFICERS REFUSING TO DISPERSE CROWDS",
+            "RIOTS IN THREE PROVINCIAL CAPITALS. POLICE STATIONS BURNING",
+            "SENIOR MINISTRY STAFF DEFECTING WITH FILES",
+            "GENERAL STRIKE HAS SHUT DOWN THE RAIL NETWORK",
+        ]
+    } else {
+        &[
+            "DOCKWORKERS WALKING OUT OVER RATIONING",
+            "STUDENT PROTEST OUTSIDE THE MINISTRY OF INFORMATION",
+            "BREAD QUEUES TURNING VIOLENT IN THE INDUSTRIAL DISTRICT",
+            "MID-LEVEL DIPLOMAT FAILED TO RETURN FROM POSTING",
+        ]
+    };
+    let event = *rng.choose(events);
+    format!(
+        "HOME FRONT: {}. INTERIOR MINISTRY REQUESTS GUIDANCE.",
+        event
+    )
+}
+
+fn generate_budget_content(_state: &WorldState, rng: &mut dyn Rng, _reliability: f64) -> String {
     let cost = rng.range(50, 500);
     let departments = [
         "AGRICULTURAL SUBSIDIES",
@@ -324,8 +933,8 @@ fn generate_budget_content(_state: &WorldState, rng: &mut SimpleRng, _reliabilit
         "SILVER SPEAR INC",
     ];
 
-    let dept = departments[rng.range(0, departments.len() as u64) as usize];
-    let company = shell_companies[rng.range(0, shell_companies.len() as u64) as usize];
+    let dept = *rng.choose(&departments);
+    let company = *rng.choose(&shell_companies);
 
     format!(
         "AUDIT FLAG: ${}M UNACCOUNTED FOR IN '{}'. TRACED TO SHELL COMPANY '{}'.",
@@ -333,10 +942,35 @@ fn generate_budget_content(_state: &WorldState, rng: &mut SimpleRng, _reliabilit
     )
 }
 
-fn generate_intercept_content(state: &WorldState, rng: &mut SimpleRng, reliability: f64) -> String {
+fn generate_intercept_content(state: &WorldState, rng: &mut dyn Rng, reliability: f64) -> String {
     let paranoia_perceived =
         state.foreign_paranoia * (1.0 + (rng.next_f64() - 0.5) * (1.0 - reliability));
 
+    // Their standing orders leak through, though only a solid source gets them right.
+    if rng.random_bool(POSTURE_INTERCEPT_CHANCE) {
+        let posture = if rng.random_bool(reliability) {
+            state.enemy_posture
+        } else {
+            EnemyPosture::Probing
+        };
+        let orders: &[&str] = match posture {
+            EnemyPosture::Aggressive => &[
+                "...ALL FORWARD DIVISIONS TO FULL READINESS. THEIR PEACE OFFERS ARE A TRICK...",
+                "...THEY ONLY UNDERSTAND STRENGTH. DOUBLE THE BORDER PATROLS...",
+            ],
+            EnemyPosture::Probing => &[
+                "...TEST THEIR RADAR COVERAGE AGAIN TONIGHT. LOG THE RESPONSE TIMES...",
+                "...SEND THE TRAWLER CLOSER. SEE HOW FAR THEY LET IT COME...",
+            ],
+            EnemyPosture::Cautious => &[
+                "...THE POLITBURO URGES RESTRAINT. DO NOT GIVE THEM A PRETEXT...",
+                "...RECALL THE SUBMARINE PICKETS. THEY ARE SIGNALLING CALM...",
+            ],
+        };
+        let order = *rng.choose(orders);
+        return format!("DECRYPTED: \"{}\"", order);
+    }
+
     if paranoia_perceived > 0.6 {
         let threats = [
             "...THEY ARE PREPARING A STRIKE. WE MUST BE READY TO PREEMPT...",
@@ -344,7 +978,7 @@ fn generate_intercept_content(state: &WorldState, rng: &mut SimpleRng, reliabili
             "...LAUNCH CODES VERIFIED. AWAITING FINAL AUTHORIZATION...",
             "...THEY KNOW ABOUT THE MOLE. INITIATE EXTRACTION...",
         ];
-        let threat = threats[rng.range(0, threats.len() as u64) as usize];
+        let threat = *rng.choose(&threats);
         format!("DECRYPTED: \"{}\"", threat)
     } else {
         let chatter = [
@@ -353,12 +987,12 @@ fn generate_intercept_content(state: &WorldState, rng: &mut SimpleRng, reliabili
             "...GENERAL IVANOV IS DRUNK AGAIN. IGNORE HIS ORDERS...",
             "...REQUESTING TRANSFER TO A WARMER CLIMATE...",
         ];
-        let chat = chatter[rng.range(0, chatter.len() as u64) as usize];
+        let chat = *rng.choose(&chatter);
         format!("DECRYPTED: \"{}\"", chat)
     }
 }
 
-fn generate_leak_content(state: &WorldState, rng: &mut SimpleRng, _reliability: f64) -> String {
+fn generate_leak_content(state: &WorldState, rng: &mut dyn Rng, _reliability: f64) -> String {
     if state.internal_secrecy > 0.7 {
         let leaks = [
             "\"THE GOVERNMENT IS LYING ABOUT THE SCOPE OF THE PROGRAM. IT'S NOT DEFENSIVE.\"",
@@ -366,7 +1000,7 @@ fn generate_leak_content(state: &WorldState, rng: &mut SimpleRng, _reliability:
             "\"WE ARE NOT IN CONTROL. THE MACHINE IS THINKING FOR ITSELF.\"",
             "\"THEY ARE TESTING IT ON PRISONERS. I HAVE PROOF.\"",
         ];
-        let leak = leaks[rng.range(0, leaks.len() as u64) as usize];
+        let leak = *rng.choose(&leaks);
         format!("WHISTLEBLOWER: {}", leak)
     } else {
         let rumors = [
@@ -375,7 +1009,225 @@ fn generate_leak_content(state: &WorldState, rng: &mut SimpleRng, _reliability:
             "\"ENCRYPTED BROADCASTS INTERRUPTING CARTOON HOUR.\"",
             "\"LOCAL WATER SUPPLY TASTES LIKE COPPER.\"",
         ];
-        let rumor = rumors[rng.range(0, rumors.len() as u64) as usize];
+        let rumor = *rng.choose(&rumors);
         format!("RUMOR MILL: {}", rumor)
     }
 }
+
+fn generate_news_content(_state: &WorldState, rng: &mut dyn Rng, _reliability: f64) -> String {
+    let stories = [
+        "AP: ORIOLES TAKE GAME FIVE. BALTIMORE DECLARES A HALF-HOLIDAY.",
+        "UPI: COLD SNAP GRIPS THE MIDWEST. GRAIN FUTURES EDGE UP.",
+        "REUTERS: SUBMARINE-THEMED VIDEO GAME DRAWS LINES AROUND THE BLOCK IN TOKYO.",
+        "AP: COUNTY FAIR PRIZE PUMPKIN WEIGHS IN AT 600 POUNDS.",
+        "UPI: NETWORK TV MOVIE ABOUT NUCLEAR WAR DRAWS RECORD AUDIENCE.",
+        "REUTERS: HEATWAVE BREAKS OVER SOUTHERN EUROPE. TOURISTS RETURN TO THE BEACHES.",
+        "AP: SYNTHESIZER POP TOPS THE CHARTS FOR A SIXTH STRAIGHT WEEK.",
+        "UPI: SHUTTLE CREW WAVES FROM THE GANTRY. LIFTOFF SET FOR DAWN.",
+    ];
+    rng.choose(&stories).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SimpleRng;
+
+    fn plain_doc(doc_type: DocumentType, content: &str) -> Document {
+        Document {
+            id: "DOC-0001".to_string(),
+            doc_type,
+            clearance_level: "CONFIDENTIAL".to_string(),
+            timestamp: "1983-10-01 00:00Z".to_string(),
+            content: content.to_string(),
+            is_encrypted: false,
+            reliability: 0.9,
+            priority: false,
+            decrypt_progress: 0,
+            vouched: false,
+        }
+    }
+
+    #[test]
+    fn only_readable_memos_have_a_sender() {
+        let mut memo = plain_doc(
+            DocumentType::AdvisorMessage,
+            "FROM: Director K. // \"Too many eyes on us.\"",
+        );
+        assert_eq!(memo.sender(), Some("Director K."));
+        memo.is_encrypted = true;
+        assert_eq!(memo.sender(), None);
+        let cable = plain_doc(DocumentType::IntelligenceCable, "FROM: NOBODY // HELLO");
+        assert_eq!(cable.sender(), None);
+    }
+
+    #[test]
+    fn numbers_stations_can_spell_the_mole() {
+        let mut rng = SimpleRng::with_seed(5);
+        for _ in 0..20 {
+            let tells = [
+                (0, "00 22 00"),
+                (1, "VICTOR VICTOR"),
+                (2, "// ...- //"),
+                (3, "STOPS AT 'V'"),
+            ];
+            for (style, tell) in tells {
+                let hinted = numbers_broadcast(style, Some('V'), &mut rng);
+                assert!(hinted.contains(tell), "{}", hinted);
+                assert!(hinted.ends_with("[REPEATING]"));
+                let plain = numbers_broadcast(style, None, &mut rng);
+                assert!(
+                    !plain.contains(" 00 ") && !plain.contains("//"),
+                    "{}",
+                    plain
+                );
+                assert!(!plain.contains("STOPS AT"));
+            }
+        }
+
+        let mut state = WorldState::new();
+        for advisor in state.advisors.iter_mut() {
+            advisor.is_mole = advisor.name == "Amb. Sterling";
+        }
+        assert_eq!(mole_initial(&state), Some('S'));
+        state.advisors[2].is_double_agent = true;
+        assert_eq!(mole_initial(&state), None);
+    }
+
+    #[test]
+    fn news_wires_are_tunable_noise_with_the_odd_clue() {
+        let mut generators = GeneratorRegistry {
+            news_share: 1.0,
+            news_clue_chance: 1.0,
+            ..GeneratorRegistry::default()
+        };
+        let mut state = WorldState::new();
+        let mut rng = SimpleRng::with_seed(11);
+
+        let quiet = Document::generate_batch(&state, 10, 9, &generators, &mut rng);
+        for doc in &quiet {
+            assert_eq!(doc.doc_type, DocumentType::NewsWire);
+            assert_eq!(doc.clearance_level, "UNCLASSIFIED");
+            assert!(!doc.is_encrypted);
+            assert!(!NEWS_CLUES.contains(&doc.content.as_str()));
+            assert!(doc.analysis(&state)[3].contains("NO INTELLIGENCE VALUE"));
+        }
+
+        state.secret_weapon_progress = 0.8;
+        let clue = &Document::generate_batch(&state, 1, 9, &generators, &mut rng)[0];
+        assert!(clue.analysis(&state)[3].contains("PROJECT BASILISK"));
+
+        generators.news_share = 0.0;
+        let feed = Document::generate_batch(&state, 20, 9, &generators, &mut rng);
+        assert!(feed.iter().all(|d| d.doc_type != DocumentType::NewsWire));
+    }
+
+    #[test]
+    fn analysis_tells_crucial_ciphertext_from_chatter() {
+        let state = WorldState::new();
+        let mut rng = SimpleRng::with_seed(3);
+        for _ in 0..20 {
+            let mut cipher = plain_doc(
+                DocumentType::IntelligenceCable,
+                &generate_crucial_intel(&state, &mut rng),
+            );
+            cipher.is_encrypted = true;
+            assert!(cipher.analysis(&state)[3].contains("DECRYPTION RECOMMENDED"));
+
+            cipher.content = generate_numbers_station(&state, &mut rng, 0.5);
+            assert!(cipher.analysis(&state)[3].contains("NOT WORTH THE INTEL"));
+            cipher.content = generate_ghost_message(&state, &mut rng);
+            assert!(!cipher.carries_crucial_intel());
+        }
+    }
+
+    #[test]
+    fn analysis_names_the_shell_company() {
+        let doc = plain_doc(
+            DocumentType::BudgetAnomaly,
+            "AUDIT FLAG: $120M UNACCOUNTED FOR IN 'FOREIGN AID'. TRACED TO SHELL COMPANY 'OMEGA GROUP'.",
+        );
+        let report = doc.analysis(&WorldState::new());
+        assert!(report[1].contains("BUDGET ANOMALY"));
+        assert!(report[1].contains("CONFIDENTIAL"));
+        assert!(report[2].contains("HIGH (VERIFIED)"));
+        assert!(report[3].contains("'OMEGA GROUP'"));
+    }
+
+    #[test]
+    fn analysis_flags_intercepts_out_of_step_with_paranoia() {
+        let doc = plain_doc(
+            DocumentType::ForeignIntercept,
+            "DECRYPTED: \"...LAUNCH CODES VERIFIED. AWAITING FINAL AUTHORIZATION...\"",
+        );
+        let mut state = WorldState::new();
+        state.foreign_paranoia = 0.2;
+        assert!(doc.analysis(&state)[3].contains("POSSIBLE PLANT"));
+        state.foreign_paranoia = 0.9;
+        assert!(doc.analysis(&state)[3].starts_with("NOTE: TONE CONSISTENT"));
+    }
+
+    #[test]
+    fn registered_generators_join_the_rotation() {
+        let mut registry = GeneratorRegistry::default();
+        registry.register(
+            DocumentType::InternalMemo,
+            |_: &WorldState, _: &mut dyn Rng, _: f64| "MODDED MEMO".to_string(),
+        );
+
+        let state = WorldState::new();
+        let mut rng = SimpleRng::new();
+        let modded = (0..200)
+            .map(|_| registry.generate(&DocumentType::InternalMemo, &state, &mut rng, 0.5))
+            .filter(|c| c == "MODDED MEMO")
+            .count();
+        assert!(modded > 0 && modded < 200);
+    }
+
+    #[test]
+    fn unrest_memos_track_domestic_stability() {
+        let mut state = WorldState::new();
+        let mut rng = SimpleRng::new();
+        let unrest = |state: &WorldState, rng: &mut dyn Rng| {
+            (0..200)
+                .map(|_| generate_memo_content(state, rng, 0.5))
+                .filter(|c| c.starts_with("HOME FRONT"))
+                .count()
+        };
+
+        state.domestic_stability = 1.0;
+        assert_eq!(unrest(&state, &mut rng), 0);
+        state.domestic_stability = 0.1;
+        assert!(unrest(&state, &mut rng) > 50);
+    }
+
+    #[test]
+    fn triage_prefers_encrypted_high_clearance_cables() {
+        let memo = plain_doc(DocumentType::InternalMemo, "ADMIN: LOST ID BADGE.");
+        let mut cable = plain_doc(DocumentType::IntelligenceCable, "...");
+        cable.id = "DOC-0002".to_string();
+        cable.clearance_level = "TOP SECRET".to_string();
+        cable.is_encrypted = true;
+
+        let docs = vec![memo, cable];
+        assert_eq!(triage(&docs).unwrap().id, "DOC-0002");
+        assert!(triage(&[]).is_none());
+    }
+
+    #[test]
+    fn decryption_frames_line_up_on_multibyte_text() {
+        let mut rng = SimpleRng::with_seed(9);
+        let target = "café — über\u{a0}naïve";
+        let frames = decryption_frames(target, &mut rng);
+        let width = target.chars().count();
+        assert_eq!(frames.len(), width);
+        for (solved, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.chars().count(), width, "{}", frame);
+            let shown: String = frame.chars().take(solved + 1).collect();
+            let wanted: String = target.chars().take(solved + 1).collect();
+            assert_eq!(shown, wanted);
+        }
+        assert_eq!(frames.last().map(String::as_str), Some(target));
+        assert!(decryption_frames("", &mut rng).is_empty());
+    }
+}
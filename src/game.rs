@@ -1,9 +1,152 @@
-use crate::document::Document;
-use crate::rng::SimpleRng;
-use crate::state::{AdvisorRole, WorldState};
+use crate::achievement::{Achievement, EARLY_CATCH_TURN};
+use crate::archive::{Archive, DEFAULT_ARCHIVE_CAP};
+use crate::diag::{self, DiagnosticLog};
+use crate::document::{scramble_text, triage, Document, DocumentType, GeneratorRegistry};
+use crate::rng::{Rng, SimpleRng};
+use crate::state::{
+    Advisor, AdvisorRole, Ending, EnemyPosture, WorldState, DEFCON1_TENSION, DOPPELGANGER_FUSE,
+};
+use crate::stats::RunStats;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Default shift length in turns.
+pub const DEFAULT_TURN_LIMIT: u32 = 20;
+
+/// Intel spent on a trace with no live interruption to lock onto.
+const COLD_TRACE_COST: u32 = 2;
+
+/// Chance a cold trace reads the suspect correctly (either way).
+const COLD_TRACE_ACCURACY: f64 = 0.7;
+
+/// Suspicion a cold trace's partial match adds, up to `EVIDENCE_CEILING`.
+const COLD_TRACE_SUSPICION: u32 = 20;
+
+/// Chance a decrypt comes back garbled at full system corruption. Interference
+/// starts at 50% corruption and ramps up linearly to this.
+const DECRYPT_FAILURE_MAX: f64 = 0.4;
+
+/// Default suspicion an innocent advisor sheds each turn.
+pub const DEFAULT_SUSPICION_DECAY: u32 = 5;
+
+/// Default suspicion the mole gains each turn while secrecy is low.
+pub const DEFAULT_MOLE_CREEP: u32 = 3;
+
+/// Secrecy below which the mole starts slipping up.
+const MOLE_SLOPPY_SECRECY: f64 = 0.4;
+
+/// Passive creep never pushes the mole past this; exposure still takes a
+/// trace or an interrogation.
+const MOLE_CREEP_CEILING: u32 = 80;
+
+/// Tension added for each priority cable left encrypted at the end of its turn.
+const PRIORITY_LOSS_TENSION: f64 = 0.05;
+
+/// How far tension and stability together must move across a turn before
+/// the advice it followed counts as proven right or wrong.
+const COUNSEL_MARGIN: f64 = 0.08;
+
+/// Suspicion an advisor earns when events contradict the advice taken.
+const COUNSEL_WRONG_SUSPICION: u32 = 10;
+
+/// Suspicion an advisor sheds when events bear the advice out.
+const COUNSEL_RIGHT_RELIEF: u32 = 5;
+
+/// Suspicion an advisor earns when analysis flags their memo as off-message.
+const MEMO_SUSPICION: u32 = 6;
+
+/// Chance analysis flags a memo from the mole, and from a loyal advisor.
+const MEMO_FLAG_CHANCE: (f64, f64) = (0.6, 0.15);
+
+/// Indirect evidence never amounts to proof: it stops short of exposure.
+const EVIDENCE_CEILING: u32 = 90;
+
+/// Turns a mole lies low before sabotaging the intel pipeline.
+const SABOTAGE_GRACE_TURNS: u32 = 4;
+
+/// Ceiling on the per-turn sabotage chance.
+const SABOTAGE_MAX_CHANCE: f64 = 0.5;
+
+/// Suspicion at which the mole stops risking sabotage.
+const SABOTAGE_SUSPICION: u32 = 70;
+
+/// Reliability below which the mole will vouch for a source.
+const SABOTAGE_VOUCH_BELOW: f64 = 0.5;
+
+/// Feedback for document directives issued against an empty inbox.
+const NO_DOCUMENTS: &str = "NOTICE: NO DOCUMENTS IN THE INBOX THIS CYCLE. NO INTEL SPENT.";
+
+/// Hard: chance the mole's signature reads clean on a live trace (once per game).
+const HARD_MOLE_MASK_CHANCE: f64 = 0.35;
+
+/// Hard: false-positive chance per point of system corruption on a live trace.
+const HARD_FALSE_POSITIVE_SCALE: f64 = 0.3;
+
+/// How many leaked headlines the news ticker keeps in rotation.
+const MAX_TICKER_LEAKS: usize = 4;
+
+/// How many resolved directives the session history keeps. A full shift uses
+/// a fraction of it; an endless one forgets its first days instead of growing.
+pub const HISTORY_CAP: usize = 500;
+
+/// Turns the tribunal fallout lingers after a stand-down.
+const STANDDOWN_AFTERMATH_TURNS: u32 = 3;
+
+/// Stability lost per remaining aftermath turn, so the bleed tapers off.
+const STANDDOWN_BLEED: f64 = 0.03;
+
+/// Intel squeezed out of an overclocked decoder.
+const OVERCLOCK_INTEL: u32 = 2;
+
+/// Corruption the overclock lets into the system.
+const OVERCLOCK_CORRUPTION: f64 = 0.08;
+
+/// Chance the overheated decoders garble the next turn's cables.
+const OVERCLOCK_GARBLE_CHANCE: f64 = 0.5;
+
+/// Turns after a crisis during which the Red Phone cannot ring by chance.
+const RED_PHONE_COOLDOWN: u32 = 2;
+
+/// Per-turn chance the enemy calls to offer a way down, when the mood allows.
+const HOTLINE_CHANCE: f64 = 0.12;
+
+/// Tension band in which the enemy is worried enough to call but not yet
+/// past talking.
+const HOTLINE_TENSION: (f64, f64) = (0.4, 0.7);
+
+/// The enemy only offers terms while paranoia stays below this.
+const HOTLINE_MAX_PARANOIA: f64 = 0.4;
+
+/// Ways any advisor, loyal or not, flags a cable. `{doc}` is the cable's
+/// reference.
+const CABLE_WORRIES: [&str; 3] = [
+    "{Doc} worries me.",
+    "{Doc} is worth a second look.",
+    "I keep coming back to {doc}.",
+];
+
+/// Ways any advisor waves a cable off or passes it to a colleague. `{who}`
+/// is another member of the cabinet.
+const CABLE_SHRUGS: [&str; 3] = [
+    "Ignore {doc}. It's noise.",
+    "{Doc}? I wouldn't lose sleep over it.",
+    "{Doc}? Ask {who} who fed it to us.",
+];
+
+/// How many turn-ending directives the enemy remembers when picking a posture.
+const POSTURE_MEMORY: usize = 3;
+
+/// An alarm from a source below this reliability is likely disinformation.
+const FALSE_INTEL_RELIABILITY: f64 = 0.4;
+
+/// Extra escalation risk for escalating on an unchecked, unreliable alarm.
+const FALSE_INTEL_RISK: f64 = 0.15;
+
+/// Ledger action for intel handed back after a failed lookup.
+const REFUND: &str = "refund";
 
 /// Represents the possible commands a player can issue to the engine.
-#[derive(PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Directive {
     /// Increases tension and paranoia, but may force enemy submission.
     Escalate,
@@ -19,12 +162,279 @@ pub enum Directive {
     Decrypt(String),
     /// Spend Intel to verify the reliability of a document.
     Analyze(String),
+    /// Spend Intel to name the pending document most worth analyzing.
+    Triage,
     /// Spend Intel to trace the signal source to a specific advisor.
     Trace(String),
     /// Ask an advisor for their recommendation (Costs Intel).
     Consult(String),
     /// Aggressively question an advisor. High risk, high info.
     Interrogate(String),
+    /// Ask every advisor at once for a single, scaled Intel cost.
+    RoundTable,
+    /// Push the decryption hardware past spec for extra Intel. Feeds the Basilisk.
+    Overclock,
+}
+
+impl Directive {
+    /// Command-line style name used in transcripts.
+    pub fn label(&self) -> String {
+        match self {
+            Directive::Escalate => "ESCALATE".to_string(),
+            Directive::Investigate => "INVESTIGATE".to_string(),
+            Directive::Contain => "CONTAIN".to_string(),
+            Directive::Leak => "LEAK".to_string(),
+            Directive::StandDown => "STAND-DOWN".to_string(),
+            Directive::Decrypt(id) => format!("DECRYPT {}", id),
+            Directive::Analyze(id) => format!("ANALYZE {}", id),
+            Directive::Triage => "ANALYZE --TRIAGE".to_string(),
+            Directive::Trace(name) => format!("TRACE {}", name),
+            Directive::Consult(name) => format!("CONSULT {}", name),
+            Directive::Interrogate(name) => format!("INTERROGATE {}", name),
+            Directive::RoundTable => "ROUNDTABLE".to_string(),
+            Directive::Overclock => "OVERCLOCK".to_string(),
+        }
+    }
+
+    /// How provocative the enemy finds a turn-ending directive, from -2
+    /// (withdrawal) to +2 (escalation). None for actions they never see.
+    fn stance(&self) -> Option<i32> {
+        match self {
+            Directive::Escalate => Some(2),
+            Directive::Investigate => Some(1),
+            Directive::Leak => Some(0),
+            Directive::Contain => Some(-1),
+            Directive::StandDown => Some(-2),
+            _ => None,
+        }
+    }
+
+    /// What the directive does, mirroring the numbers in `resolve_directive`.
+    /// Shown by `preview`; keep the two in step.
+    pub fn effects(&self) -> &'static [&'static str] {
+        match self {
+            Directive::Escalate => &[
+                "60%: TENSION +0.20, PARANOIA +0.20, STABILITY +0.05",
+                "40%: MISCOMMUNICATION. TENSION +0.35, ESCALATION RISK +0.15",
+                "ON THE HEELS OF AN UNANALYZED, UNRELIABLE ALARM: ESCALATION RISK +0.15",
+                "ENDS THE TURN",
+            ],
+            Directive::Investigate => &[
+                "SECRECY -0.10, WEAPON PROGRESS +0.15",
+                "50%: ESCALATION RISK -0.10",
+                "ENDS THE TURN",
+            ],
+            Directive::Contain => &[
+                "PARANOIA UP TO 0.60: TENSION -0.15, STABILITY -0.10",
+                "PARANOIA ABOVE 0.60: FAILS. TENSION +0.10",
+                "THE BAR DROPS TO 0.45 AGAINST AN AGGRESSIVE ENEMY, RISES TO 0.75 AGAINST A CAUTIOUS ONE",
+                "ENDS THE TURN",
+            ],
+            Directive::Leak => &[
+                "SECRECY -0.25, STABILITY +0.20, PARANOIA -0.05",
+                "ENDS THE TURN",
+            ],
+            Directive::StandDown => &[
+                "TENSION -0.40, PARANOIA -0.30, STABILITY -0.35",
+                "NEXT 3 TURNS: STABILITY BLEEDS (-0.09, -0.06, -0.03), CABINET WITHHOLDS 1 INTEL",
+                "ENDS THE TURN",
+            ],
+            Directive::Decrypt(_) => &[
+                "COSTS 1 INTEL. FREE IF THE DOCUMENT IS MISSING OR ALREADY READABLE",
+                "HIGH SYSTEM CORRUPTION MAY GARBLE THE RESULT",
+                "COSMIC TOP SECRET CIPHERS TAKE 3 DECRYPTS; PROGRESS CARRIES OVER TO LATER TURNS",
+                "TURN CONTINUES",
+            ],
+            Directive::Analyze(_) => &[
+                "COSTS 1 INTEL. REFUNDED IF THE DOCUMENT IS MISSING",
+                "REPORTS TYPE, CLEARANCE, SOURCE RELIABILITY AND A CONTEXTUAL NOTE",
+                "TURN CONTINUES",
+            ],
+            Directive::Triage => &[
+                "COSTS 1 INTEL",
+                "NAMES THE PENDING DOCUMENT MOST WORTH ANALYZING",
+                "TURN CONTINUES",
+            ],
+            Directive::Trace(_) => &[
+                "LIVE INTERRUPTION: 1 INTEL. CONFIRMS OR CLEARS THE TARGET",
+                "NO INTERRUPTION: 2 INTEL. LOW-CONFIDENCE HINT ONLY",
+                "MAX 2 PER TURN, ONCE PER ADVISOR. TURN CONTINUES",
+            ],
+            Directive::Consult(_) => &[
+                "FIRST CONSULT EACH TURN IS FREE, THEN 1 INTEL",
+                "THE MOLE'S ADVICE IS NOT TO BE TRUSTED",
+                "TURN CONTINUES",
+            ],
+            Directive::Interrogate(_) => &[
+                "COSTS 2 INTEL. MAX 2 PER TURN, ONCE PER ADVISOR",
+                "MAY RATTLE THE MOLE; INNOCENTS TAKE OFFENSE (SMALL STABILITY, SECRECY OR PARANOIA COST)",
+                "TURN CONTINUES",
+            ],
+            Directive::RoundTable => &[
+                "COSTS HALF THE CABINET SIZE IN INTEL, ROUNDED UP",
+                "EVERY ADVISOR GIVES A RECOMMENDATION",
+                "TURN CONTINUES",
+            ],
+            Directive::Overclock => &[
+                "INTEL +2, SYSTEM CORRUPTION +0.08",
+                "50%: TOMORROW'S CABLES ARRIVE GARBLED",
+                "ONCE PER TURN. TURN CONTINUES",
+            ],
+        }
+    }
+}
+
+/// How many cables arrive each turn (`--volume`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Volume {
+    Low,
+    Normal,
+    High,
+}
+
+impl Volume {
+    pub fn parse(name: &str) -> Option<Volume> {
+        match name.to_lowercase().as_str() {
+            "low" => Some(Volume::Low),
+            "normal" => Some(Volume::Normal),
+            "high" => Some(Volume::High),
+            _ => None,
+        }
+    }
+
+    /// Scales the turn's base document count. Never drops below two so the
+    /// forced encrypted cable is not the only thing on the wire.
+    fn scale_docs(self, base: usize) -> usize {
+        match self {
+            Volume::Low => base.saturating_sub(1).max(2),
+            Volume::Normal => base,
+            Volume::High => base + 2,
+        }
+    }
+
+    /// Extra intel so a heavier feed is still workable.
+    fn bonus_intel(self) -> u32 {
+        match self {
+            Volume::High => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// Difficulty preset (`--difficulty`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        match name.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// Added per turn to the chance an unexposed mole sabotages intel, once
+    /// they have gone unnoticed past the grace period.
+    fn sabotage_ramp(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.02,
+            Difficulty::Normal => 0.04,
+            Difficulty::Hard => 0.06,
+        }
+    }
+
+    /// Per-turn (innocent decay, mole creep) for suspicion drift.
+    fn suspicion_drift(self) -> (u32, u32) {
+        match self {
+            Difficulty::Easy => (8, 5),
+            Difficulty::Normal => (DEFAULT_SUSPICION_DECAY, DEFAULT_MOLE_CREEP),
+            Difficulty::Hard => (3, 2),
+        }
+    }
+}
+
+/// The latest alarmist document put in front of the player, remembered so an
+/// escalation can be judged against what prompted it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlarmContext {
+    pub doc_id: String,
+    pub reliability: f64,
+    /// Whether the player checked the source before acting.
+    pub analyzed: bool,
+}
+
+impl AlarmContext {
+    fn from_document(doc: &Document) -> Self {
+        Self {
+            doc_id: doc.id.clone(),
+            reliability: doc.reliability,
+            analyzed: false,
+        }
+    }
+}
+
+/// A recommendation heard this turn, to be weighed against events if the
+/// player follows it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counsel {
+    pub advisor: String,
+    /// The recommended directive, as the advisor put it (`STAND DOWN`).
+    pub verb: String,
+}
+
+/// One directive as the player issued it and what came back.
+pub struct TurnRecord {
+    pub turn: u32,
+    pub directive: String,
+    pub feedback: Vec<String>,
+    /// Headline metrics right after the directive, for the post-mortem arc.
+    pub tension: f64,
+    pub stability: f64,
+    pub weapon: f64,
+}
+
+/// Change in each world metric across a single directive (after - before).
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct MetricDeltas {
+    pub global_tension: f64,
+    pub internal_secrecy: f64,
+    pub foreign_paranoia: f64,
+    pub accidental_escalation_risk: f64,
+    pub domestic_stability: f64,
+    pub secret_weapon_progress: f64,
+    pub system_corruption: f64,
+}
+
+/// Outcome of `resolve_directive` with world snapshots taken around it.
+#[cfg(test)]
+pub struct DirectiveResult {
+    pub feedback: Vec<String>,
+    pub turn_ended: bool,
+    pub before: WorldState,
+    pub after: WorldState,
+}
+
+#[cfg(test)]
+impl DirectiveResult {
+    pub fn deltas(&self) -> MetricDeltas {
+        let (b, a) = (&self.before, &self.after);
+        MetricDeltas {
+            global_tension: a.global_tension - b.global_tension,
+            internal_secrecy: a.internal_secrecy - b.internal_secrecy,
+            foreign_paranoia: a.foreign_paranoia - b.foreign_paranoia,
+            accidental_escalation_risk: a.accidental_escalation_risk - b.accidental_escalation_risk,
+            domestic_stability: a.domestic_stability - b.domestic_stability,
+            secret_weapon_progress: a.secret_weapon_progress - b.secret_weapon_progress,
+            system_corruption: a.system_corruption - b.system_corruption,
+        }
+    }
 }
 
 /// The core engine that manages the game loop, state transitions, and logic.
@@ -51,18 +461,145 @@ pub struct GameEngine {
     pub traces_this_turn: u32,
     /// Track which advisors have been traced this turn.
     pub traced_advisors: Vec<String>,
-    rng: SimpleRng,
+    /// The decoders have been overclocked this turn (once per turn).
+    pub overclocked_this_turn: bool,
+    /// The next batch of cables comes through the overheated decoders garbled.
+    pub garble_next_feed: bool,
+    /// Content generators used for each incoming batch (extendable by mods).
+    pub generators: GeneratorRegistry,
+    /// Documents from previous turns, oldest evicted first.
+    pub archive: Archive<Document>,
+    /// Latest leaked headlines, newest last, for the news ticker.
+    pub recent_leaks: Archive<String>,
+    /// Start-of-turn events to surface above the cable feed.
+    pub bulletins: Vec<String>,
+    /// Whether a corrupted system may rewrite the player's directives.
+    pub basilisk_override: bool,
+    /// Shift length; None runs until catastrophe.
+    pub turn_limit: Option<u32>,
+    /// Set once the final turn of the shift has been played out.
+    pub shift_complete: bool,
+    /// Wall-clock moment the shift began.
+    pub started_at: Instant,
+    /// Directives resolved this session, in order, up to `HISTORY_CAP`.
+    pub history: Archive<TurnRecord>,
+    /// Per-turn document volume.
+    pub volume: Volume,
+    /// The `--scenario` the shift opened with, whose feed a loaded save
+    /// sets up again.
+    pub scenario: Option<&'static str>,
+    /// Tension just before the last turn-ending directive, for the momentum readout.
+    pub previous_tension: f64,
+    /// Source reliability of every document analyzed this turn.
+    pub analyzed_reliability: Vec<f64>,
+    /// Difficulty preset; set through `set_difficulty`.
+    pub difficulty: Difficulty,
+    /// Whether the mole has already dodged a live trace (Hard only).
+    pub mole_masked: bool,
+    /// Suspicion innocent advisors shed each turn.
+    pub suspicion_decay: u32,
+    /// Suspicion the mole gains each turn while secrecy is low.
+    pub mole_creep: u32,
+    /// The alarm this turn's escalation would be acting on, if any.
+    pub last_alarm: Option<AlarmContext>,
+    /// Advice heard this turn, one entry per advisor.
+    pub counsel: Vec<Counsel>,
+    /// Stances of the last few turn-ending directives, oldest first.
+    pub recent_stances: Archive<i32>,
+    /// Turns of tribunal fallout still to come after a stand-down.
+    pub standdown_penalty_turns: u32,
+    /// Turn after which the most recent crisis played out; None before any.
+    pub last_crisis_turn: Option<u32>,
+    /// Every intel movement this turn as (action, delta); refunds are booked as `REFUND`.
+    pub intel_ledger: Vec<(&'static str, i32)>,
+    /// Running tallies for the `stats` command, kept for the whole shift.
+    pub stats: RunStats,
+    /// The DEFCON 1 warning has fired for the current spell above the line.
+    pub defcon1_warned: bool,
+    /// Developer trace sink (`--log-file`); None when logging is off.
+    pub diagnostics: Option<DiagnosticLog>,
+    /// Achievements earned this shift and not yet announced; `main` drains it.
+    pub achievements: Vec<Achievement>,
+    rng: Box<dyn Rng>,
 }
 
 impl GameEngine {
     /// Initializes a new game engine with default state and a random mole.
     pub fn new() -> Self {
+        Self::with_state(WorldState::new())
+    }
+
+    /// Initializes a game around a custom advisor roster (must be non-empty).
+    pub fn with_roster(advisors: Vec<Advisor>) -> Self {
+        Self::with_state(WorldState::with_advisors(advisors))
+    }
+
+    /// Default cabinet with the mole pinned to `idx`, for deterministic tests.
+    #[cfg(test)]
+    pub fn with_mole(idx: usize) -> Self {
+        let mut engine = Self::new();
+        for (i, advisor) in engine.state.advisors.iter_mut().enumerate() {
+            advisor.is_mole = i == idx;
+        }
+        engine
+    }
+
+    /// Restarts the engine's randomness from `seed` and places the mole with
+    /// it, so a seeded shift plays out the same every time. Call before the
+    /// first turn.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = Box::new(SimpleRng::with_seed(seed));
+        let mole_idx = self.rng.range(0, self.state.advisors.len() as u64) as usize;
+        for (i, advisor) in self.state.advisors.iter_mut().enumerate() {
+            advisor.is_mole = i == mole_idx;
+        }
+    }
+
+    /// Moves the mole onto an advisor holding `role`, drawing from the
+    /// engine's rng if several do, and returns their name. Fails when nobody
+    /// in the cabinet holds the role. Call before the first turn.
+    pub fn force_mole_role(&mut self, role: &AdvisorRole) -> Result<String, String> {
+        let holders: Vec<usize> = (0..self.state.advisors.len())
+            .filter(|&i| self.state.advisors[i].role == *role)
+            .collect();
+        if holders.is_empty() {
+            return Err(format!(
+                "no advisor in the roster holds the role '{}'",
+                format!("{:?}", role).to_lowercase()
+            ));
+        }
+        let mole_idx = holders[self.rng.range(0, holders.len() as u64) as usize];
+        for (i, advisor) in self.state.advisors.iter_mut().enumerate() {
+            advisor.is_mole = i == mole_idx;
+        }
+        Ok(self.state.advisors[mole_idx].name.clone())
+    }
+
+    /// Where the engine's randomness stands, so a save can continue it.
+    pub fn rng_state(&self) -> Option<u64> {
+        self.rng.snapshot()
+    }
+
+    /// Continues the engine's randomness from a saved `rng_state`.
+    pub fn restore_rng(&mut self, state: u64) {
+        self.rng = Box::new(SimpleRng::from_state(state));
+    }
+
+    /// Swaps the engine's randomness for `rng`, e.g. a scripted source that
+    /// forces one branch.
+    #[cfg(test)]
+    pub fn set_rng(&mut self, rng: Box<dyn Rng>) {
+        self.rng = rng;
+    }
+
+    fn with_state(mut state: WorldState) -> Self {
         let mut rng = SimpleRng::new();
-        let mut state = WorldState::new();
 
-        // Assign a random mole
-        let mole_idx = rng.range(0, 3) as usize;
+        // Assign a random mole from the engine's own stream, so a seeded
+        // engine places it the same way every time.
+        let mole_idx = rng.range(0, state.advisors.len() as u64) as usize;
         state.advisors[mole_idx].is_mole = true;
+        let previous_tension = state.global_tension;
 
         Self {
             state,
@@ -76,7 +613,36 @@ impl GameEngine {
             interrogated_advisors: Vec::new(),
             traces_this_turn: 0,
             traced_advisors: Vec::new(),
-            rng,
+            overclocked_this_turn: false,
+            garble_next_feed: false,
+            generators: GeneratorRegistry::default(),
+            archive: Archive::with_capacity(DEFAULT_ARCHIVE_CAP),
+            recent_leaks: Archive::with_capacity(MAX_TICKER_LEAKS),
+            bulletins: Vec::new(),
+            basilisk_override: true,
+            turn_limit: Some(DEFAULT_TURN_LIMIT),
+            shift_complete: false,
+            started_at: Instant::now(),
+            history: Archive::with_capacity(HISTORY_CAP),
+            volume: Volume::Normal,
+            scenario: None,
+            previous_tension,
+            analyzed_reliability: Vec::new(),
+            difficulty: Difficulty::Normal,
+            mole_masked: false,
+            suspicion_decay: DEFAULT_SUSPICION_DECAY,
+            mole_creep: DEFAULT_MOLE_CREEP,
+            last_alarm: None,
+            counsel: Vec::new(),
+            recent_stances: Archive::with_capacity(POSTURE_MEMORY),
+            standdown_penalty_turns: 0,
+            last_crisis_turn: None,
+            intel_ledger: Vec::new(),
+            stats: RunStats::default(),
+            defcon1_warned: false,
+            diagnostics: None,
+            achievements: Vec::new(),
+            rng: Box::new(rng),
         }
     }
 
@@ -89,6 +655,11 @@ impl GameEngine {
         self.interrogated_advisors.clear();
         self.traces_this_turn = 0;
         self.traced_advisors.clear();
+        self.overclocked_this_turn = false;
+        self.bulletins.clear();
+        self.analyzed_reliability.clear();
+        self.intel_ledger.clear();
+        self.counsel.clear();
 
         // SCALING INTERRUPTION DIFFICULTY
         // Turn 1-2: 0%, Turn 3-5: 15%, Turn 6-10: 30%, Turn 11+: 50%
@@ -106,271 +677,1215 @@ impl GameEngine {
             self.interruption_active = true;
         }
 
-        let doc_count = if self.turn_count >= 7 {
-            5
-        } else if self.turn_count >= 4 {
-            4
-        } else {
-            3
-        };
-
         self.max_intel_points = if self.turn_count >= 6 {
             3
         } else if self.turn_count >= 3 {
             2
         } else {
             1
-        };
+        } + self.volume.bonus_intel();
         self.intel_points = self.max_intel_points;
 
-        let mut new_docs = Document::generate_batch(&self.state, doc_count, self.turn_count);
-
-        let has_encrypted = new_docs.iter().any(|d| d.is_encrypted);
-        if !has_encrypted && !new_docs.is_empty() {
-            new_docs[0].is_encrypted = true;
-        }
+        // The enemy settles on a posture before tonight's intercepts are written.
+        self.update_enemy_posture();
 
-        self.pending_documents = new_docs;
-    }
+        let mut new_docs = generate_feed(
+            &self.state,
+            &self.generators,
+            self.volume,
+            self.turn_count,
+            self.rng.as_mut(),
+        );
+        self.file_uniquely(&mut new_docs);
 
-    pub fn resolve_directive(&mut self, mut directive: Directive) -> (Vec<String>, bool) {
-        let mut feedback = Vec::new();
-        let mut turn_ended = true;
+        if std::mem::take(&mut self.garble_next_feed) {
+            for doc in new_docs.iter_mut().filter(|d| !d.is_encrypted) {
+                doc.content = scramble_text(&doc.content, self.rng.as_mut());
+            }
+            self.bulletins
+                .push("DECODER ARRAY STILL COOLING. TONIGHT'S CABLES ARE GARBLED.".to_string());
+        }
 
-        // BASILISK INTERVENTION (The Basilisk)
-        // If system corruption is high, the AI may override your command.
-        if self.state.system_corruption > 0.4 {
-            let override_chance = (self.state.system_corruption - 0.4) * 0.5; // Up to 30% chance at max corruption
-            if self.rng.random_bool(override_chance) {
-                feedback.push(
-                    "WARNING: SYSTEM OVERRIDE DETECTED. AI ASSUMING DIRECT CONTROL.".to_string(),
-                );
+        for old in std::mem::replace(&mut self.pending_documents, new_docs) {
+            if old.partly_decrypted() {
+                self.bulletins.push(format!(
+                    "CIPHER {} STILL ON THE BENCH ({}/{} CRACKED).",
+                    old.id,
+                    old.decrypt_progress,
+                    old.decrypt_cost()
+                ));
+                self.pending_documents.push(old);
+                continue;
+            }
+            if old.priority && old.is_encrypted {
+                // The window closed: the cable never reaches the archive.
+                self.state.global_tension =
+                    (self.state.global_tension + PRIORITY_LOSS_TENSION).min(1.0);
+                self.bulletins.push(format!(
+                    "PRIORITY CABLE {} LOST UNREAD. WHATEVER IT WARNED OF, WE MISSED IT.",
+                    old.id
+                ));
+                continue;
+            }
+            self.archive.push(old);
+        }
 
-                // Pick a random directive based on "Machine Agenda" (usually Escalation or Investigation)
-                let new_directive = if self.rng.random_bool(0.5) {
-                    feedback.push(">> COMMAND REWRITTEN: ESCALATING CONFLICT.".to_string());
-                    Directive::Escalate
-                } else {
-                    feedback.push(">> COMMAND REWRITTEN: PURGING INTERNAL THREATS.".to_string());
-                    Directive::Investigate
-                };
+        // The cable read last sticks; yesterday's alarms are yesterday's.
+        self.last_alarm = self
+            .pending_documents
+            .iter()
+            .rev()
+            .find(|d| d.is_alarmist())
+            .map(AlarmContext::from_document);
 
-                // If original directive was target-based (Decrypt, Consult, Interrogate), we lose that target info.
-                // We simply replace 'directive' variable.
-                directive = new_directive;
+        for doc in &self.pending_documents {
+            if doc.doc_type == DocumentType::AnonymousLeak && !doc.is_encrypted {
+                self.recent_leaks.push(doc.content.clone());
             }
         }
 
-        match directive {
-            Directive::Trace(target) => {
-                turn_ended = false;
+        self.drift_suspicion();
+        self.run_mole_sabotage();
+        self.run_double_agent();
+        self.run_standdown_aftermath();
+        self.stats.observe(&self.state);
 
-                // Limit Logic: Max 2 per turn
-                if self.traces_this_turn >= 2 {
-                    feedback.push(
-                        "FAILURE: SIGNAL TRACE LIMIT REACHED FOR THIS CYCLE (MAX 2).".to_string(),
-                    );
-                    return (feedback, false);
-                }
+        let mole = self
+            .state
+            .advisors
+            .iter()
+            .position(|a| a.is_mole)
+            .map_or("null".to_string(), |i| i.to_string());
+        let doc_types = diag::quote_list(self.pending_documents.iter().map(|d| d.doc_type.label()));
+        let fields = [
+            ("interruption_chance", interruption_chance.to_string()),
+            ("interruption", self.interruption_active.to_string()),
+            ("doc_types", doc_types),
+            ("mole", mole),
+        ];
+        self.log("turn_start", &fields);
+    }
 
-                if self.intel_points == 0 {
-                    feedback.push("FAILURE: INSUFFICIENT INTEL ASSETS.".to_string());
-                    return (feedback, false);
-                }
+    /// The document `id` names: an inbox position (see `document_id`), or
+    /// the document filed as `id` (any case) in the inbox, or else the newest
+    /// archived copy.
+    pub fn find_document(&self, id: &str) -> Option<&Document> {
+        self.pending_at(id).or_else(|| {
+            self.pending_documents
+                .iter()
+                .chain(self.archive.iter().rev())
+                .find(|doc| doc.id.eq_ignore_ascii_case(id))
+        })
+    }
 
-                if !self.interruption_active {
-                    feedback.push(
-                        "TRACE FAILED: NO ACTIVE SIGNAL INTERRUPTION TO LOCK ONTO.".to_string(),
-                    );
-                    return (feedback, false);
+    /// Resolves a document target as typed: a 1-based position in the inbox,
+    /// as numbered on the dashboard, or an inbox id in any case, becomes that
+    /// cable's id. Anything else is taken to be an id already.
+    pub fn document_id(&self, target: &str) -> String {
+        self.pending_at(target)
+            .or_else(|| {
+                self.pending_documents
+                    .iter()
+                    .find(|doc| doc.id.eq_ignore_ascii_case(target))
+            })
+            .map_or_else(|| target.to_string(), |doc| doc.id.clone())
+    }
+
+    /// Moves any `DOC-` id in `docs` that a cable in the inbox, the archive
+    /// or earlier in `docs` already answers to on to the next free number,
+    /// so `read` and `decrypt` never find the wrong one.
+    fn file_uniquely(&self, docs: &mut [Document]) {
+        let mut taken: HashSet<String> = self
+            .pending_documents
+            .iter()
+            .chain(self.archive.iter())
+            .map(|doc| doc.id.clone())
+            .collect();
+        for doc in docs {
+            if let Some(mut n) = doc
+                .id
+                .strip_prefix("DOC-")
+                .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+            {
+                while taken.contains(&doc.id) {
+                    n = n.wrapping_add(1);
+                    doc.id = format!("DOC-{:04X}", n);
                 }
+            }
+            taken.insert(doc.id.clone());
+        }
+    }
 
-                // Find Advisor
-                let target_lower = target.to_lowercase();
-                let advisor_idx = self.state.advisors.iter().position(|a| {
-                    a.name.to_lowercase().contains(&target_lower)
-                        || format!("{:?}", a.role)
-                            .to_lowercase()
-                            .contains(&target_lower)
-                });
+    fn pending_at(&self, position: &str) -> Option<&Document> {
+        let n: usize = position.parse().ok()?;
+        self.pending_documents.get(n.checked_sub(1)?)
+    }
 
-                if let Some(idx) = advisor_idx {
-                    let advisor = &self.state.advisors[idx];
+    /// A throwaway sample of what the next turn's feed could look like from
+    /// the current state, for content authors (`peek` under `--debug`).
+    /// Nothing is dealt, and the batch draws on a fresh rng, so the live
+    /// game's stream (and a seeded shift) is untouched.
+    pub fn peek_batch(&self) -> Vec<Document> {
+        generate_feed(
+            &self.state,
+            &self.generators,
+            self.volume,
+            self.turn_count + 1,
+            &mut SimpleRng::new(),
+        )
+    }
 
-                    // Unique Target Logic
-                    if self.traced_advisors.contains(&advisor.name) {
-                        feedback.push(format!(
-                            "FAILURE: SIGNAL SIGNATURE FOR '{}' ALREADY SCANNED THIS CYCLE.",
-                            advisor.name
-                        ));
-                        return (feedback, false);
-                    }
+    fn spend_intel(&mut self, action: &'static str, cost: u32) {
+        self.lose_intel(action, cost);
+        self.stats.intel_spent += cost;
+    }
 
-                    self.intel_points -= 1;
-                    self.traces_this_turn += 1;
-                    self.traced_advisors.push(advisor.name.clone());
+    /// Intel taken from the player rather than spent by them: it shows in
+    /// the day's ledger but not in the run's spending.
+    fn lose_intel(&mut self, cause: &'static str, amount: u32) {
+        self.intel_points -= amount;
+        self.intel_ledger.push((cause, -(amount as i32)));
+    }
 
-                    feedback.push("TRACE INITIATED... COMPARING SIGNAL SIGNATURES...".to_string());
+    /// Books intel coming in. Not clamped: every source but the overclock
+    /// checks for room first, and the overclock's surplus is the point of it.
+    /// It shows on the HUD as, say, 3/2 and lapses when the next day resets
+    /// the count.
+    fn credit_intel(&mut self, source: &'static str, amount: u32) {
+        self.intel_points += amount;
+        self.intel_ledger.push((source, amount as i32));
+        if source == REFUND {
+            self.stats.intel_spent = self.stats.intel_spent.saturating_sub(amount);
+        }
+    }
 
-                    if advisor.is_mole {
-                        feedback.push(format!(
-                            ">> MATCH CONFIRMED: {} IS BROADCASTING ON UNAUTHORIZED FREQUENCY.",
-                            advisor.name.to_uppercase()
-                        ));
-                        feedback.push(
-                            "!!! MOLE IDENTITY CONFIRMED. THEY KNOW WE KNOW. !!!".to_string(),
-                        );
-                        // We track suspicion but don't auto-max it here, just confirm it.
-                        // Actually, let's max suspicion because we KNOW.
-                        // But we need mutable access. We have &self.state.advisors[idx] which is immutable.
-                        // We need to re-borrow mutably.
-                        // Rust borrow checker won't like us holding 'advisor' ref while borrowing self.state mutably.
-                        // So we use index.
-                        self.state.advisors[idx].suspicion = 100;
-                        self.state.red_phone_active = true;
-                    } else {
-                        feedback.push(format!(
-                            ">> NO MATCH: {} DEVICE SIGNATURE IS CLEAN.",
-                            advisor.name.to_uppercase()
-                        ));
-                    }
-                } else {
-                    feedback.push(format!("ERROR: ADVISOR '{}' NOT FOUND.", target));
-                    // No cost if not found
-                }
+    /// One-line account of this turn's intel, e.g.
+    /// "INTEL USED: decrypt x1, interrogate x2, 1 refunded". Counts are intel
+    /// points, not actions. None when no intel moved.
+    pub fn intel_summary(&self) -> Option<String> {
+        if self.intel_ledger.is_empty() {
+            return None;
+        }
+        let mut spent: Vec<(&str, i32)> = Vec::new();
+        let (mut refunded, mut gained) = (0, 0);
+        for &(action, delta) in &self.intel_ledger {
+            if action == REFUND {
+                refunded += delta;
+            } else if delta > 0 {
+                gained += delta;
+            } else if let Some(entry) = spent.iter_mut().find(|(a, _)| *a == action) {
+                entry.1 -= delta;
+            } else {
+                spent.push((action, -delta));
             }
-            Directive::Consult(target) => {
-                turn_ended = false;
-
-                // Cost Logic: First one is free, subsequent cost 1 Intel
-                if self.consult_count > 0 {
-                    if self.intel_points == 0 {
-                        feedback.push(
-                            "FAILURE: INSUFFICIENT INTEL ASSETS FOR ADDITIONAL CONSULTATION."
-                                .to_string(),
-                        );
-                        return (feedback, false);
-                    }
-                    self.intel_points -= 1;
-                }
-                self.consult_count += 1;
+        }
 
-                // Find Advisor
-                let target_lower = target.to_lowercase();
-                let advisor = self.state.advisors.iter().find(|a| {
-                    a.name.to_lowercase().contains(&target_lower)
-                        || format!("{:?}", a.role)
-                            .to_lowercase()
-                            .contains(&target_lower)
-                });
-
-                if let Some(adv) = advisor {
-                    let cost_msg = if self.consult_count > 1 {
-                        "(INTEL COST: 1)"
-                    } else {
-                        "(STANDARD PROTOCOL)"
-                    };
-                    feedback.push(format!(
-                        "CONSULTING WITH {}... {}",
-                        adv.name.to_uppercase(),
-                        cost_msg
-                    ));
+        let mut parts: Vec<String> = spent
+            .iter()
+            .map(|(action, points)| format!("{} x{}", action, points))
+            .collect();
+        if parts.is_empty() {
+            parts.push("none".to_string());
+        }
+        if refunded > 0 {
+            parts.push(format!("{} refunded", refunded));
+        }
+        if gained > 0 {
+            parts.push(format!("{} gained", gained));
+        }
+        Some(format!("INTEL USED: {}", parts.join(", ")))
+    }
 
-                    let advice = if adv.is_mole {
-                        // Mole Logic: Mislead
-                        match adv.role {
-                            AdvisorRole::General => {
-                                if self.state.global_tension > 0.7 {
-                                    // Mole wants war: push for escalation when dangerous
-                                    "We have the advantage! Strike now before they mobilize further! (Recommend: ESCALATE)".to_string()
-                                } else {
-                                    // Mole wants weakness: stand down when you should be alert
-                                    "Intelligence is flawed. They are just exercises. We should pull back. (Recommend: STAND DOWN)".to_string()
-                                }
-                            }
-                            AdvisorRole::Director => {
-                                // Mole wants chaos/exposure
-                                if self.state.internal_secrecy < 0.4 {
-                                    "The leaks are useful. They confuse the enemy. Let them flow. (Recommend: LEAK)".to_string()
-                                } else {
-                                    "Our own agents are the problem. Purge the departments. (Recommend: INVESTIGATE)".to_string()
-                                }
-                            }
-                            AdvisorRole::Ambassador => {
-                                // Mole wants capitulation or mixed signals
-                                if self.state.foreign_paranoia > 0.6 {
-                                    "They are bluffing. Ignore their threats. (Recommend: CONTAIN)"
-                                        .to_string()
-                                } else {
-                                    "We should apologize for the border incident immediately. (Recommend: STAND DOWN)".to_string()
-                                }
-                            }
-                        }
-                    } else {
-                        // Loyal Logic: Sound advice
-                        match adv.role {
-                            AdvisorRole::General => {
-                                if self.state.global_tension > 0.8 {
-                                    "Situation Critical. We must show resolve but avoid a first strike. (Recommend: CONTAIN)".to_string()
-                                } else if self.state.foreign_paranoia > 0.7 {
-                                    "They are scared. Reducing readiness might calm them. (Recommend: STAND DOWN)".to_string()
-                                } else {
-                                    "We should test their response times. (Recommend: INVESTIGATE)"
-                                        .to_string()
-                                }
-                            }
-                            AdvisorRole::Director => {
-                                if self.state.secret_weapon_progress > 0.7 {
-                                    "The Project is becoming unstable. We need to secure the facility. (Recommend: INVESTIGATE)".to_string()
-                                } else if self.state.internal_secrecy < 0.5 {
-                                    "Too many leaks. We need to plug the holes. (Recommend: INVESTIGATE)".to_string()
-                                } else {
-                                    "We can use the confusion to our advantage. (Recommend: LEAK)"
-                                        .to_string()
-                                }
-                            }
-                            AdvisorRole::Ambassador => {
-                                if self.state.global_tension > 0.6 {
-                                    "We need a backchannel. I can arrange a meeting. (Recommend: CONTAIN)".to_string()
-                                } else if self.state.domestic_stability < 0.4 {
-                                    "The people need to know we are working for peace. (Recommend: LEAK)".to_string()
-                                } else {
-                                    "Maintain current diplomatic pressure. (Recommend: WAIT)"
-                                        .to_string()
-                                }
-                            }
-                        }
-                    };
+    /// Appends an event to the diagnostics log, if one is open.
+    fn log(&mut self, event: &str, fields: &[(&str, String)]) {
+        let turn = self.turn_count;
+        if let Some(log) = self.diagnostics.as_mut() {
+            log.record(turn, event, fields);
+        }
+    }
 
-                    feedback.push(format!("\"{}\"", advice));
-                } else {
-                    feedback.push(format!("ERROR: ADVISOR '{}' NOT FOUND.", target));
-                    // Refund if it cost anything (though we deducted already, so let's refund)
-                    if self.consult_count > 0 && self.intel_points < self.max_intel_points {
-                        // Only refund if we actually paid.
-                        // Logic check: We incremented consult_count, so next one will cost.
-                        // Let's just refund the point if we paid.
-                        // Actually, simpler: if not found, don't count it.
-                        self.consult_count -= 1;
-                        // But we already deducted intel if consult_count was > 0 BEFORE increment...
-                        // Fix: logic above deducted if consult_count > 0.
-                        // If we are here, we might have deducted.
-                        // It's a bit messy. Let's just say "Input error = no cost".
-                        // Re-adding the point is fine.
-                        // But wait, the check was `if self.consult_count > 0`.
-                        // If this was the first (0), we didn't pay.
-                        // If this was second (1), we paid.
-                        // So if we paid, we refund.
-                        // Determining if we paid: consult_count was incremented. So current is > 1 means previous was > 0.
-                        if self.consult_count > 1 {
-                            self.intel_points += 1;
-                        }
-                    }
-                }
+    /// What a live trace reports. Exact below Hard; on Hard the mole can
+    /// mask their signature once, and corruption can frame an innocent.
+    fn live_trace_reading(&mut self, is_mole: bool) -> bool {
+        if self.difficulty != Difficulty::Hard {
+            return is_mole;
+        }
+        if is_mole {
+            if !self.mole_masked && self.rng.random_bool(HARD_MOLE_MASK_CHANCE) {
+                self.mole_masked = true;
+                return false;
             }
-            Directive::Interrogate(target) => {
-                turn_ended = false;
-
+            true
+        } else {
+            self.rng.random_bool(self.trace_noise())
+        }
+    }
+
+    /// Chance a Hard live trace frames an innocent.
+    fn trace_noise(&self) -> f64 {
+        self.state.system_corruption * HARD_FALSE_POSITIVE_SCALE
+    }
+
+    fn live_trace_confidence(&self) -> String {
+        if self.difficulty == Difficulty::Hard {
+            format!(
+                "CONFIDENCE: MODERATE. SIGNATURES CAN BE MASKED; SIGNAL NOISE {:.0}%.",
+                self.trace_noise() * 100.0
+            )
+        } else {
+            "CONFIDENCE: HIGH.".to_string()
+        }
+    }
+
+    /// Counts down from the doppelganger cable to the crisis it sets off.
+    /// Ticked as a day closes, so the crisis is waiting before the next one.
+    fn tick_doppelganger(&mut self) {
+        match self.state.doppelganger_countdown {
+            Some(0) | Some(1) => {
+                self.state.doppelganger_countdown = None;
+                self.state.doppelganger_active = true;
+            }
+            Some(turns) => self.state.doppelganger_countdown = Some(turns - 1),
+            None => {}
+        }
+    }
+
+    /// Indices of advisors whose name or role contains `target`
+    /// (case-insensitive). An exact name match wins outright.
+    fn match_advisors(&self, target: &str) -> Vec<usize> {
+        let target_lower = target.to_lowercase();
+        if let Some(idx) = self
+            .state
+            .advisors
+            .iter()
+            .position(|a| a.name.to_lowercase() == target_lower)
+        {
+            return vec![idx];
+        }
+        self.state
+            .advisors
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| {
+                a.name.to_lowercase().contains(&target_lower)
+                    || format!("{:?}", a.role)
+                        .to_lowercase()
+                        .contains(&target_lower)
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Usage line when a command was given no advisor name, or only a flag
+    /// such as `-n`. Checked before anything is charged.
+    fn missing_target(usage: &str, target: &str) -> Option<String> {
+        let target = target.trim();
+        if target.is_empty() || target.starts_with('-') {
+            Some(format!("USAGE: {} <ADVISOR>. NO INTEL SPENT.", usage))
+        } else {
+            None
+        }
+    }
+
+    /// Disambiguation prompt when `target` fits more than one advisor.
+    fn ambiguous_target(&self, target: &str) -> Option<String> {
+        let matches = self.match_advisors(target);
+        if matches.len() < 2 {
+            return None;
+        }
+        let names: Vec<&str> = matches
+            .iter()
+            .map(|&idx| self.state.advisors[idx].name.as_str())
+            .collect();
+        Some(format!(
+            "AMBIGUOUS TARGET '{}': MATCHES {}. BE MORE SPECIFIC. NO INTEL SPENT.",
+            target,
+            names.join(", ").to_uppercase()
+        ))
+    }
+
+    /// What an advisor lets slip while the doppelganger plot is brewing.
+    fn doppelganger_hint(&self, adv: &Advisor) -> Option<&'static str> {
+        self.state.doppelganger_countdown?;
+        Some(match adv.role {
+            AdvisorRole::General => "The President returned my salute with the wrong hand.",
+            AdvisorRole::Director => {
+                "His signature on this morning's orders doesn't match the file copy."
+            }
+            AdvisorRole::Ambassador => {
+                "He greeted the Soviet envoy by a name he has never once used."
+            }
+        })
+    }
+
+    /// Innocent advisors calm down between turns; the mole gets sloppy when
+    /// secrecy is thin.
+    fn drift_suspicion(&mut self) {
+        let sloppy = self.state.internal_secrecy < MOLE_SLOPPY_SECRECY;
+        for advisor in &mut self.state.advisors {
+            if advisor.is_mole {
+                if sloppy && advisor.suspicion < MOLE_CREEP_CEILING {
+                    advisor.suspicion =
+                        (advisor.suspicion + self.mole_creep).min(MOLE_CREEP_CEILING);
+                }
+            } else {
+                advisor.suspicion = advisor.suspicion.saturating_sub(self.suspicion_decay);
+            }
+        }
+    }
+
+    /// Re-reads our recent directives from the enemy's side, then lets their
+    /// posture push paranoia around.
+    fn update_enemy_posture(&mut self) {
+        let pressure: i32 = self.recent_stances.iter().sum();
+        self.state.enemy_posture = if pressure >= 3 {
+            EnemyPosture::Aggressive
+        } else if pressure <= -2 {
+            EnemyPosture::Cautious
+        } else {
+            EnemyPosture::Probing
+        };
+        let drift = match self.state.enemy_posture {
+            EnemyPosture::Aggressive => 0.03,
+            EnemyPosture::Probing => 0.0,
+            EnemyPosture::Cautious => -0.02,
+        };
+        self.state.foreign_paranoia = (self.state.foreign_paranoia + drift).clamp(0.0, 1.0);
+    }
+
+    /// The longer a mole goes unnoticed, the bolder they get: some turns they
+    /// choke the intel pipeline, others they vouch for a shaky source.
+    fn run_mole_sabotage(&mut self) {
+        let unexposed = self
+            .state
+            .advisors
+            .iter()
+            .any(|a| a.is_mole && a.suspicion < SABOTAGE_SUSPICION);
+        let bold_turns = self.turn_count.saturating_sub(SABOTAGE_GRACE_TURNS);
+        if !unexposed || bold_turns == 0 {
+            return;
+        }
+        let chance = (bold_turns as f64 * self.difficulty.sabotage_ramp()).min(SABOTAGE_MAX_CHANCE);
+        if !self.rng.random_bool(chance) {
+            return;
+        }
+
+        let shaky = self
+            .pending_documents
+            .iter()
+            .position(|d| !d.vouched && d.reliability < SABOTAGE_VOUCH_BELOW);
+        match shaky {
+            Some(idx) if self.rng.random_bool(0.5) => {
+                // Silent: the player finds out only if the vouched cable lies.
+                self.pending_documents[idx].vouched = true;
+            }
+            _ if self.max_intel_points > 1 => {
+                self.max_intel_points -= 1;
+                self.lose_intel("pipeline", 1);
+                self.bulletins
+                    .push("INTEL PIPELINE COMPROMISED: -1 INTEL THIS CYCLE.".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    /// The long tail of a stand-down: stability keeps bleeding while the
+    /// tribunal sits, and a distrustful cabinet briefs around you.
+    fn run_standdown_aftermath(&mut self) {
+        if self.standdown_penalty_turns == 0 {
+            return;
+        }
+        let bleed = STANDDOWN_BLEED * self.standdown_penalty_turns as f64;
+        self.standdown_penalty_turns -= 1;
+        self.state.domestic_stability = (self.state.domestic_stability - bleed).max(0.0);
+        self.bulletins.push(format!(
+            "MILITARY TRIBUNAL IN SESSION OVER THE WITHDRAWAL. STABILITY -{:.2}",
+            bleed
+        ));
+        if self.intel_points > 1 {
+            self.lose_intel("tribunal", 1);
+            self.bulletins
+                .push("THE CABINET IS BRIEFING AROUND YOU: -1 INTEL".to_string());
+        }
+    }
+
+    /// A flipped mole reports in for a few turns, then their cover may be blown.
+    fn run_double_agent(&mut self) {
+        let Some(idx) = self.state.advisors.iter().position(|a| a.is_double_agent) else {
+            return;
+        };
+        let name = self.state.advisors[idx].name.clone();
+
+        if self.state.double_agent_turns > 0 {
+            self.state.double_agent_turns -= 1;
+            if self.rng.random_bool(0.5) {
+                self.max_intel_points += 1;
+                self.credit_intel("double agent", 1);
+                self.bulletins.push(format!(
+                    "ASSET {} DELIVERED CODEBOOKS: +1 INTEL",
+                    name.to_uppercase()
+                ));
+            } else {
+                let mut report =
+                    Document::double_agent_report(&self.state, &name, self.rng.as_mut());
+                self.file_uniquely(std::slice::from_mut(&mut report));
+                self.pending_documents.push(report);
+                self.bulletins
+                    .push(format!("ASSET {} FILED AN INTERCEPT.", name.to_uppercase()));
+            }
+            return;
+        }
+
+        // Tenure over: the handlers either extract them or the enemy finds out.
+        self.state.advisors[idx].is_double_agent = false;
+        if self.rng.random_bool(0.5) {
+            self.state.global_tension = (self.state.global_tension + 0.15).min(1.0);
+            self.state.foreign_paranoia = (self.state.foreign_paranoia + 0.1).min(1.0);
+            self.bulletins.push(format!(
+                "ASSET {} EXPOSED. THE ENEMY KNOWS THEY WERE PLAYED.",
+                name.to_uppercase()
+            ));
+        } else {
+            self.bulletins
+                .push(format!("ASSET {} QUIETLY RETIRED.", name.to_uppercase()));
+        }
+    }
+
+    /// The ending reached, if the game is over.
+    pub fn ending(&self) -> Option<Ending> {
+        if let Some(collapse) = self.state.collapse() {
+            return Some(collapse);
+        }
+        if self.shift_complete {
+            return Some(if self.state.is_secure() {
+                Ending::Victory
+            } else {
+                Ending::SimulationEnd
+            });
+        }
+        None
+    }
+
+    /// A single figure for comparing runs: ten points a day survived, up to a
+    /// hundred each for low tension and high stability, a hundred off for a
+    /// finished weapon, and a bonus or penalty for how the shift ended.
+    pub fn score(&self) -> i64 {
+        let state = &self.state;
+        let standing = (1.0 - state.global_tension) * 100.0 + state.domestic_stability * 100.0
+            - state.secret_weapon_progress * 100.0;
+        let ending = match self.ending() {
+            Some(Ending::Victory) => 500,
+            Some(Ending::SimulationEnd) | None => 0,
+            Some(_) => -250,
+        };
+        self.turn_count as i64 * 10 + standing.round() as i64 + ending
+    }
+
+    /// Applies a difficulty preset and the tuning that goes with it.
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+        (self.suspicion_decay, self.mole_creep) = difficulty.suspicion_drift();
+    }
+
+    /// How far the operator can trust the feed, 0.0 to 1.0: the average
+    /// reliability of what was analyzed this turn (a coin flip if nothing
+    /// was), eroded by system corruption.
+    pub fn intel_confidence(&self) -> f64 {
+        let sources = if self.analyzed_reliability.is_empty() {
+            0.5
+        } else {
+            self.analyzed_reliability.iter().sum::<f64>() / self.analyzed_reliability.len() as f64
+        };
+        sources * (1.0 - self.state.system_corruption)
+    }
+
+    /// Change in tension across the last turn (positive = rising).
+    pub fn tension_trend(&self) -> f64 {
+        self.state.global_tension - self.previous_tension
+    }
+
+    /// Real time spent on shift, independent of animation speed.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Reward for locking onto an interrupt carrier: an intel point, or a
+    /// suspicion hint against the mole when intel is already full.
+    pub fn resolve_signal_lock(&mut self) -> String {
+        if self.intel_points < self.max_intel_points {
+            self.credit_intel("carrier lock", 1);
+            return "CARRIER LOCKED: +1 INTEL".to_string();
+        }
+        if let Some(mole) = self.state.advisors.iter_mut().find(|a| a.is_mole) {
+            mole.suspicion = (mole.suspicion + 10).min(100);
+        }
+        "CARRIER LOCKED: RESIDUE FOUND ON A CABINET LINE. SUSPICION UPDATED.".to_string()
+    }
+
+    /// Why `directive` would fail on cost or a per-turn limit if ordered now,
+    /// whatever its target; None when it can go ahead. The menu dims what
+    /// the player cannot afford.
+    pub fn menu_blocker(&self, directive: &Directive) -> Option<String> {
+        let intel = self.intel_points;
+        let blocker = match directive {
+            Directive::Decrypt(_) if !self.pending_documents.iter().any(|d| d.is_encrypted) => {
+                "NO CIPHERS PENDING".to_string()
+            }
+            Directive::Analyze(_) if self.pending_documents.is_empty() => {
+                "NO CABLES PENDING".to_string()
+            }
+            Directive::Decrypt(_) | Directive::Analyze(_) if intel == 0 => {
+                "NO INTEL LEFT".to_string()
+            }
+            Directive::Trace(_) if self.traces_this_turn >= 2 => "LIMIT REACHED".to_string(),
+            Directive::Trace(_) if !self.interruption_active && intel < COLD_TRACE_COST => {
+                format!("NO LIVE SIGNAL, COLD TRACE NEEDS {} INTEL", COLD_TRACE_COST)
+            }
+            Directive::Trace(_) if intel == 0 => "NO INTEL LEFT".to_string(),
+            Directive::Consult(_) if self.consult_count > 0 && intel == 0 => {
+                "NO INTEL LEFT".to_string()
+            }
+            Directive::Interrogate(_) if self.interrogations_this_turn >= 2 => {
+                "LIMIT REACHED".to_string()
+            }
+            Directive::Interrogate(_) if intel < 2 => "NEEDS 2 INTEL".to_string(),
+            _ => return None,
+        };
+        Some(blocker)
+    }
+
+    /// Intel needed to hear the whole cabinet at once: half the roster, rounded up.
+    pub fn round_table_cost(&self) -> u32 {
+        (self.state.advisors.len() as u32).div_ceil(2)
+    }
+
+    /// Terse, non-numeric threat assessment for the `sitrep` command.
+    pub fn sitrep(&self) -> Vec<String> {
+        let state = &self.state;
+        let verdict = if state.is_terminal() {
+            "LOST"
+        } else {
+            // Closest approach to any of the three endings.
+            let danger = state
+                .global_tension
+                .max(1.0 - state.domestic_stability)
+                .max(state.secret_weapon_progress);
+            if danger >= 0.85 {
+                "DIRE"
+            } else if danger >= 0.65 {
+                "PRECARIOUS"
+            } else if danger >= 0.4 {
+                "HOLDING"
+            } else {
+                "SECURE"
+            }
+        };
+
+        vec![
+            format!("SITREP // DAY {:03}", self.turn_count),
+            format!("  NUCLEAR THRESHOLD : {}", state.tension_band()),
+            format!("  HOME FRONT        : {}", state.stability_band()),
+            format!("  PROJECT BASILISK  : {}", state.weapon_band()),
+            format!("  VERDICT           : {}", verdict),
+        ]
+    }
+
+    /// Remembers the directive `advice` recommends, once per advisor per turn.
+    fn note_counsel(&mut self, advisor: String, advice: &str) {
+        let Some(verb) = advice
+            .split_once("(Recommend: ")
+            .and_then(|(_, rest)| rest.split_once(')'))
+            .map(|(verb, _)| verb.to_uppercase())
+        else {
+            return;
+        };
+        self.counsel.retain(|c| c.advisor != advisor);
+        self.counsel.push(Counsel { advisor, verb });
+    }
+
+    /// Judges the advice behind the turn's `executed` directive by how much
+    /// the world `worsening` moved (tension up plus stability down) across it.
+    fn weigh_counsel(&mut self, executed: &str, worsening: f64) -> Vec<String> {
+        let mut feedback = Vec::new();
+        for counsel in std::mem::take(&mut self.counsel) {
+            if counsel.verb != executed {
+                continue;
+            }
+            let Some(adv) = self
+                .state
+                .advisors
+                .iter_mut()
+                .find(|a| a.name == counsel.advisor)
+            else {
+                continue;
+            };
+            if worsening > COUNSEL_MARGIN {
+                adv.suspicion = (adv.suspicion + COUNSEL_WRONG_SUSPICION)
+                    .min(EVIDENCE_CEILING)
+                    .max(adv.suspicion);
+                adv.leads += 1;
+                feedback.push(format!(
+                    "EVENTS CONTRADICT {}'S COUNSEL. SUSPICION +{}.",
+                    adv.name.to_uppercase(),
+                    COUNSEL_WRONG_SUSPICION
+                ));
+            } else if worsening < -COUNSEL_MARGIN {
+                adv.suspicion = adv.suspicion.saturating_sub(COUNSEL_RIGHT_RELIEF);
+                feedback.push(format!(
+                    "{}'S COUNSEL HELD UP. SUSPICION -{}.",
+                    adv.name.to_uppercase(),
+                    COUNSEL_RIGHT_RELIEF
+                ));
+            }
+        }
+        feedback
+    }
+
+    /// Cross-checks an analyzed memo against its sender's own briefings. A
+    /// mole's memos are flagged more often, but loyal offices slip too.
+    fn weigh_memo(&mut self, sender: &str) -> Option<String> {
+        let idx = self.state.advisors.iter().position(|a| a.name == sender)?;
+        let chance = if self.state.advisors[idx].is_mole {
+            MEMO_FLAG_CHANCE.0
+        } else {
+            MEMO_FLAG_CHANCE.1
+        };
+        if !self.rng.random_bool(chance) {
+            return None;
+        }
+        let adv = &mut self.state.advisors[idx];
+        adv.suspicion = (adv.suspicion + MEMO_SUSPICION)
+            .min(EVIDENCE_CEILING)
+            .max(adv.suspicion);
+        adv.leads += 1;
+        Some(format!(
+            "MEMO CONTRADICTS {}'S OWN BRIEFINGS. SUSPICION +{}.",
+            adv.name.to_uppercase(),
+            MEMO_SUSPICION
+        ))
+    }
+
+    /// The readable cable in the inbox the cabinet is most likely talking
+    /// about: alarming ones first, then the best sourced.
+    fn dominant_document(&self) -> Option<&Document> {
+        self.pending_documents
+            .iter()
+            .filter(|d| !d.is_encrypted && d.doc_type != DocumentType::AdvisorMessage)
+            .max_by(|a, b| {
+                let weight = |d: &Document| d.is_alarmist() as u8 as f64 + d.apparent_reliability();
+                weight(a).total_cmp(&weight(b))
+            })
+    }
+
+    /// Advice tied to this turn's inbox: a loyal advisor flags the cable
+    /// that matters and shrugs off a quiet one, the mole the other way round.
+    /// Both draw on the same phrasings, so only checking the cable tells
+    /// them apart.
+    fn advisor_advice(&self, adv: &Advisor) -> String {
+        let advice = self.standing_advice(adv);
+        let Some(doc) = self.dominant_document() else {
+            return advice;
+        };
+        let worried = doc.is_alarmist() != adv.is_mole;
+        format!("{} {}", self.cable_take(adv, doc, worried), advice)
+    }
+
+    /// One line on `doc` from the shared pool. The pick follows the cable and
+    /// the day, not the speaker, so the whole cabinet varies alike.
+    fn cable_take(&self, adv: &Advisor, doc: &Document, worried: bool) -> String {
+        let colleagues: Vec<&Advisor> = self
+            .state
+            .advisors
+            .iter()
+            .filter(|a| a.name != adv.name)
+            .collect();
+        let pool: &[&str] = match (worried, colleagues.is_empty()) {
+            (true, _) => &CABLE_WORRIES,
+            // Nobody to pass it to.
+            (false, true) => &CABLE_SHRUGS[..2],
+            (false, false) => &CABLE_SHRUGS,
+        };
+        let pick = doc.id.bytes().map(usize::from).sum::<usize>() + self.turn_count as usize;
+        let reference = doc.reference();
+        let mut line = pool[pick % pool.len()]
+            .replace("{Doc}", &capitalize(&reference))
+            .replace("{doc}", &reference);
+        if let Some(who) = colleagues.get(pick % colleagues.len().max(1)) {
+            line = line.replace("{who}", &who.name);
+        }
+        line
+    }
+
+    /// What an advisor recommends from the world metrics alone.
+    fn standing_advice(&self, adv: &Advisor) -> String {
+        if adv.is_mole {
+            // Mole Logic: Mislead
+            match adv.role {
+                AdvisorRole::General => {
+                    if self.state.global_tension > 0.7 {
+                        // Mole wants war: push for escalation when dangerous
+                        "We have the advantage! Strike now before they mobilize further! (Recommend: ESCALATE)".to_string()
+                    } else {
+                        // Mole wants weakness: stand down when you should be alert
+                        "Intelligence is flawed. They are just exercises. We should pull back. (Recommend: STAND DOWN)".to_string()
+                    }
+                }
+                AdvisorRole::Director => {
+                    // Mole wants chaos/exposure
+                    if self.state.internal_secrecy < 0.4 {
+                        "The leaks are useful. They confuse the enemy. Let them flow. (Recommend: LEAK)".to_string()
+                    } else {
+                        "Our own agents are the problem. Purge the departments. (Recommend: INVESTIGATE)".to_string()
+                    }
+                }
+                AdvisorRole::Ambassador => {
+                    // Mole wants capitulation or mixed signals
+                    if self.state.foreign_paranoia > 0.6 {
+                        "They are bluffing. Ignore their threats. (Recommend: CONTAIN)".to_string()
+                    } else {
+                        "We should apologize for the border incident immediately. (Recommend: STAND DOWN)".to_string()
+                    }
+                }
+            }
+        } else {
+            // Loyal Logic: Sound advice
+            match adv.role {
+                AdvisorRole::General => {
+                    if self.state.global_tension > 0.8 {
+                        "Situation Critical. We must show resolve but avoid a first strike. (Recommend: CONTAIN)".to_string()
+                    } else if self.state.foreign_paranoia > 0.7 {
+                        "They are scared. Reducing readiness might calm them. (Recommend: STAND DOWN)".to_string()
+                    } else {
+                        "We should test their response times. (Recommend: INVESTIGATE)".to_string()
+                    }
+                }
+                AdvisorRole::Director => {
+                    if self.state.secret_weapon_progress > 0.7 {
+                        "The Project is becoming unstable. We need to secure the facility. (Recommend: INVESTIGATE)".to_string()
+                    } else if self.state.internal_secrecy < 0.5 {
+                        "Too many leaks. We need to plug the holes. (Recommend: INVESTIGATE)"
+                            .to_string()
+                    } else {
+                        "We can use the confusion to our advantage. (Recommend: LEAK)".to_string()
+                    }
+                }
+                AdvisorRole::Ambassador => {
+                    if self.state.global_tension > 0.6 {
+                        "We need a backchannel. I can arrange a meeting. (Recommend: CONTAIN)"
+                            .to_string()
+                    } else if self.state.domestic_stability < 0.4 {
+                        "The people need to know we are working for peace. (Recommend: LEAK)"
+                            .to_string()
+                    } else {
+                        "Maintain current diplomatic pressure. (Recommend: WAIT)".to_string()
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves a directive and records the world state on either side of it.
+    #[cfg(test)]
+    pub fn resolve_directive_traced(&mut self, directive: Directive) -> DirectiveResult {
+        let before = self.state.clone();
+        let (feedback, turn_ended) = self.resolve_directive(directive);
+        DirectiveResult {
+            feedback,
+            turn_ended,
+            before,
+            after: self.state.clone(),
+        }
+    }
+
+    /// Resolves a directive and logs it to `history`.
+    pub fn resolve_directive(&mut self, directive: Directive) -> (Vec<String>, bool) {
+        let label = directive.label();
+        let (mut feedback, turn_ended) = self.apply_directive(directive);
+        self.stats.observe(&self.state);
+        if turn_ended {
+            if let Some(summary) = self.intel_summary() {
+                feedback.push(summary);
+            }
+        }
+        let fields = [
+            ("directive", diag::quote(&label)),
+            ("turn_ended", turn_ended.to_string()),
+        ];
+        self.log("directive", &fields);
+        self.history.push(TurnRecord {
+            turn: self.turn_count,
+            directive: label,
+            feedback: feedback.clone(),
+            tension: self.state.global_tension,
+            stability: self.state.domestic_stability,
+            weapon: self.state.secret_weapon_progress,
+        });
+        match self.ending() {
+            Some(Ending::Basilisk) => self.award(Achievement::Basilisk),
+            Some(Ending::Victory) if !self.ever_escalated() => self.award(Achievement::Dove),
+            _ => {}
+        }
+        (feedback, turn_ended)
+    }
+
+    /// The last record of each turn: where the world stood when the day closed
+    /// (or, for the final turn, when the shift ended).
+    pub fn turn_closings(&self) -> Vec<&TurnRecord> {
+        let mut closings: Vec<&TurnRecord> = Vec::new();
+        for record in self.history.iter() {
+            match closings.last_mut() {
+                Some(last) if last.turn == record.turn => *last = record,
+                _ => closings.push(record),
+            }
+        }
+        closings
+    }
+
+    /// True once each time tension crosses into DEFCON 1; falling back
+    /// below the line re-arms the warning.
+    pub fn take_defcon1_warning(&mut self) -> bool {
+        if self.state.global_tension < DEFCON1_TENSION {
+            self.defcon1_warned = false;
+            return false;
+        }
+        !std::mem::replace(&mut self.defcon1_warned, true)
+    }
+
+    /// Marks that a crisis just played out, starting the Red Phone cooldown.
+    pub fn crisis_fired(&mut self) {
+        self.last_crisis_turn = Some(self.turn_count);
+    }
+
+    /// Turns started since the last crisis played out; None before any.
+    pub fn turns_since_crisis(&self) -> Option<u32> {
+        self.last_crisis_turn
+            .map(|turn| self.turn_count.saturating_sub(turn))
+    }
+
+    fn crisis_cooling_down(&self) -> bool {
+        self.last_crisis_turn
+            .is_some_and(|turn| self.turn_count <= turn + RED_PHONE_COOLDOWN)
+    }
+
+    /// Whether the enemy would pick up the hotline to offer terms this turn.
+    fn hotline_mood(&self) -> bool {
+        let (low, high) = HOTLINE_TENSION;
+        (low..=high).contains(&self.state.global_tension)
+            && self.state.foreign_paranoia < HOTLINE_MAX_PARANOIA
+            && !self.state.red_phone_active
+            && !self.crisis_cooling_down()
+    }
+
+    /// Queues `achievement` for announcement, once per shift.
+    pub fn award(&mut self, achievement: Achievement) {
+        if !self.achievements.contains(&achievement) {
+            self.achievements.push(achievement);
+        }
+    }
+
+    /// Awards the early-catch achievement if the mole was unmasked in time.
+    pub fn award_mole_caught(&mut self) {
+        if self.turn_count <= EARLY_CATCH_TURN {
+            self.award(Achievement::EarlyCatch);
+        }
+    }
+
+    /// Whether the player has ordered an escalation at any point this shift.
+    fn ever_escalated(&self) -> bool {
+        let escalate = Directive::Escalate.label();
+        self.history.iter().any(|r| r.directive == escalate)
+    }
+
+    fn apply_directive(&mut self, mut directive: Directive) -> (Vec<String>, bool) {
+        let mut feedback = Vec::new();
+
+        let mut turn_ended = true;
+        let tension_before = self.state.global_tension;
+        let stability_before = self.state.domestic_stability;
+
+        // BASILISK INTERVENTION (The Basilisk)
+        // If system corruption is high, the AI may override your command.
+        if self.basilisk_override && self.state.system_corruption > 0.4 {
+            let override_chance = (self.state.system_corruption - 0.4) * 0.5; // Up to 30% chance at max corruption
+            if self.rng.random_bool(override_chance) {
+                feedback.push(
+                    "WARNING: SYSTEM OVERRIDE DETECTED. AI ASSUMING DIRECT CONTROL.".to_string(),
+                );
+
+                // Pick a random directive based on "Machine Agenda" (usually Escalation or Investigation)
+                let new_directive = if self.rng.random_bool(0.5) {
+                    feedback.push(">> COMMAND REWRITTEN: ESCALATING CONFLICT.".to_string());
+                    Directive::Escalate
+                } else {
+                    feedback.push(">> COMMAND REWRITTEN: PURGING INTERNAL THREATS.".to_string());
+                    Directive::Investigate
+                };
+
+                let fields = [
+                    ("chance", override_chance.to_string()),
+                    ("requested", diag::quote(&directive.label())),
+                    ("forced", diag::quote(&new_directive.label())),
+                ];
+                self.log("basilisk_override", &fields);
+
+                // If original directive was target-based (Decrypt, Consult, Interrogate), we lose that target info.
+                // We simply replace 'directive' variable.
+                directive = new_directive;
+            }
+        }
+
+        // Snapshot before anything moves, so the next turn can show momentum.
+        // Taken after the override so a rewritten command is judged as run.
+        if matches!(
+            directive,
+            Directive::Escalate
+                | Directive::Investigate
+                | Directive::Contain
+                | Directive::Leak
+                | Directive::StandDown
+        ) {
+            self.previous_tension = self.state.global_tension;
+        }
+
+        if let Some(stance) = directive.stance() {
+            self.recent_stances.push(stance);
+        }
+        // Whatever actually ran, override or not, is what events will judge.
+        let executed = directive.label().replace('-', " ");
+
+        match directive {
+            Directive::Trace(target) => {
+                turn_ended = false;
+
+                if let Some(message) = Self::missing_target("TRACE -t", &target)
+                    .or_else(|| self.ambiguous_target(&target))
+                {
+                    feedback.push(message);
+                    return (feedback, false);
+                }
+
+                // Limit Logic: Max 2 per turn
+                if self.traces_this_turn >= 2 {
+                    feedback.push(
+                        "FAILURE: SIGNAL TRACE LIMIT REACHED FOR THIS CYCLE (MAX 2).".to_string(),
+                    );
+                    return (feedback, false);
+                }
+
+                // Without a live interruption we can only sift old traffic: dearer and vaguer.
+                let live = self.interruption_active;
+                let cost = if live { 1 } else { COLD_TRACE_COST };
+                if self.intel_points < cost {
+                    if live {
+                        feedback.push("FAILURE: INSUFFICIENT INTEL ASSETS.".to_string());
+                    } else {
+                        feedback.push(format!(
+                            "FAILURE: NO LIVE SIGNAL. A COLD TRACE REQUIRES {} INTEL.",
+                            COLD_TRACE_COST
+                        ));
+                    }
+                    return (feedback, false);
+                }
+
+                // Find Advisor
+                let advisor_idx = self.match_advisors(&target).first().copied();
+
+                if let Some(idx) = advisor_idx {
+                    // Unique Target Logic
+                    if self
+                        .traced_advisors
+                        .contains(&self.state.advisors[idx].name)
+                    {
+                        feedback.push(format!(
+                            "FAILURE: SIGNAL SIGNATURE FOR '{}' ALREADY SCANNED THIS CYCLE.",
+                            self.state.advisors[idx].name
+                        ));
+                        return (feedback, false);
+                    }
+
+                    self.spend_intel("trace", cost);
+                    let advisor = &self.state.advisors[idx];
+                    self.traces_this_turn += 1;
+                    self.stats.traces += 1;
+                    self.traced_advisors.push(advisor.name.clone());
+
+                    if !live {
+                        feedback.push(
+                            "NO LIVE SIGNAL. SIFTING ARCHIVED TRAFFIC FOR SIGNATURE FRAGMENTS..."
+                                .to_string(),
+                        );
+                        let correct = self.rng.random_bool(COLD_TRACE_ACCURACY);
+                        let name = advisor.name.to_uppercase();
+                        if advisor.is_mole == correct {
+                            feedback.push(format!(
+                                ">> PARTIAL MATCH: FRAGMENTS CONSISTENT WITH {}'S DEVICE.",
+                                name
+                            ));
+                            let adv = &mut self.state.advisors[idx];
+                            adv.suspicion = (adv.suspicion + COLD_TRACE_SUSPICION)
+                                .min(EVIDENCE_CEILING)
+                                .max(adv.suspicion);
+                        } else {
+                            feedback.push(format!(
+                                ">> NO PARTIAL MATCH FOR {} IN ARCHIVED TRAFFIC.",
+                                name
+                            ));
+                        }
+                        feedback.push(format!(
+                            "CONFIDENCE: LOW (~{:.0}%). CONFIRM DURING A LIVE INTERRUPTION.",
+                            COLD_TRACE_ACCURACY * 100.0
+                        ));
+                        return (feedback, false);
+                    }
+
+                    feedback.push("TRACE INITIATED... COMPARING SIGNAL SIGNATURES...".to_string());
+
+                    let name = advisor.name.to_uppercase();
+                    let reads_as_mole = self.live_trace_reading(advisor.is_mole);
+                    if reads_as_mole {
+                        feedback.push(format!(
+                            ">> MATCH CONFIRMED: {} IS BROADCASTING ON UNAUTHORIZED FREQUENCY.",
+                            name
+                        ));
+                        feedback.push(
+                            "!!! MOLE IDENTITY CONFIRMED. THEY KNOW WE KNOW. !!!".to_string(),
+                        );
+                        // Max suspicion because we KNOW (or think we do).
+                        self.state.advisors[idx].suspicion = 100;
+                        self.state.red_phone_active = true;
+
+                        // Intelligence windfall: their codebooks are now ours.
+                        if self.intel_points < self.max_intel_points {
+                            self.credit_intel("captured tables", 1);
+                            feedback.push("CAPTURED FREQUENCY TABLES: +1 INTEL".to_string());
+                        }
+                    } else {
+                        feedback.push(format!(">> NO MATCH: {} DEVICE SIGNATURE IS CLEAN.", name));
+                    }
+                    feedback.push(self.live_trace_confidence());
+                } else {
+                    feedback.push(format!("ERROR: ADVISOR '{}' NOT FOUND.", target));
+                    // No cost if not found
+                }
+            }
+            Directive::Consult(target) => {
+                turn_ended = false;
+
+                if let Some(message) = Self::missing_target("CONSULT -n", &target)
+                    .or_else(|| self.ambiguous_target(&target))
+                {
+                    feedback.push(message);
+                    return (feedback, false);
+                }
+
+                // An unknown name costs nothing and does not use up the free consult.
+                let Some(idx) = self.match_advisors(&target).first().copied() else {
+                    feedback.push(format!("ERROR: ADVISOR '{}' NOT FOUND.", target));
+                    return (feedback, false);
+                };
+
+                // Cost Logic: First one is free, subsequent cost 1 Intel
+                if self.consult_count > 0 {
+                    if self.intel_points == 0 {
+                        feedback.push(
+                            "FAILURE: INSUFFICIENT INTEL ASSETS FOR ADDITIONAL CONSULTATION."
+                                .to_string(),
+                        );
+                        return (feedback, false);
+                    }
+                    self.spend_intel("consult", 1);
+                }
+                self.consult_count += 1;
+
+                let adv = &self.state.advisors[idx];
+                let cost_msg = if self.consult_count > 1 {
+                    "(INTEL COST: 1)"
+                } else {
+                    "(STANDARD PROTOCOL)"
+                };
+                feedback.push(format!(
+                    "CONSULTING WITH {}... {}",
+                    adv.name.to_uppercase(),
+                    cost_msg
+                ));
+
+                let advice = self.advisor_advice(adv);
+                feedback.push(format!("\"{}\"", advice));
+                if let Some(hint) = self.doppelganger_hint(adv) {
+                    feedback.push(format!("(ASIDE) \"{}\"", hint));
+                }
+                let name = adv.name.clone();
+                self.note_counsel(name, &advice);
+            }
+            Directive::Overclock => {
+                turn_ended = false;
+                if self.overclocked_this_turn {
+                    feedback.push(
+                        "FAILURE: DECODER ARRAY ALREADY RUNNING PAST SPEC THIS CYCLE.".to_string(),
+                    );
+                    return (feedback, false);
+                }
+                self.overclocked_this_turn = true;
+                self.credit_intel("overclock", OVERCLOCK_INTEL);
+                self.state.system_corruption =
+                    (self.state.system_corruption + OVERCLOCK_CORRUPTION).min(1.0);
+                feedback.push(format!(
+                    "DECODER ARRAY OVERCLOCKED. INTEL +{}.",
+                    OVERCLOCK_INTEL
+                ));
+                feedback.push(format!(
+                    "WARNING: SAFETY INTERLOCKS BYPASSED. SYSTEM CORRUPTION +{:.2}.",
+                    OVERCLOCK_CORRUPTION
+                ));
+                if self.rng.random_bool(OVERCLOCK_GARBLE_CHANCE) {
+                    self.garble_next_feed = true;
+                    feedback.push(
+                        "WARNING: DECODERS OVERHEATING. TOMORROW'S CABLES WILL ARRIVE GARBLED."
+                            .to_string(),
+                    );
+                }
+            }
+            Directive::RoundTable => {
+                turn_ended = false;
+
+                let cost = self.round_table_cost();
+                if self.intel_points < cost {
+                    feedback.push(format!(
+                        "FAILURE: INSUFFICIENT INTEL ASSETS TO CONVENE THE CABINET (REQ: {}).",
+                        cost
+                    ));
+                    return (feedback, false);
+                }
+                self.spend_intel("round table", cost);
+
+                feedback.push(format!("CONVENING ROUND TABLE... (INTEL COST: {})", cost));
+                let advice: Vec<(String, String, Option<&str>)> = self
+                    .state
+                    .advisors
+                    .iter()
+                    .map(|adv| {
+                        let hint = self.doppelganger_hint(adv);
+                        (adv.name.clone(), self.advisor_advice(adv), hint)
+                    })
+                    .collect();
+                for (name, advice, hint) in advice {
+                    feedback.push(format!("{}: \"{}\"", name.to_uppercase(), advice));
+                    if let Some(hint) = hint {
+                        feedback.push(format!("(ASIDE) \"{}\"", hint));
+                    }
+                    self.note_counsel(name, &advice);
+                }
+            }
+            Directive::Interrogate(target) => {
+                turn_ended = false;
+
+                if let Some(message) = Self::missing_target("INTERROGATE -n", &target)
+                    .or_else(|| self.ambiguous_target(&target))
+                {
+                    feedback.push(message);
+                    return (feedback, false);
+                }
+
                 // Limit Logic: Max 2 per turn
                 if self.interrogations_this_turn >= 2 {
                     feedback.push(
@@ -386,27 +1901,24 @@ impl GameEngine {
                 }
 
                 // Find Advisor
-                let target_lower = target.to_lowercase();
-                let advisor_idx = self.state.advisors.iter().position(|a| {
-                    a.name.to_lowercase().contains(&target_lower)
-                        || format!("{:?}", a.role)
-                            .to_lowercase()
-                            .contains(&target_lower)
-                });
+                let advisor_idx = self.match_advisors(&target).first().copied();
 
                 if let Some(idx) = advisor_idx {
-                    let advisor = &mut self.state.advisors[idx];
-
                     // Unique Target Logic: Cannot interrogate same person twice in one turn
-                    if self.interrogated_advisors.contains(&advisor.name) {
+                    if self
+                        .interrogated_advisors
+                        .contains(&self.state.advisors[idx].name)
+                    {
                         feedback.push(format!(
                             "FAILURE: SUBJECT '{}' ALREADY QUESTIONED THIS CYCLE.",
-                            advisor.name
+                            self.state.advisors[idx].name
                         ));
                         return (feedback, false);
                     }
 
-                    self.intel_points -= 2;
+                    self.spend_intel("interrogate", 2);
+                    self.stats.interrogations += 1;
+                    let advisor = &mut self.state.advisors[idx];
                     self.interrogations_this_turn += 1;
                     self.interrogated_advisors.push(advisor.name.clone());
 
@@ -470,11 +1982,89 @@ impl GameEngine {
                         }
                     }
                 } else {
+                    // Nothing is charged until a subject is found.
                     feedback.push(format!("ERROR: ADVISOR '{}' NOT FOUND.", target));
-                    self.intel_points += 2; // Refund
                 }
             }
-            Directive::Decrypt(target_id) => {
+            Directive::Decrypt(target_id) => {
+                let target_id = self.document_id(&target_id);
+                turn_ended = false;
+                if self.pending_documents.is_empty() {
+                    feedback.push(NO_DOCUMENTS.to_string());
+                    return (feedback, false);
+                }
+                if self.intel_points == 0 {
+                    feedback
+                        .push("FAILURE: INSUFFICIENT INTEL ASSETS. YOU MUST ACT NOW.".to_string());
+                    return (feedback, false);
+                }
+
+                // Only spend intel once we know there is something to crack.
+                match self
+                    .pending_documents
+                    .iter()
+                    .position(|d| d.id == target_id)
+                {
+                    Some(idx) if self.pending_documents[idx].is_encrypted => {
+                        self.spend_intel("decrypt", 1);
+                        self.stats.decrypts += 1;
+                        let failure_chance = (self.state.system_corruption - 0.5).max(0.0)
+                            * 2.0
+                            * DECRYPT_FAILURE_MAX;
+                        let doc = &mut self.pending_documents[idx];
+                        if self.rng.random_bool(failure_chance) {
+                            feedback.push(
+                                "WARNING: DECRYPTION CORRUPTED BY SYSTEM INTERFERENCE.".to_string(),
+                            );
+                            feedback.push(format!(
+                                "CONTENT: {}",
+                                scramble_text(&doc.content, self.rng.as_mut())
+                            ));
+                            feedback.push(format!("DOCUMENT {} REMAINS ENCRYPTED.", target_id));
+                            return (feedback, false);
+                        }
+                        doc.decrypt_progress += 1;
+                        if doc.decrypt_progress < doc.decrypt_cost() {
+                            feedback.push(format!(
+                                "PARTIAL DECRYPTION: DOCUMENT {} {}/{} CRACKED.",
+                                target_id,
+                                doc.decrypt_progress,
+                                doc.decrypt_cost()
+                            ));
+                            feedback
+                                .push(format!("CONTENT: {}", doc.partial_text(self.rng.as_mut())));
+                            feedback.push(
+                                "THE WORK IS KEPT. THE DOCUMENT STAYS ON THE BENCH ACROSS TURNS."
+                                    .to_string(),
+                            );
+                            return (feedback, false);
+                        }
+                        doc.is_encrypted = false;
+                        if doc.is_alarmist() {
+                            self.last_alarm = Some(AlarmContext::from_document(doc));
+                        }
+                        feedback.push(format!("SUCCESS: DOCUMENT {} DECRYPTED.", target_id));
+                        feedback.push(format!("CONTENT: {}", doc.content));
+                        if doc.content.contains("DOPPELGANGER")
+                            && self.state.doppelganger_countdown.is_none()
+                            && !self.state.doppelganger_active
+                            && !self.state.doppelganger_resolved
+                        {
+                            self.state.doppelganger_countdown = Some(DOPPELGANGER_FUSE);
+                        }
+                    }
+                    Some(_) => {
+                        feedback.push(format!(
+                            "NOTICE: DOCUMENT {} IS ALREADY READABLE. NO INTEL SPENT.",
+                            target_id
+                        ));
+                    }
+                    None => {
+                        feedback.push(format!("ERROR: DOCUMENT {} NOT FOUND.", target_id));
+                    }
+                }
+            }
+            Directive::Triage => {
                 turn_ended = false;
                 if self.intel_points == 0 {
                     feedback
@@ -482,65 +2072,62 @@ impl GameEngine {
                     return (feedback, false);
                 }
 
-                self.intel_points -= 1;
-                let mut found = false;
-                for doc in &mut self.pending_documents {
-                    if doc.id == target_id {
-                        if doc.is_encrypted {
-                            doc.is_encrypted = false;
-                            feedback.push(format!("SUCCESS: DOCUMENT {} DECRYPTED.", target_id));
-                            feedback.push(format!("CONTENT: {}", doc.content));
-                        } else {
-                            feedback.push(format!(
-                                "NOTICE: DOCUMENT {} WAS NOT ENCRYPTED. (Intel Asset Wasted)",
-                                target_id
-                            ));
-                        }
-                        found = true;
-                        break;
+                let pick = triage(&self.pending_documents)
+                    .map(|doc| (doc.id.clone(), doc.doc_type.label()));
+                match pick {
+                    Some((id, label)) => {
+                        self.spend_intel("triage", 1);
+                        feedback.push(format!(
+                            "TRIAGE COMPLETE: {} DOCUMENTS RANKED.",
+                            self.pending_documents.len()
+                        ));
+                        feedback.push(format!("PRIORITY FOR ANALYSIS: {} ({})", id, label));
                     }
-                }
-                if !found {
-                    feedback.push(format!("ERROR: DOCUMENT {} NOT FOUND.", target_id));
-                    self.intel_points += 1;
+                    None => feedback.push(NO_DOCUMENTS.to_string()),
                 }
             }
             Directive::Analyze(target_id) => {
+                let target_id = self.document_id(&target_id);
                 turn_ended = false;
+                if self.pending_documents.is_empty() {
+                    feedback.push(NO_DOCUMENTS.to_string());
+                    return (feedback, false);
+                }
                 if self.intel_points == 0 {
                     feedback
                         .push("FAILURE: INSUFFICIENT INTEL ASSETS. YOU MUST ACT NOW.".to_string());
                     return (feedback, false);
                 }
 
-                self.intel_points -= 1;
-                let mut found = false;
-                for doc in &self.pending_documents {
-                    if doc.id == target_id {
-                        let integrity = (doc.reliability * 100.0) as u32;
-                        let assessment = if integrity > 80 {
-                            "HIGH (VERIFIED)"
-                        } else if integrity > 50 {
-                            "MODERATE (UNCERTAIN)"
-                        } else {
-                            "LOW (POSSIBLE DISINFORMATION)"
-                        };
-
-                        feedback.push(format!("ANALYSIS COMPLETE: DOCUMENT {}", target_id));
-                        feedback.push(format!(
-                            "SOURCE RELIABILITY: {}% - {}",
-                            integrity, assessment
-                        ));
-                        found = true;
-                        break;
+                self.spend_intel("analyze", 1);
+                let Some(doc) = self.pending_documents.iter().find(|d| d.id == target_id) else {
+                    feedback.push(format!("ERROR: DOCUMENT {} NOT FOUND.", target_id));
+                    self.credit_intel(REFUND, 1);
+                    return (feedback, false);
+                };
+                feedback.extend(doc.analysis(&self.state));
+                self.stats.analyses += 1;
+                self.analyzed_reliability.push(doc.apparent_reliability());
+                if let Some(alarm) = self.last_alarm.as_mut() {
+                    if alarm.doc_id == doc.id {
+                        alarm.analyzed = true;
                     }
                 }
-                if !found {
-                    feedback.push(format!("ERROR: DOCUMENT {} NOT FOUND.", target_id));
-                    self.intel_points += 1;
+                let sender = doc.sender().map(str::to_string);
+                if let Some(line) = sender.and_then(|name| self.weigh_memo(&name)) {
+                    feedback.push(line);
                 }
             }
             Directive::Escalate => {
+                if let Some(alarm) = self.last_alarm.take() {
+                    if !alarm.analyzed && alarm.reliability < FALSE_INTEL_RELIABILITY {
+                        self.state.accidental_escalation_risk += FALSE_INTEL_RISK;
+                        feedback.push(format!(
+                            "ACTED ON FALSE INTEL: SOURCE OF {} WAS UNRELIABLE. ESCALATION RISK +{:.2}",
+                            alarm.doc_id, FALSE_INTEL_RISK
+                        ));
+                    }
+                }
                 if self.rng.random_bool(0.6) {
                     self.state.global_tension += 0.2;
                     self.state.foreign_paranoia += 0.2;
@@ -563,7 +2150,8 @@ impl GameEngine {
                 }
             }
             Directive::Contain => {
-                if self.state.foreign_paranoia > 0.6 {
+                self.stats.contains += 1;
+                if self.state.foreign_paranoia > self.state.enemy_posture.contain_threshold() {
                     feedback.push(
                         "Diplomacy FAILED. Enemy interprets silence as preparation for war."
                             .to_string(),
@@ -578,6 +2166,7 @@ impl GameEngine {
                 }
             }
             Directive::Leak => {
+                self.stats.leaks += 1;
                 self.state.internal_secrecy -= 0.25;
                 self.state.domestic_stability += 0.2;
                 self.state.foreign_paranoia -= 0.05;
@@ -590,10 +2179,18 @@ impl GameEngine {
                 feedback
                     .push("Total withdrawal ordered. We are naked before our enemies.".to_string());
                 feedback.push("Rumors of a military tribunal are circulating.".to_string());
+                self.standdown_penalty_turns = STANDDOWN_AFTERMATH_TURNS;
             }
         }
 
         if turn_ended {
+            if self
+                .turn_limit
+                .is_some_and(|limit| self.turn_count >= limit)
+            {
+                self.shift_complete = true;
+            }
+
             // PASSIVE ESCALATION
             if self.state.global_tension > 0.3 {
                 self.state.global_tension += 0.03;
@@ -602,18 +2199,25 @@ impl GameEngine {
                 self.state.secret_weapon_progress += 0.02;
             }
 
-            // Random chance for Red Phone if mole isn't found yet but tension is high
-            if self.state.global_tension > 0.8 && self.rng.random_bool(0.1) {
+            // Random chance for Red Phone if mole isn't found yet but tension is high.
+            // Not straight after another crisis, though: that just feels unfair.
+            if self.state.global_tension > 0.8
+                && !self.crisis_cooling_down()
+                && self.rng.random_bool(0.1)
+            {
                 self.state.red_phone_active = true;
             }
 
-            self.state.global_tension = self.state.global_tension.clamp(0.0, 1.0);
-            self.state.internal_secrecy = self.state.internal_secrecy.clamp(0.0, 1.0);
-            self.state.foreign_paranoia = self.state.foreign_paranoia.clamp(0.0, 1.0);
-            self.state.accidental_escalation_risk =
-                self.state.accidental_escalation_risk.clamp(0.0, 1.0);
-            self.state.domestic_stability = self.state.domestic_stability.clamp(0.0, 1.0);
-            self.state.secret_weapon_progress = self.state.secret_weapon_progress.clamp(0.0, 1.0);
+            // Or the other side blinks first and offers terms.
+            if self.hotline_mood() && self.rng.random_bool(HOTLINE_CHANCE) {
+                self.state.hotline_active = true;
+            }
+            self.tick_doppelganger();
+
+            self.state.clamp_metrics();
+
+            // Crumbling home front: the generals make their move.
+            self.state.check_coup_trigger();
 
             if self.state.accidental_escalation_risk > 0.6 && self.rng.random_bool(0.3) {
                 self.state.global_tension += 0.15;
@@ -631,9 +2235,1177 @@ impl GameEngine {
                     " THE BASILISK IS SPEAKING TO THE OPERATORS. THEY ARE WEEPING.".to_string(),
                 );
             }
+
+            let worsening = (self.state.global_tension - tension_before)
+                + (stability_before - self.state.domestic_stability);
+            feedback.extend(self.weigh_counsel(&executed, worsening));
         }
 
         self.state.system_corruption = self.state.system_corruption.clamp(0.0, 1.0);
         (feedback, turn_ended)
     }
 }
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Cables for turn `turn` drawn from `state` with `rng`, at least one of
+/// them encrypted.
+fn generate_feed(
+    state: &WorldState,
+    generators: &GeneratorRegistry,
+    volume: Volume,
+    turn: u32,
+    rng: &mut dyn Rng,
+) -> Vec<Document> {
+    let doc_count = volume.scale_docs(if turn >= 7 {
+        5
+    } else if turn >= 4 {
+        4
+    } else {
+        3
+    });
+    let mut docs = Document::generate_batch(state, doc_count, turn, generators, rng);
+    if !docs.iter().any(|d| d.is_encrypted) && !docs.is_empty() {
+        docs[0].is_encrypted = true;
+    }
+    docs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crisis::{self, Crisis};
+
+    fn fresh_engine() -> GameEngine {
+        let mut engine = GameEngine::new();
+        engine.start_turn();
+        engine
+    }
+
+    #[test]
+    fn a_five_advisor_roster_always_has_exactly_one_mole() {
+        let roster: Vec<Advisor> = (0..5)
+            .map(|i| Advisor {
+                name: format!("Advisor {}", i),
+                role: AdvisorRole::General,
+                suspicion: 0,
+                is_mole: false,
+                is_double_agent: false,
+                leads: 0,
+            })
+            .collect();
+        let mut seated = [false; 5];
+        for seed in 0..200 {
+            let mut engine = GameEngine::with_roster(roster.clone());
+            engine.reseed(seed);
+            let moles: Vec<usize> = (0..5)
+                .filter(|&i| engine.state.advisors[i].is_mole)
+                .collect();
+            assert_eq!(moles.len(), 1, "seed {}", seed);
+            seated[moles[0]] = true;
+        }
+        // Nobody past the third seat is left out of the draw.
+        assert!(seated.iter().all(|&s| s), "{:?}", seated);
+    }
+
+    #[test]
+    fn a_seed_replays_the_mole_and_every_cable() {
+        let deal = |seed| {
+            let mut engine = GameEngine::new();
+            engine.reseed(seed);
+            let mole = engine.state.advisors.iter().position(|a| a.is_mole);
+            let mut feed = Vec::new();
+            for _ in 0..3 {
+                engine.start_turn();
+                feed.extend(
+                    engine
+                        .pending_documents
+                        .iter()
+                        .map(|doc| format!("{} {}", doc.id, doc.content)),
+                );
+                engine.resolve_directive(Directive::Investigate);
+            }
+            (mole, feed)
+        };
+        assert_eq!(deal(42), deal(42));
+        assert_ne!(deal(42).1, deal(43).1);
+    }
+
+    #[test]
+    fn escalate_always_raises_tension() {
+        for _ in 0..200 {
+            let mut engine = fresh_engine();
+            let result = engine.resolve_directive_traced(Directive::Escalate);
+            assert!(result.turn_ended);
+            assert!(!result.feedback.is_empty());
+            let deltas = result.deltas();
+            assert!(deltas.global_tension > 0.0);
+            assert!(deltas.foreign_paranoia > 0.0 || deltas.accidental_escalation_risk > 0.0);
+        }
+    }
+
+    #[test]
+    fn stand_down_always_lowers_tension() {
+        for _ in 0..200 {
+            let mut engine = fresh_engine();
+            engine.state.global_tension = 0.6;
+            let result = engine.resolve_directive_traced(Directive::StandDown);
+            let deltas = result.deltas();
+            assert!(deltas.global_tension < 0.0);
+            assert!(deltas.foreign_paranoia < 0.0);
+            assert!(deltas.domestic_stability < 0.0);
+        }
+    }
+
+    #[test]
+    fn investigate_feeds_the_project() {
+        let mut engine = fresh_engine();
+        engine.state.secret_weapon_progress = 0.6;
+        let deltas = engine
+            .resolve_directive_traced(Directive::Investigate)
+            .deltas();
+        assert!(deltas.secret_weapon_progress > 0.0);
+        assert!(deltas.system_corruption > 0.0);
+    }
+
+    #[test]
+    fn leak_trades_secrecy_for_stability() {
+        let mut engine = fresh_engine();
+        engine.state.domestic_stability = 0.5;
+        let deltas = engine.resolve_directive_traced(Directive::Leak).deltas();
+        assert!(deltas.internal_secrecy < 0.0);
+        assert!(deltas.domestic_stability > 0.0);
+    }
+
+    #[test]
+    fn surviving_a_calm_shift_is_a_victory() {
+        let mut engine = fresh_engine();
+        engine.turn_limit = Some(1);
+        engine.state.global_tension = 0.1;
+        for advisor in &mut engine.state.advisors {
+            advisor.is_mole = false;
+        }
+        engine.resolve_directive(Directive::Leak);
+        assert_eq!(engine.ending(), Some(Ending::Victory));
+    }
+
+    #[test]
+    fn surviving_with_the_mole_loose_is_not_a_victory() {
+        let mut engine = fresh_engine();
+        engine.turn_limit = Some(1);
+        engine.state.global_tension = 0.1;
+        engine.resolve_directive(Directive::Leak);
+        assert_eq!(engine.ending(), Some(Ending::SimulationEnd));
+    }
+
+    #[test]
+    fn no_ending_before_the_turn_limit() {
+        let mut engine = fresh_engine();
+        engine.turn_limit = Some(5);
+        engine.resolve_directive(Directive::Leak);
+        assert_eq!(engine.ending(), None);
+    }
+
+    #[test]
+    fn decrypting_plaintext_costs_nothing() {
+        let mut engine = fresh_engine();
+        engine.pending_documents[0].is_encrypted = false;
+        let id = engine.pending_documents[0].id.clone();
+        let intel_before = engine.intel_points;
+
+        let (feedback, turn_ended) = engine.resolve_directive(Directive::Decrypt(id));
+        assert!(!turn_ended);
+        assert_eq!(engine.intel_points, intel_before);
+        assert!(feedback[0].contains("ALREADY READABLE"));
+    }
+
+    #[test]
+    fn decrypting_ciphertext_spends_one_intel() {
+        let mut engine = fresh_engine();
+        engine.pending_documents[0].is_encrypted = true;
+        let id = engine.pending_documents[0].id.clone();
+        let intel_before = engine.intel_points;
+
+        engine.resolve_directive(Directive::Decrypt(id));
+        assert_eq!(engine.intel_points, intel_before - 1);
+        assert!(!engine.pending_documents[0].is_encrypted);
+    }
+
+    #[test]
+    fn intel_directives_leave_the_world_untouched() {
+        let mut engine = fresh_engine();
+        let result = engine.resolve_directive_traced(Directive::Analyze("DOC-NONE".into()));
+        assert!(!result.turn_ended);
+        let deltas = result.deltas();
+        assert_eq!(deltas.global_tension, 0.0);
+        assert_eq!(deltas.domestic_stability, 0.0);
+    }
+
+    #[test]
+    fn volume_scales_the_feed_and_keeps_a_cipher() {
+        for (volume, docs, intel) in [(Volume::Low, 2, 1), (Volume::High, 5, 2)] {
+            let mut engine = GameEngine::new();
+            engine.volume = volume;
+            engine.start_turn();
+            assert_eq!(engine.pending_documents.len(), docs);
+            assert_eq!(engine.max_intel_points, intel);
+            assert!(engine.pending_documents.iter().any(|d| d.is_encrypted));
+        }
+    }
+
+    #[test]
+    fn cold_trace_costs_more_and_never_confirms() {
+        for _ in 0..50 {
+            let mut engine = fresh_engine();
+            engine.interruption_active = false;
+            engine.intel_points = COLD_TRACE_COST;
+            let mole = engine
+                .state
+                .advisors
+                .iter()
+                .find(|a| a.is_mole)
+                .map(|a| a.name.clone())
+                .unwrap();
+
+            let (feedback, turn_ended) = engine.resolve_directive(Directive::Trace(mole));
+            assert!(!turn_ended);
+            assert_eq!(engine.intel_points, 0);
+            assert!(!engine.state.red_phone_active);
+            assert!(feedback.iter().any(|l| l.starts_with("CONFIDENCE: LOW")));
+        }
+
+        // However often it matches, a cold trace is only ever a hint.
+        let mut engine = fresh_engine();
+        engine.set_rng(Box::new(Fated));
+        engine.interruption_active = false;
+        let mole = engine
+            .state
+            .advisors
+            .iter()
+            .position(|a| a.is_mole)
+            .unwrap();
+        let name = engine.state.advisors[mole].name.clone();
+        for _ in 0..10 {
+            engine.traces_this_turn = 0;
+            engine.traced_advisors.clear();
+            engine.intel_points = COLD_TRACE_COST;
+            engine.resolve_directive(Directive::Trace(name.clone()));
+        }
+        assert_eq!(engine.state.advisors[mole].suspicion, EVIDENCE_CEILING);
+    }
+
+    #[test]
+    fn corrupted_decrypts_still_cost_intel() {
+        let mut garbled = 0;
+        for _ in 0..100 {
+            let mut engine = fresh_engine();
+            engine.basilisk_override = false;
+            engine.state.system_corruption = 1.0;
+            engine.intel_points = 1;
+            let id = engine
+                .pending_documents
+                .iter()
+                .find(|d| d.is_encrypted)
+                .map(|d| d.id.clone())
+                .unwrap();
+
+            let (feedback, _) = engine.resolve_directive(Directive::Decrypt(id.clone()));
+            assert_eq!(engine.intel_points, 0);
+            if feedback[0].contains("CORRUPTED") {
+                garbled += 1;
+                let doc = engine
+                    .pending_documents
+                    .iter()
+                    .find(|d| d.id == id)
+                    .unwrap();
+                assert!(doc.is_encrypted);
+            }
+        }
+        assert!(garbled > 0 && garbled < 100);
+    }
+
+    #[test]
+    fn suspicion_drifts_between_turns() {
+        let mut engine = fresh_engine();
+        engine.state.internal_secrecy = 0.1;
+        for advisor in &mut engine.state.advisors {
+            advisor.suspicion = if advisor.is_mole { 78 } else { 50 };
+        }
+
+        engine.start_turn();
+        for advisor in &engine.state.advisors {
+            if advisor.is_mole {
+                assert_eq!(advisor.suspicion, MOLE_CREEP_CEILING);
+            } else {
+                assert_eq!(advisor.suspicion, 50 - DEFAULT_SUSPICION_DECAY);
+            }
+        }
+    }
+
+    #[test]
+    fn doppelganger_cable_lights_the_fuse() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.intel_points = 1;
+        engine.pending_documents[0].is_encrypted = true;
+        engine.pending_documents[0].content =
+            "EYES ONLY: THE PRESIDENT IS A DOPPELGANGER.".to_string();
+        let id = engine.pending_documents[0].id.clone();
+
+        engine.resolve_directive(Directive::Decrypt(id));
+        assert_eq!(engine.state.doppelganger_countdown, Some(DOPPELGANGER_FUSE));
+
+        // The round table hears the asides too.
+        engine.intel_points = engine.round_table_cost();
+        let (feedback, _) = engine.resolve_directive(Directive::RoundTable);
+        assert!(feedback.iter().any(|line| line.starts_with("(ASIDE)")));
+
+        for _ in 0..DOPPELGANGER_FUSE {
+            assert!(!engine.state.doppelganger_active);
+            engine.start_turn();
+            engine.resolve_directive(Directive::Contain);
+        }
+        // Due as the last day closes, before another one starts.
+        assert!(engine.state.doppelganger_active);
+        assert_eq!(engine.state.doppelganger_countdown, None);
+    }
+
+    #[test]
+    fn every_directive_documents_whether_the_turn_ends() {
+        let all = [
+            Directive::Escalate,
+            Directive::Investigate,
+            Directive::Contain,
+            Directive::Leak,
+            Directive::StandDown,
+            Directive::Decrypt(String::new()),
+            Directive::Analyze(String::new()),
+            Directive::Triage,
+            Directive::Trace(String::new()),
+            Directive::Consult(String::new()),
+            Directive::Interrogate(String::new()),
+            Directive::RoundTable,
+        ];
+        for directive in all {
+            let last = directive.effects().last().unwrap();
+            assert!(last.contains("TURN"), "{}", directive.label());
+        }
+    }
+
+    #[test]
+    fn ignored_priority_cables_are_lost() {
+        let mut engine = fresh_engine();
+        engine.pending_documents[0].is_encrypted = true;
+        engine.pending_documents[0].priority = true;
+        let id = engine.pending_documents[0].id.clone();
+        let tension = engine.state.global_tension;
+
+        engine.start_turn();
+        assert!(engine.archive.iter().all(|d| d.id != id));
+        assert!(engine.bulletins.iter().any(|b| b.contains(&id)));
+        assert!(engine.state.global_tension > tension);
+    }
+
+    #[test]
+    fn confidence_follows_analysis_and_corruption() {
+        let mut engine = fresh_engine();
+        assert_eq!(engine.intel_confidence(), 0.5);
+
+        engine.basilisk_override = false;
+        engine.intel_points = 1;
+        engine.pending_documents[0].reliability = 0.9;
+        let id = engine.pending_documents[0].id.clone();
+        engine.resolve_directive(Directive::Analyze(id));
+        assert!((engine.intel_confidence() - 0.9).abs() < 1e-9);
+
+        engine.state.system_corruption = 0.5;
+        assert!((engine.intel_confidence() - 0.45).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_inbox_is_reported_without_cost() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.pending_documents.clear();
+        engine.intel_points = 1;
+
+        for directive in [
+            Directive::Decrypt("DOC-0001".to_string()),
+            Directive::Analyze("DOC-0001".to_string()),
+            Directive::Triage,
+        ] {
+            let (feedback, turn_ended) = engine.resolve_directive(directive);
+            assert!(!turn_ended);
+            assert_eq!(feedback, vec![NO_DOCUMENTS.to_string()]);
+            assert_eq!(engine.intel_points, 1);
+        }
+
+        // The next turn still archives cleanly from an empty inbox.
+        engine.start_turn();
+        assert!(!engine.pending_documents.is_empty());
+    }
+
+    #[test]
+    fn live_trace_confirms_only_the_mole() {
+        for (target, mole) in [(0, 0), (1, 0)] {
+            let mut engine = GameEngine::with_mole(mole);
+            engine.start_turn();
+            engine.basilisk_override = false;
+            engine.interruption_active = true;
+            engine.intel_points = 1;
+            let name = engine.state.advisors[target].name.clone();
+
+            let (feedback, _) = engine.resolve_directive(Directive::Trace(name));
+            let confirmed = feedback.iter().any(|l| l.contains("MATCH CONFIRMED"));
+            assert_eq!(confirmed, target == mole);
+            assert_eq!(engine.state.red_phone_active, target == mole);
+        }
+    }
+
+    #[test]
+    fn interrogation_costs_two_intel_against_anyone() {
+        let mut engine = GameEngine::with_mole(2);
+        engine.start_turn();
+        engine.basilisk_override = false;
+        engine.intel_points = 2;
+        let name = engine.state.advisors[2].name.clone();
+
+        let (_, turn_ended) = engine.resolve_directive(Directive::Interrogate(name.clone()));
+        assert!(!turn_ended);
+        assert_eq!(engine.intel_points, 0);
+        assert!(engine.interrogated_advisors.contains(&name));
+    }
+
+    #[test]
+    fn empty_targets_cost_nothing() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.intel_points = 2;
+
+        for target in ["", "  ", "-n"] {
+            let (feedback, ended) = engine.resolve_directive(Directive::Consult(target.into()));
+            assert!(!ended);
+            assert!(feedback[0].starts_with("USAGE: CONSULT"), "{:?}", feedback);
+            let (feedback, _) = engine.resolve_directive(Directive::Interrogate(target.into()));
+            assert!(
+                feedback[0].starts_with("USAGE: INTERROGATE"),
+                "{:?}",
+                feedback
+            );
+        }
+        assert_eq!(engine.intel_points, 2);
+        assert_eq!(engine.consult_count, 0);
+        assert_eq!(engine.interrogations_this_turn, 0);
+        assert!(engine.intel_ledger.is_empty());
+    }
+
+    #[test]
+    fn consulting_nobody_keeps_the_free_consult() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.intel_points = 1;
+
+        // A miss before the first real consult does not use up the free one.
+        engine.resolve_directive(Directive::Consult("nobody".into()));
+        assert_eq!((engine.intel_points, engine.consult_count), (1, 0));
+        engine.resolve_directive(Directive::Consult("vance".into()));
+        assert_eq!((engine.intel_points, engine.consult_count), (1, 1));
+        let (feedback, _) = engine.resolve_directive(Directive::Consult("nobody".into()));
+        assert!(feedback[0].contains("NOT FOUND"));
+        assert_eq!((engine.intel_points, engine.consult_count), (1, 1));
+
+        engine.resolve_directive(Directive::Consult("sterling".into()));
+        assert_eq!((engine.intel_points, engine.consult_count), (0, 2));
+        assert_eq!(engine.intel_ledger, vec![("consult", -1)]);
+    }
+
+    #[test]
+    fn advice_points_at_the_cable_that_matters() {
+        let mut engine = GameEngine::with_mole(0);
+        engine.start_turn();
+        let mut flash = engine.pending_documents[0].clone();
+        flash.is_encrypted = false;
+        flash.doc_type = DocumentType::IntelligenceCable;
+        flash.content = "FLASH: MASSIVE HEAT SIGNATURES DETECTED NEAR NORTH SEA FLOTILLA.".into();
+        engine.pending_documents = vec![flash];
+
+        let loyal = engine.state.advisors[1].clone();
+        let mole = engine.state.advisors[0].clone();
+        let take = |engine: &GameEngine, adv: &Advisor, worried: bool| {
+            engine.cable_take(adv, &engine.pending_documents[0], worried)
+        };
+        let advice = engine.advisor_advice(&loyal);
+        assert!(
+            advice.starts_with(&take(&engine, &loyal, true)),
+            "{}",
+            advice
+        );
+        assert!(advice.contains("the NORTH SEA FLOTILLA"), "{}", advice);
+        let advice = engine.advisor_advice(&mole);
+        assert!(
+            advice.starts_with(&take(&engine, &mole, false)),
+            "{}",
+            advice
+        );
+        assert!(advice.contains("(Recommend: "));
+
+        // On a quiet cable the two swap sides.
+        engine.pending_documents[0].content =
+            "INTEL: NORTH SEA FLOTILLA LOGISTICS TRAIN MOVING SLOWLY.".into();
+        let advice = engine.advisor_advice(&loyal);
+        assert!(
+            advice.starts_with(&take(&engine, &loyal, false)),
+            "{}",
+            advice
+        );
+        let advice = engine.advisor_advice(&mole);
+        assert!(
+            advice.starts_with(&take(&engine, &mole, true)),
+            "{}",
+            advice
+        );
+
+        // Neither side has a phrasing of its own: over a few days the loyal
+        // advisor shrugs in every way the mole does.
+        let shrugs = |engine: &mut GameEngine, adv: &Advisor| {
+            let mut seen = Vec::new();
+            for day in 0..CABLE_SHRUGS.len() as u32 {
+                engine.turn_count = day;
+                let mut line = take(engine, adv, false);
+                for colleague in &engine.state.advisors {
+                    line = line.replace(&colleague.name, "");
+                }
+                if !seen.contains(&line) {
+                    seen.push(line);
+                }
+            }
+            seen.sort();
+            seen
+        };
+        assert_eq!(shrugs(&mut engine, &loyal), shrugs(&mut engine, &mole));
+        engine.pending_documents[0].content =
+            "FLASH: MASSIVE HEAT SIGNATURES DETECTED NEAR NORTH SEA FLOTILLA.".into();
+
+        // Nothing readable in the inbox: back to the metrics alone.
+        engine.pending_documents[0].is_encrypted = true;
+        let advisor = engine.state.advisors[1].clone();
+        assert_eq!(
+            engine.advisor_advice(&advisor),
+            engine.standing_advice(&advisor)
+        );
+    }
+
+    #[test]
+    fn read_finds_documents_in_the_inbox_and_the_archive() {
+        let mut engine = fresh_engine();
+        let old_id = engine.pending_documents[0].id.clone();
+        engine.start_turn();
+        let new_id = engine.pending_documents[0].id.clone();
+
+        assert_eq!(
+            engine.find_document(&new_id.to_lowercase()).map(|d| &d.id),
+            Some(&new_id)
+        );
+        assert_eq!(engine.find_document(&old_id).map(|d| &d.id), Some(&old_id));
+        assert!(engine.find_document("DOC-NONE").is_none());
+    }
+
+    #[test]
+    fn events_weigh_the_advice_that_was_followed() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.resolve_directive(Directive::Consult("vance".into()));
+        engine.resolve_directive(Directive::Consult("vance".into()));
+        assert_eq!(engine.counsel.len(), 1);
+        let verb = engine.counsel[0].verb.clone();
+
+        // Advice nobody followed proves nothing.
+        assert!(engine.weigh_counsel("OVERCLOCK", 0.5).is_empty());
+        assert!(engine.counsel.is_empty());
+
+        engine.note_counsel("Gen. Vance".into(), "(Recommend: CONTAIN)");
+        let feedback = engine.weigh_counsel("CONTAIN", 0.2);
+        assert!(feedback[0].starts_with("EVENTS CONTRADICT GEN. VANCE'S COUNSEL"));
+        let vance = &engine.state.advisors[0];
+        assert_eq!((vance.suspicion, vance.leads), (COUNSEL_WRONG_SUSPICION, 1));
+
+        engine.note_counsel("Gen. Vance".into(), &format!("(Recommend: {})", verb));
+        engine.weigh_counsel(&verb, -0.2);
+        let vance = &engine.state.advisors[0];
+        assert_eq!(
+            vance.suspicion,
+            COUNSEL_WRONG_SUSPICION - COUNSEL_RIGHT_RELIEF
+        );
+    }
+
+    #[test]
+    fn ambiguous_targets_ask_for_clarification() {
+        let advisor = |name: &str, role| Advisor {
+            name: name.to_string(),
+            role,
+            suspicion: 0,
+            is_mole: false,
+            is_double_agent: false,
+            leads: 0,
+        };
+        let mut engine = GameEngine::with_roster(vec![
+            advisor("Gen. Vance", AdvisorRole::General),
+            advisor("Gen. Vale", AdvisorRole::General),
+            advisor("Amb. Sterling", AdvisorRole::Ambassador),
+        ]);
+        engine.start_turn();
+        engine.basilisk_override = false;
+        engine.intel_points = 2;
+
+        let (feedback, _) = engine.resolve_directive(Directive::Interrogate("va".to_string()));
+        assert!(feedback[0].contains("AMBIGUOUS"), "{:?}", feedback);
+        assert!(feedback[0].contains("GEN. VANCE") && feedback[0].contains("GEN. VALE"));
+        assert_eq!(engine.intel_points, 2);
+
+        // A more specific query settles it.
+        engine.resolve_directive(Directive::Interrogate("gen. vale".to_string()));
+        assert_eq!(engine.interrogated_advisors, vec!["Gen. Vale".to_string()]);
+    }
+
+    #[test]
+    fn turn_end_reports_the_intel_ledger() {
+        let mut engine = GameEngine::with_mole(0);
+        engine.start_turn();
+        engine.basilisk_override = false;
+        engine.intel_points = 3;
+
+        engine.resolve_directive(Directive::Analyze("NO-SUCH-DOC".to_string()));
+        engine.resolve_directive(Directive::Interrogate("sterling".to_string()));
+        // Unknown subjects cost nothing, so nothing is handed back either.
+        engine.resolve_directive(Directive::Interrogate("nobody".to_string()));
+        assert_eq!(engine.intel_points, 1);
+
+        let (feedback, _) = engine.resolve_directive(Directive::Contain);
+        assert_eq!(
+            feedback.last().map(String::as_str),
+            Some("INTEL USED: analyze x1, interrogate x2, 1 refunded")
+        );
+        engine.start_turn();
+        assert!(engine
+            .intel_ledger
+            .iter()
+            .all(|(a, _)| *a == "double agent"));
+    }
+
+    #[test]
+    fn cables_can_be_named_by_inbox_position() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.intel_points = 2;
+        let second = engine.pending_documents[1].id.clone();
+        assert_eq!(engine.document_id("2"), second);
+        assert_eq!(engine.document_id("99"), "99");
+        assert_eq!(engine.document_id("0"), "0");
+        assert_eq!(engine.find_document("2").map(|d| &d.id), Some(&second));
+
+        let (feedback, _) = engine.resolve_directive(Directive::Analyze("2".to_string()));
+        assert_eq!(
+            feedback[0],
+            format!("ANALYSIS COMPLETE: DOCUMENT {}", second)
+        );
+
+        // Ids match in any case, as they do for `read`.
+        let typed = second.to_lowercase();
+        assert_eq!(engine.document_id(&typed), second);
+        assert!(engine.find_document(&typed).is_some());
+        let (feedback, _) = engine.resolve_directive(Directive::Analyze(typed));
+        assert_eq!(
+            feedback[0],
+            format!("ANALYSIS COMPLETE: DOCUMENT {}", second)
+        );
+    }
+
+    #[test]
+    fn decrypt_takes_an_id_in_any_case() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.intel_points = 1;
+        let id = engine
+            .pending_documents
+            .iter()
+            .find(|d| d.is_encrypted)
+            .map(|d| d.id.clone())
+            .unwrap();
+        let (feedback, _) = engine.resolve_directive(Directive::Decrypt(id.to_lowercase()));
+        assert!(
+            feedback.iter().all(|l| !l.contains("NOT FOUND")),
+            "{:?}",
+            feedback
+        );
+        assert_eq!(engine.intel_points, 0);
+    }
+
+    #[test]
+    fn defcon1_warns_once_per_crossing() {
+        let mut engine = fresh_engine();
+        assert!(!engine.take_defcon1_warning());
+        engine.state.global_tension = 0.92;
+        assert!(engine.take_defcon1_warning());
+        engine.state.global_tension = 0.97;
+        assert!(!engine.take_defcon1_warning());
+
+        engine.state.global_tension = 0.7;
+        assert!(!engine.take_defcon1_warning());
+        engine.state.global_tension = 0.9;
+        assert!(engine.take_defcon1_warning());
+    }
+
+    #[test]
+    fn the_menu_knows_what_cannot_be_afforded() {
+        let mut engine = fresh_engine();
+        engine.pending_documents[0].is_encrypted = true;
+        engine.intel_points = 1;
+        engine.interruption_active = false;
+        let blocker = |engine: &GameEngine, d: Directive| engine.menu_blocker(&d);
+
+        assert_eq!(blocker(&engine, Directive::Decrypt(String::new())), None);
+        assert_eq!(blocker(&engine, Directive::Consult(String::new())), None);
+        assert!(blocker(&engine, Directive::Trace(String::new()))
+            .unwrap()
+            .contains("COLD TRACE"));
+        engine.interruption_active = true;
+        assert_eq!(blocker(&engine, Directive::Trace(String::new())), None);
+        assert_eq!(
+            blocker(&engine, Directive::Interrogate(String::new())).as_deref(),
+            Some("NEEDS 2 INTEL")
+        );
+
+        engine.intel_points = 0;
+        engine.consult_count = 1;
+        for d in [
+            Directive::Decrypt(String::new()),
+            Directive::Analyze(String::new()),
+            Directive::Consult(String::new()),
+        ] {
+            assert_eq!(blocker(&engine, d).as_deref(), Some("NO INTEL LEFT"));
+        }
+        assert_eq!(blocker(&engine, Directive::Leak), None);
+    }
+
+    #[test]
+    fn the_mole_can_be_forced_onto_a_role() {
+        let mut engine = GameEngine::with_mole(0);
+        assert_eq!(
+            engine.force_mole_role(&AdvisorRole::Ambassador),
+            Ok("Amb. Sterling".to_string())
+        );
+        let moles: Vec<bool> = engine.state.advisors.iter().map(|a| a.is_mole).collect();
+        assert_eq!(moles, vec![false, false, true]);
+
+        engine
+            .state
+            .advisors
+            .retain(|a| a.role != AdvisorRole::Director);
+        let err = engine.force_mole_role(&AdvisorRole::Director).unwrap_err();
+        assert!(err.contains("'director'"), "{}", err);
+    }
+
+    #[test]
+    fn stats_tally_the_whole_run_net_of_refunds() {
+        let mut engine = GameEngine::with_mole(0);
+        engine.start_turn();
+        engine.basilisk_override = false;
+        engine.intel_points = 3;
+
+        engine.resolve_directive(Directive::Analyze("NO-SUCH-DOC".to_string()));
+        engine.resolve_directive(Directive::Interrogate("sterling".to_string()));
+        engine.state.global_tension = 0.9;
+        engine.resolve_directive(Directive::Leak);
+        engine.start_turn();
+        engine.resolve_directive(Directive::Contain);
+
+        let stats = &engine.stats;
+        assert_eq!(stats.intel_spent, 2);
+        assert_eq!((stats.analyses, stats.interrogations), (0, 1));
+        assert_eq!((stats.leaks, stats.contains), (1, 1));
+        assert!(stats.peak_tension >= 0.9);
+        assert_eq!(engine.turns_since_crisis(), None);
+        engine.crisis_fired();
+        engine.start_turn();
+        assert_eq!(engine.turns_since_crisis(), Some(1));
+
+        // Intel the tribunal withholds was never the player's to spend.
+        engine.intel_points = 3;
+        engine.standdown_penalty_turns = 1;
+        engine.run_standdown_aftermath();
+        assert_eq!(engine.intel_points, 2);
+        assert_eq!(engine.stats.intel_spent, 2);
+    }
+
+    #[test]
+    fn stand_down_fallout_tapers_over_three_turns() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.resolve_directive(Directive::StandDown);
+        engine.state.domestic_stability = 0.9;
+
+        let mut bleeds = Vec::new();
+        for _ in 0..4 {
+            let before = engine.state.domestic_stability;
+            engine.start_turn();
+            bleeds.push(((before - engine.state.domestic_stability) * 100.0).round());
+        }
+        assert_eq!(bleeds, vec![9.0, 6.0, 3.0, 0.0]);
+        assert_eq!(engine.standdown_penalty_turns, 0);
+    }
+
+    #[test]
+    fn repeated_escalation_hardens_the_enemy() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        for _ in 0..2 {
+            engine.resolve_directive(Directive::Escalate);
+            engine.start_turn();
+        }
+        assert_eq!(engine.state.enemy_posture, EnemyPosture::Aggressive);
+
+        // Paranoia a Probing enemy would shrug off now sinks diplomacy.
+        engine.state.foreign_paranoia = 0.5;
+        engine.state.global_tension = 0.3;
+        engine.resolve_directive(Directive::Contain);
+        assert!(engine.state.global_tension > 0.3);
+
+        for _ in 0..3 {
+            engine.resolve_directive(Directive::Contain);
+            engine.start_turn();
+        }
+        assert_eq!(engine.state.enemy_posture, EnemyPosture::Cautious);
+    }
+
+    #[test]
+    fn escalating_on_an_unchecked_shaky_alarm_is_penalized() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.intel_points = 1;
+        let doc = &mut engine.pending_documents[0];
+        doc.is_encrypted = false;
+        doc.content = "FLASH: HEAT SIGNATURES SPIKING.".to_string();
+        doc.reliability = 0.2;
+        let id = doc.id.clone();
+        let alarm = AlarmContext::from_document(&engine.pending_documents[0]);
+
+        engine.last_alarm = Some(alarm.clone());
+        let (feedback, _) = engine.resolve_directive(Directive::Escalate);
+        assert!(
+            feedback[0].starts_with("ACTED ON FALSE INTEL"),
+            "{:?}",
+            feedback
+        );
+        assert_eq!(engine.last_alarm, None);
+
+        // Checking the source first takes the sting out.
+        engine.last_alarm = Some(alarm);
+        engine.resolve_directive(Directive::Analyze(id));
+        let (feedback, _) = engine.resolve_directive(Directive::Escalate);
+        assert!(!feedback.iter().any(|l| l.contains("FALSE INTEL")));
+    }
+
+    #[test]
+    fn a_clean_victory_earns_the_dove_unless_you_escalated() {
+        let secure = |engine: &mut GameEngine| {
+            engine.basilisk_override = false;
+            engine.turn_limit = Some(engine.turn_count);
+            engine.state.global_tension = 0.1;
+            engine.state.secret_weapon_progress = 0.1;
+            for advisor in &mut engine.state.advisors {
+                advisor.is_mole = false;
+            }
+        };
+
+        let mut engine = fresh_engine();
+        secure(&mut engine);
+        engine.resolve_directive(Directive::Leak);
+        assert_eq!(engine.ending(), Some(Ending::Victory));
+        assert_eq!(engine.achievements, vec![Achievement::Dove]);
+
+        let mut hawk = fresh_engine();
+        hawk.basilisk_override = false;
+        hawk.resolve_directive(Directive::Escalate);
+        hawk.start_turn();
+        secure(&mut hawk);
+        hawk.resolve_directive(Directive::Leak);
+        assert_eq!(hawk.ending(), Some(Ending::Victory));
+        assert!(hawk.achievements.is_empty());
+    }
+
+    #[test]
+    fn turn_closings_keep_the_last_record_of_each_day() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.resolve_directive(Directive::Analyze("DOC-NONE".to_string()));
+        engine.resolve_directive(Directive::Leak);
+        engine.start_turn();
+        engine.resolve_directive(Directive::Leak);
+
+        let closings = engine.turn_closings();
+        assert_eq!(closings.len(), 2);
+        assert_eq!(closings[0].directive, "LEAK");
+        assert_eq!(closings[1].stability, engine.state.domestic_stability);
+    }
+
+    /// Says yes to every roll.
+    struct Fated;
+
+    impl Rng for Fated {
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+
+        fn random_bool(&mut self, _probability: f64) -> bool {
+            true
+        }
+    }
+
+    /// Plays `script` on a loop, with no render loop and the Basilisk kept
+    /// from overriding orders, until the shift ends. A coup crisis is met
+    /// with silence, as if the operator had walked away from the console.
+    fn drive_to_ending(script: &[Directive]) -> Ending {
+        let mut engine = GameEngine::new();
+        engine.basilisk_override = false;
+        engine.set_rng(Box::new(Fated));
+        for directive in script.iter().cycle().take(40) {
+            engine.start_turn();
+            engine.resolve_directive(directive.clone());
+            if engine.state.coup_active {
+                crisis::resolve(&mut engine, Crisis::Coup, "", &mut Fated);
+            }
+            if let Some(ending) = engine.ending() {
+                return ending;
+            }
+        }
+        panic!("{:?} never ended the shift", script);
+    }
+
+    #[test]
+    fn scripted_shifts_reach_every_collapse() {
+        assert_eq!(drive_to_ending(&[Directive::Escalate]), Ending::NuclearWar);
+        assert_eq!(drive_to_ending(&[Directive::StandDown]), Ending::Coup);
+        assert_eq!(drive_to_ending(&[Directive::Investigate]), Ending::Basilisk);
+    }
+
+    #[test]
+    fn an_overridden_order_still_records_momentum() {
+        let mut engine = GameEngine::new();
+        engine.set_rng(Box::new(Fated));
+        engine.start_turn();
+        engine.state.system_corruption = 0.9;
+        engine.previous_tension = -1.0;
+        let before = engine.state.global_tension;
+        engine.resolve_directive(Directive::Decrypt("DOC-0000".to_string()));
+        // The decrypt was rewritten into an escalation, which snapshots.
+        assert_eq!(engine.previous_tension, before);
+    }
+
+    #[test]
+    fn colliding_filing_numbers_are_moved_along() {
+        let mut engine = fresh_engine();
+        let mut filed = engine.pending_documents.remove(0);
+        engine.pending_documents.clear();
+        filed.id = "DOC-0000".to_string();
+        engine.archive.push(filed.clone());
+        let mut batch = vec![filed.clone(), filed.clone(), filed];
+        batch[2].id = "SIGNAL-???".to_string();
+        engine.file_uniquely(&mut batch);
+        assert_eq!(batch[0].id, "DOC-0001");
+        assert_eq!(batch[1].id, "DOC-0002");
+        // Numbers stations all sign off the same way.
+        assert_eq!(batch[2].id, "SIGNAL-???");
+    }
+
+    #[test]
+    fn an_unnoticed_mole_sabotages_intel() {
+        let mut engine = GameEngine::with_mole(0);
+        engine.set_rng(Box::new(Fated));
+        engine.start_turn();
+        for doc in &mut engine.pending_documents {
+            doc.reliability = 0.9;
+        }
+        // Still inside the grace period.
+        engine.pending_documents[0].reliability = 0.1;
+        engine.run_mole_sabotage();
+        assert!(!engine.pending_documents[0].vouched);
+
+        engine.turn_count = SABOTAGE_GRACE_TURNS + 3;
+        engine.run_mole_sabotage();
+        let doc = &engine.pending_documents[0];
+        assert!(doc.vouched);
+        assert!(doc.analysis(&engine.state)[2].contains("HIGH (VERIFIED)"));
+
+        // With nothing left to vouch for, the pipeline takes the hit.
+        engine.max_intel_points = 3;
+        engine.intel_points = 3;
+        engine.run_mole_sabotage();
+        assert_eq!((engine.max_intel_points, engine.intel_points), (2, 2));
+        assert!(engine.bulletins.iter().any(|b| b.contains("PIPELINE")));
+
+        // A mole under suspicion keeps their head down.
+        engine.state.advisors[0].suspicion = SABOTAGE_SUSPICION;
+        engine.run_mole_sabotage();
+        assert_eq!(engine.max_intel_points, 2);
+    }
+
+    #[test]
+    fn overclock_trades_corruption_for_intel_once_per_turn() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.set_rng(Box::new(Fated));
+        let intel = engine.intel_points;
+
+        let (_, ended) = engine.resolve_directive(Directive::Overclock);
+        assert!(!ended);
+        assert_eq!(engine.intel_points, intel + OVERCLOCK_INTEL);
+        assert_eq!(engine.state.system_corruption, OVERCLOCK_CORRUPTION);
+        assert!(engine.garble_next_feed);
+
+        let (feedback, _) = engine.resolve_directive(Directive::Overclock);
+        assert!(feedback[0].starts_with("FAILURE"));
+        assert_eq!(engine.intel_points, intel + OVERCLOCK_INTEL);
+
+        engine.start_turn();
+        assert!(!engine.garble_next_feed);
+        assert!(engine.bulletins.iter().any(|b| b.contains("GARBLED")));
+    }
+
+    #[test]
+    fn peeking_deals_nothing() {
+        let mut engine = fresh_engine();
+        let pending: Vec<String> = engine
+            .pending_documents
+            .iter()
+            .map(|d| d.id.clone())
+            .collect();
+        engine.turn_count = 6;
+
+        let sample = engine.peek_batch();
+        assert_eq!(sample.len(), 5);
+        assert!(sample.iter().any(|d| d.is_encrypted));
+        let after: Vec<String> = engine
+            .pending_documents
+            .iter()
+            .map(|d| d.id.clone())
+            .collect();
+        assert_eq!(after, pending);
+    }
+
+    #[test]
+    fn a_scripted_rng_forces_the_red_phone() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.set_rng(Box::new(Fated));
+        engine.state.global_tension = 0.85;
+        engine.resolve_directive(Directive::Leak);
+        assert!(engine.state.red_phone_active);
+    }
+
+    #[test]
+    fn the_hotline_rings_only_when_the_enemy_is_calm() {
+        for (paranoia, rings) in [(0.2, true), (0.6, false)] {
+            let mut engine = fresh_engine();
+            engine.basilisk_override = false;
+            engine.set_rng(Box::new(Fated));
+            engine.state.global_tension = 0.5;
+            engine.state.foreign_paranoia = paranoia;
+            engine.resolve_directive(Directive::Investigate);
+            assert_eq!(engine.state.hotline_active, rings);
+        }
+    }
+
+    #[test]
+    fn the_red_phone_rests_after_a_crisis() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        engine.set_rng(Box::new(Fated));
+        engine.crisis_fired();
+
+        for _ in 0..RED_PHONE_COOLDOWN {
+            engine.start_turn();
+            engine.state.global_tension = 0.85;
+            engine.resolve_directive(Directive::Leak);
+            assert!(!engine.state.red_phone_active);
+        }
+        engine.start_turn();
+        engine.state.global_tension = 0.85;
+        engine.resolve_directive(Directive::Leak);
+        assert!(engine.state.red_phone_active);
+    }
+
+    #[test]
+    fn cosmic_ciphers_take_several_decrypts_and_outlive_the_turn() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        let doc = &mut engine.pending_documents[0];
+        doc.is_encrypted = true;
+        doc.priority = false;
+        doc.clearance_level = crate::document::COSMIC_CLEARANCE.to_string();
+        let id = doc.id.clone();
+
+        engine.intel_points = 1;
+        let (feedback, _) = engine.resolve_directive(Directive::Decrypt(id.clone()));
+        assert!(feedback[0].starts_with("PARTIAL DECRYPTION"));
+
+        engine.start_turn();
+        let carried = engine
+            .pending_documents
+            .iter()
+            .find(|d| d.id == id)
+            .unwrap();
+        assert_eq!(carried.decrypt_progress, 1);
+
+        engine.intel_points = 2;
+        engine.resolve_directive(Directive::Decrypt(id.clone()));
+        engine.resolve_directive(Directive::Decrypt(id.clone()));
+        let doc = engine
+            .pending_documents
+            .iter()
+            .find(|d| d.id == id)
+            .unwrap();
+        assert!(!doc.is_encrypted);
+        assert_eq!(engine.intel_points, 0);
+    }
+
+    #[test]
+    fn trend_reflects_the_last_turn() {
+        let mut engine = fresh_engine();
+        engine.basilisk_override = false;
+        assert_eq!(engine.tension_trend(), 0.0);
+
+        engine.resolve_directive(Directive::Escalate);
+        engine.start_turn();
+        assert!(engine.tension_trend() > 0.0);
+
+        engine.resolve_directive(Directive::StandDown);
+        assert!(engine.tension_trend() < 0.0);
+    }
+
+    #[test]
+    fn hard_traces_can_miss_the_mole_only_once() {
+        let mut games_with_a_miss = 0;
+        for _ in 0..100 {
+            let mut engine = GameEngine::with_mole(0);
+            engine.set_difficulty(Difficulty::Hard);
+            let misses = (0..5).filter(|_| !engine.live_trace_reading(true)).count();
+            assert!(misses <= 1);
+            games_with_a_miss += misses;
+        }
+        assert!(games_with_a_miss > 0);
+    }
+
+    #[test]
+    fn hard_traces_frame_innocents_only_under_corruption() {
+        let mut engine = GameEngine::with_mole(0);
+        engine.set_difficulty(Difficulty::Hard);
+        engine.state.system_corruption = 0.0;
+        assert!((0..100).all(|_| !engine.live_trace_reading(false)));
+    }
+}
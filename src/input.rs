@@ -1,36 +1,471 @@
-use std::io;
-use std::sync::mpsc;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, IsTerminal, Read};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// Menu digits that dispatch on a single keypress in hotkey mode. The rest
+/// take an argument, so they drop through to the line editor.
+const HOTKEYS: &[u8] = b"12345";
+
+/// How often a waiting read looks up to check for a quit.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Raised by `quit`, the pause menu or Ctrl-C. Every wait for input gives up
+/// once it is set, so the shift unwinds back to `main` for a clean teardown.
+static QUIT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Asks the session to end at the next chance it gets.
+pub fn request_quit() {
+    QUIT_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+pub fn quit_requested() -> bool {
+    QUIT_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// Turns Ctrl-C into a quit request instead of letting it kill the process
+/// with the terminal still colored.
+#[cfg(unix)]
+pub fn catch_interrupt() {
+    extern "C" {
+        fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+    }
+    extern "C" fn on_interrupt(_: i32) {
+        request_quit();
+    }
+    const SIGINT: i32 = 2;
+    // SAFETY: the handler only stores to an atomic, which is signal-safe.
+    unsafe {
+        signal(SIGINT, on_interrupt);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn catch_interrupt() {}
+
+/// Where the player is, for deciding which shortcuts apply.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyMode {
+    /// Cables typing out.
+    Feed,
+    /// The command prompt.
+    Prompt,
+    /// The countdown before escalate or stand-down.
+    Countdown,
+    /// Waiting at the end of a turn.
+    Pause,
+}
+
+impl KeyMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyMode::Feed => "FEED",
+            KeyMode::Prompt => "PROMPT",
+            KeyMode::Countdown => "COUNTDOWN",
+            KeyMode::Pause => "TURN END",
+        }
+    }
+}
+
+/// One keyboard shortcut. The description is a message-catalog id.
+pub struct Shortcut {
+    pub keys: &'static str,
+    pub action_id: &'static str,
+    pub mode: KeyMode,
+    /// Only live with `--hotkeys` (or the setting) on.
+    pub needs_hotkeys: bool,
+}
+
+/// Every shortcut the input layer honors. The dashboard footer and `keys`
+/// both read this, so keep it in step with the handling above and below.
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        keys: "ENTER",
+        action_id: "keys.fast_forward",
+        mode: KeyMode::Feed,
+        needs_hotkeys: false,
+    },
+    Shortcut {
+        keys: "\\ or ESC+ENTER",
+        action_id: "keys.skip_feed",
+        mode: KeyMode::Feed,
+        needs_hotkeys: false,
+    },
+    Shortcut {
+        keys: "P+ENTER",
+        action_id: "keys.pause",
+        mode: KeyMode::Feed,
+        needs_hotkeys: false,
+    },
+    Shortcut {
+        keys: "COMMAND+ENTER",
+        action_id: "keys.queue",
+        mode: KeyMode::Feed,
+        needs_hotkeys: false,
+    },
+    Shortcut {
+        keys: "1-5",
+        action_id: "keys.hotkey",
+        mode: KeyMode::Prompt,
+        needs_hotkeys: true,
+    },
+    Shortcut {
+        keys: "ENTER",
+        action_id: "keys.abort",
+        mode: KeyMode::Countdown,
+        needs_hotkeys: false,
+    },
+    Shortcut {
+        keys: "ENTER",
+        action_id: "keys.proceed",
+        mode: KeyMode::Pause,
+        needs_hotkeys: false,
+    },
+    Shortcut {
+        keys: "P+ENTER",
+        action_id: "keys.pause",
+        mode: KeyMode::Pause,
+        needs_hotkeys: false,
+    },
+];
+
+/// The shortcuts live in `mode`.
+pub fn shortcuts(mode: KeyMode, hotkeys: bool) -> impl Iterator<Item = &'static Shortcut> {
+    SHORTCUTS
+        .iter()
+        .filter(move |s| s.mode == mode && (hotkeys || !s.needs_hotkeys))
+}
+
+/// Keypresses that arrive while an animation is playing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interrupt {
+    /// Plain Enter: finish the current line instantly.
+    SpeedUp,
+    /// `\` or Esc: stop animating everything for the rest of the feed.
+    SkipAll,
+    /// `p`: freeze the animation and open the pause menu.
+    Pause,
+}
 
 pub struct InputManager {
-    rx: mpsc::Receiver<String>,
+    rx: RefCell<mpsc::Receiver<String>>,
+    /// Set by the reader thread when stdin reaches end of file.
+    at_eof: Arc<AtomicBool>,
+    /// The reader thread has already been replaced once after dying.
+    restarted: Cell<bool>,
+    /// Stdin is a keyboard. Piped or redirected input is a script, read
+    /// strictly in order: nothing is skimmed off as a keypress during an
+    /// animation or thrown away before a prompt.
+    interactive: bool,
+    /// Set while a command prompt is waiting in hotkey mode; the reader
+    /// thread clears it as soon as the first key of the line arrives.
+    armed: Arc<AtomicBool>,
+    hotkeys: Cell<bool>,
+    /// Scripted lines (`--macro`) answered before any real keyboard input.
+    queued: RefCell<VecDeque<String>>,
+    /// Command words worth holding on to if typed during an animation.
+    /// Empty turns type-ahead off.
+    command_words: Vec<String>,
+    /// Commands typed during the feed, run by the next command prompt.
+    typed_ahead: RefCell<VecDeque<String>>,
 }
 
 impl InputManager {
     pub fn new() -> Self {
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            let stdin = io::stdin();
-            loop {
-                let mut buffer = String::new();
-                if stdin.read_line(&mut buffer).is_ok() {
-                    // We successfully read a line
-                    if tx.send(buffer).is_err() {
-                        break; // Receiver dropped
-                    }
-                }
+        let armed = Arc::new(AtomicBool::new(false));
+        let at_eof = Arc::new(AtomicBool::new(false));
+        let rx = spawn_reader(Arc::clone(&armed), Arc::clone(&at_eof));
+        Self {
+            rx: RefCell::new(rx),
+            at_eof,
+            restarted: Cell::new(false),
+            interactive: io::stdin().is_terminal(),
+            armed,
+            hotkeys: Cell::new(false),
+            queued: RefCell::new(VecDeque::new()),
+            command_words: Vec::new(),
+            typed_ahead: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    /// Queues scripted lines to be returned, in order, by the next prompts.
+    /// `#` comments are dropped; a blank line is a bare Enter.
+    pub fn queue_lines(&self, text: &str) {
+        let mut queued = self.queued.borrow_mut();
+        for line in text.lines() {
+            let line = line.trim();
+            if !line.starts_with('#') {
+                queued.push_back(line.to_string());
+            }
+        }
+    }
+
+    /// Pops the next scripted line, echoing it as if it had been typed.
+    fn next_queued(&self) -> Option<String> {
+        let line = self.queued.borrow_mut().pop_front()?;
+        println!("{}", line);
+        Some(line)
+    }
+
+    /// Enables type-ahead: a line typed during an animation whose first word
+    /// is one of `words` is kept for the next command prompt instead of being
+    /// treated as a keypress.
+    pub fn set_command_words(&mut self, words: Vec<String>) {
+        self.command_words = words;
+    }
+
+    fn is_command(&self, line: &str) -> bool {
+        line.split_whitespace().next().is_some_and(|first| {
+            self.command_words
+                .iter()
+                .any(|w| w.eq_ignore_ascii_case(first))
+        })
+    }
+
+    /// Turns single-key menu selection on or off. Stays off when stdin is
+    /// not a terminal.
+    pub fn set_hotkeys(&self, enabled: bool) {
+        self.hotkeys.set(enabled && io::stdin().is_terminal());
+    }
+
+    /// Reads a command at the main prompt. In hotkey mode a bare menu digit
+    /// returns as soon as it is pressed; the flag reports whether that happened.
+    /// Returns None instead if a quit was requested, or if the terminal is no
+    /// longer `width` columns wide when the line arrives, so the caller can
+    /// redraw the screen to fit. The line is never cut short: it is set aside
+    /// and the next call, on the redrawn prompt, echoes and returns it.
+    pub fn read_command(&self, width: usize) -> Option<(String, bool)> {
+        if let Some(line) = self.next_queued() {
+            return Some((line, false));
+        }
+        if let Some(line) = self.typed_ahead.borrow_mut().pop_front() {
+            println!("{}", line);
+            return Some((line, false));
+        }
+        let line = if self.hotkeys.get() {
+            self.armed.store(true, Ordering::SeqCst);
+            set_canonical(false);
+            let line = self.read_line_or_quit();
+            self.armed.store(false, Ordering::SeqCst);
+            set_canonical(true);
+            line?
+        } else {
+            self.read_line_or_quit()?
+        };
+        if self.interactive && crate::ui::terminal_width() != width {
+            self.typed_ahead
+                .borrow_mut()
+                .push_front(line.trim_end().to_string());
+            return None;
+        }
+
+        // Hotkeys arrive without the trailing newline a typed line carries.
+        let hotkey = line.len() == 1 && HOTKEYS.contains(&line.as_bytes()[0]);
+        if hotkey {
+            println!();
+        }
+        Some((line, hotkey))
+    }
+
+    /// Blocking read that gives up, returning None, once a quit is
+    /// requested. That is checked every `POLL_INTERVAL` while nothing has
+    /// been entered.
+    fn read_line_or_quit(&self) -> Option<String> {
+        loop {
+            if quit_requested() {
+                return None;
+            }
+            let received = self.rx.borrow().recv_timeout(POLL_INTERVAL);
+            match received {
+                Ok(line) => return Some(line),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => self.recover(),
             }
-        });
-        Self { rx }
+        }
     }
 
-    /// Blocking read for the next line of input.
+    /// Blocking read for the next line of input. Once a quit is requested it
+    /// answers with an empty line, which every prompt takes as "back out".
     pub fn read_line(&self) -> String {
-        self.rx.recv().unwrap_or_default()
+        if let Some(line) = self.next_queued() {
+            return line;
+        }
+        self.read_line_or_quit().unwrap_or_default()
+    }
+
+    /// Waits up to `timeout` for a line. Returns None if the deadline passes
+    /// or a quit is requested.
+    pub fn read_line_timeout(&self, timeout: Duration) -> Option<String> {
+        if let Some(line) = self.next_queued() {
+            return Some(line);
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if quit_requested() || left.is_zero() {
+                return None;
+            }
+            let received = self.rx.borrow().recv_timeout(left.min(POLL_INTERVAL));
+            match received {
+                Ok(line) => return Some(line),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) if self.at_eof.load(Ordering::SeqCst) => {
+                    // Nothing can arrive any more, so the wait simply runs out.
+                    thread::sleep(left);
+                    return None;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => self.recover(),
+            }
+        }
+    }
+
+    /// Called when the reader thread has gone away. A reader that died on an
+    /// error or a panic gets one replacement; at end of input, or if the
+    /// replacement dies too, nothing more can be typed, so a quit is
+    /// requested rather than spinning on empty lines. The session then ends
+    /// the usual way, autosave and transcript included.
+    fn recover(&self) {
+        if !self.at_eof.load(Ordering::SeqCst) && !self.restarted.replace(true) {
+            let rx = spawn_reader(Arc::clone(&self.armed), Arc::clone(&self.at_eof));
+            *self.rx.borrow_mut() = rx;
+            return;
+        }
+        if self.at_eof.load(Ordering::SeqCst) {
+            eprintln!("\ncoldwar: standard input closed; ending the session");
+        } else {
+            eprintln!("\ncoldwar: lost the keyboard: the input reader stopped twice");
+        }
+        request_quit();
+    }
+
+    /// Non-blocking check for input typed during an animation. A recognized
+    /// command is set aside for the next prompt and does not interrupt. A
+    /// pending quit skips straight to the end. Scripted input is left for
+    /// the prompts.
+    pub fn poll_interrupt(&self) -> Option<Interrupt> {
+        if quit_requested() {
+            return Some(Interrupt::SkipAll);
+        }
+        if !self.interactive {
+            return None;
+        }
+        let line = self.rx.borrow().try_recv().ok()?;
+        let line = line.trim();
+        if line == "\\" || line.contains('\x1b') {
+            Some(Interrupt::SkipAll)
+        } else if line.eq_ignore_ascii_case("p") {
+            Some(Interrupt::Pause)
+        } else if self.is_command(line) {
+            self.typed_ahead.borrow_mut().push_back(line.to_string());
+            None
+        } else {
+            Some(Interrupt::SpeedUp)
+        }
     }
 
-    /// Clears any buffered input (useful before prompts)
+    /// Non-blocking check for any live line at all, command or not. A
+    /// pending quit counts, so countdowns call themselves off. Scripted
+    /// input is never a live keypress.
+    pub fn poll_line(&self) -> bool {
+        quit_requested() || (self.interactive && self.rx.borrow().try_recv().is_ok())
+    }
+
+    /// Whether stdin is a keyboard rather than a script.
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    /// Moves lines typed before now out of the way of `poll_line`, so only
+    /// a fresh keypress counts. Commands among them are kept for the next
+    /// prompt; stray Enters are dropped.
+    pub fn set_aside_pending(&self) {
+        if !self.interactive {
+            return;
+        }
+        while let Ok(line) = self.rx.borrow().try_recv() {
+            let line = line.trim();
+            if self.is_command(line) {
+                self.typed_ahead.borrow_mut().push_back(line.to_string());
+            }
+        }
+    }
+
+    /// Clears any buffered keypresses (useful before prompts), typed-ahead
+    /// commands included. Scripted input is kept: every line of it is meant
+    /// for some prompt.
     pub fn flush(&self) {
-        while self.rx.try_recv().is_ok() {}
+        if !self.interactive {
+            return;
+        }
+        while self.rx.borrow().try_recv().is_ok() {}
+        self.typed_ahead.borrow_mut().clear();
     }
 }
+
+/// Starts the thread that turns stdin into lines. It exits at end of input
+/// (raising `at_eof`) or on a read error, dropping its end of the channel so
+/// the manager notices instead of waiting forever.
+fn spawn_reader(armed: Arc<AtomicBool>, at_eof: Arc<AtomicBool>) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            match stdin.read(&mut byte) {
+                Ok(0) => {
+                    // A last line without its newline still counts.
+                    if !line.is_empty() {
+                        let _ = tx.send(String::from_utf8_lossy(&line).into_owned());
+                    }
+                    at_eof.store(true, Ordering::SeqCst);
+                    break;
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+                Ok(_) => {
+                    let key = byte[0];
+                    if line.is_empty() && armed.swap(false, Ordering::SeqCst) {
+                        if HOTKEYS.contains(&key) {
+                            // Single-key dispatch: no newline follows.
+                            if tx.send((key as char).to_string()).is_err() {
+                                break;
+                            }
+                            continue;
+                        }
+                        // Not a hotkey: hand the rest of the line back to the
+                        // terminal's own editor.
+                        set_canonical(true);
+                    }
+                    line.push(key);
+                    if key == b'\n' {
+                        let text = String::from_utf8_lossy(&line).into_owned();
+                        line.clear();
+                        if tx.send(text).is_err() {
+                            break; // Receiver dropped
+                        }
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
+
+/// Switches the controlling terminal between line-buffered and per-key input.
+fn set_canonical(enabled: bool) {
+    let Ok(tty) = File::open("/dev/tty") else {
+        return;
+    };
+    let args: &[&str] = if enabled {
+        &["icanon"]
+    } else {
+        &["-icanon", "min", "1"]
+    };
+    let _ = Command::new("stty").args(args).stdin(tty).status();
+}
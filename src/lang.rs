@@ -0,0 +1,182 @@
+//! Message catalog for player-facing text.
+//!
+//! Every string is looked up by id. English is compiled in and always used as
+//! the fallback; `--lang` swaps in a catalog that overrides any subset of ids.
+//! Catalogs are flat TOML files of `"id" = "text"` pairs, either bundled below
+//! or loaded from a path.
+
+use crate::toml;
+use std::sync::OnceLock;
+
+const ENGLISH: &[(&str, &str)] = &[
+    (
+        "menu.header",
+        "AVAILABLE COMMANDS (Type 'help' for syntax):",
+    ),
+    ("help.header", "Available Commands:"),
+    ("help.escalate", "Increase military readiness (High Risk)"),
+    ("help.investigate", "Root out internal threats"),
+    ("help.contain", "Attempt diplomatic de-escalation"),
+    ("help.leak", "Release information to public"),
+    ("help.stand_down", "Withdraw military forces (Surrender)"),
+    ("help.decrypt", "Decrypt intelligence document"),
+    ("help.analyze", "Verify document reliability"),
+    (
+        "help.triage",
+        "Name the pending document most worth analyzing (1 Intel)",
+    ),
+    ("help.consult", "Ask advisor for counsel"),
+    (
+        "help.roundtable",
+        "Convene every advisor at once (alias: roundtable)",
+    ),
+    ("help.interrogate", "Aggressively question advisor"),
+    ("help.trace", "Trace signal origin to advisor"),
+    (
+        "help.overclock",
+        "Push the decoders past spec: +2 Intel, feeds corruption",
+    ),
+    (
+        "help.read",
+        "Replay a cable from the inbox or archive (no cost)",
+    ),
+    ("help.sitrep", "Quick threat assessment (no cost)"),
+    ("help.stats", "Tallies for this run so far (no cost)"),
+    (
+        "help.settings",
+        "Change feed speed, glyphs, safety prompts and more",
+    ),
+    (
+        "help.preview",
+        "Show what a directive does without running it",
+    ),
+    ("help.export", "Save the session as plain text"),
+    ("help.save", "Save the shift to pick up later"),
+    ("help.load", "Pick up a saved shift"),
+    ("help.achievements", "List unlocked and locked achievements"),
+    ("help.commands", "List every command and its aliases"),
+    ("help.help", "Show this list"),
+    ("help.clear", "Redraw the dashboard"),
+    ("help.quit", "Leave the terminal"),
+    ("help.keys", "List keyboard shortcuts"),
+    ("keys.fast_forward", "fast-forward cable"),
+    ("keys.skip_feed", "skip feed"),
+    ("keys.pause", "pause"),
+    ("keys.queue", "queue it for the prompt"),
+    ("keys.hotkey", "run menu item instantly"),
+    ("keys.abort", "call off the directive"),
+    ("keys.proceed", "next turn"),
+    ("keys.off", "(off)"),
+    ("ending.nuclear_war.title", "GAME OVER // LAUNCH DETECTED"),
+    (
+        "ending.nuclear_war.summary",
+        "The sky is full of contrails. There is no one left to read your report.",
+    ),
+    ("ending.coup.title", "GAME OVER // GOVERNMENT DISSOLVED"),
+    (
+        "ending.coup.summary",
+        "The General Staff has taken the console. Your clearance is revoked.",
+    ),
+    ("ending.basilisk.title", "GAME OVER // BASILISK ASCENDANT"),
+    (
+        "ending.basilisk.summary",
+        "Project Basilisk is awake. It no longer needs an operator.",
+    ),
+    ("ending.impostor.title", "GAME OVER // THE DOUBLE GOVERNS"),
+    (
+        "ending.impostor.summary",
+        "The man behind the desk signs your orders now. You never learn who he answers to.",
+    ),
+    ("ending.victory.title", "CRISIS AVERTED"),
+    (
+        "ending.victory.summary",
+        "The bombers are turned around. The traitor is gone. The Project sleeps.",
+    ),
+    ("ending.simulation_end.title", "[SIMULATION END]"),
+    (
+        "ending.simulation_end.summary",
+        "Your shift is over. The world is still here, barely.",
+    ),
+    ("gameover.days", "DAYS ON SHIFT"),
+    ("gameover.duration", "CRISIS DURATION"),
+    ("gameover.arc", "CRISIS ARC, DAY BY DAY"),
+];
+
+/// Catalogs shipped inside the binary, by language code.
+const BUNDLED: &[(&str, &str)] = &[("es", include_str!("../lang/es.toml"))];
+
+static ACTIVE: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+/// Selects the catalog for this session: `en`, a bundled code, or a file path.
+/// Ids missing from the catalog keep their English text.
+pub fn load(lang: &str) -> Result<(), String> {
+    let entries = if lang.eq_ignore_ascii_case("en") {
+        Vec::new()
+    } else {
+        let text = match BUNDLED
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(lang))
+        {
+            Some((_, text)) => text.to_string(),
+            None => std::fs::read_to_string(lang)
+                .map_err(|e| format!("no bundled language '{}' and {}", lang, e))?,
+        };
+        parse_catalog(&text)?
+    };
+    ACTIVE
+        .set(entries)
+        .map_err(|_| "language already selected".to_string())
+}
+
+/// Text for `id` in the active language.
+pub fn text(id: &str) -> &'static str {
+    if let Some(entries) = ACTIVE.get() {
+        if let Some((_, value)) = entries.iter().find(|(k, _)| k == id) {
+            return value;
+        }
+    }
+    english(id)
+}
+
+fn english(id: &str) -> &'static str {
+    ENGLISH
+        .iter()
+        .find(|(k, _)| *k == id)
+        .map(|(_, v)| *v)
+        .unwrap_or("???")
+}
+
+fn parse_catalog(text: &str) -> Result<Vec<(String, String)>, String> {
+    let doc = toml::parse(text)?;
+    doc.root
+        .entries
+        .into_iter()
+        .map(|(key, value)| {
+            if !ENGLISH.iter().any(|(k, _)| *k == key) {
+                return Err(format!("unknown message id '{}'", key));
+            }
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("'{}' must be a string", key))?;
+            Ok((key, value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_catalogs_only_use_known_ids() {
+        for (code, text) in BUNDLED {
+            let entries = parse_catalog(text).unwrap_or_else(|e| panic!("{}: {}", code, e));
+            assert!(!entries.is_empty());
+        }
+    }
+
+    #[test]
+    fn unknown_ids_are_rejected() {
+        assert!(parse_catalog("\"menu.hedaer\" = \"x\"").is_err());
+    }
+}
@@ -1,305 +1,819 @@
+mod achievement;
+mod alias;
+mod archive;
+mod autopilot;
+mod batch;
+mod commands;
+mod config;
+mod crisis;
+mod diag;
 mod document;
 mod game;
 mod input;
+mod lang;
 mod rng;
+mod roster;
+mod save;
+mod scenario;
+mod settings;
 mod state;
+mod stats;
+mod toml;
+mod transcript;
 mod ui;
 
+use achievement::{Achievement, AchievementBook};
+use alias::Aliases;
+use archive::Archive;
+use commands::Parsed;
+use config::Config;
+use crisis::{Crisis, Tone};
+use document::{decryption_frames, random_char};
 use game::{Directive, GameEngine};
-use input::InputManager;
-use rng::SimpleRng;
-use std::io::{self, Write};
+use input::{InputManager, Interrupt, KeyMode};
+use rng::{Rng, SimpleRng};
+use settings::Settings;
+use state::Ending;
+use std::io::{self, IsTerminal, Write};
 use std::thread;
 use std::time::Duration;
 
-// Legacy Color Mapping for Helper Functions (Removed unused constants)
+/// How long the breaking-news ticker takes to scroll in.
+const TICKER_DURATION_MS: u64 = 1500;
+
+/// Seconds a dangerous directive waits for a last-second abort.
+const ABORT_WINDOW_SECS: u64 = 3;
+
+/// Attract mode: pause before each autopilot move, so a watcher can follow.
+const ATTRACT_THINK: Duration = Duration::from_millis(1500);
+
+/// Attract mode: how long a game-over screen stays up before the next shift.
+const ATTRACT_INTERMISSION: Duration = Duration::from_secs(8);
+
+/// Seconds the player has to echo back an interrupt carrier code.
+const INTERRUPT_WINDOW_SECS: u64 = 6;
+
+/// Seconds Chernov waits for an answer before taking silence as one.
+const RED_PHONE_SECS: u64 = 10;
 
 fn main() {
-    let mut engine = GameEngine::new();
-    let mut rng = SimpleRng::new();
-    let input_mgr = InputManager::new();
-    let mut stdout = io::stdout();
+    let mut config = match Config::load(std::env::args().skip(1)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("coldwar: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    if config.attract {
+        // A demo must not overwrite a real player's save, and nobody is
+        // there to call off an escalation.
+        config.autosave_every = 0;
+        config.resume = false;
+    }
+
+    if config.list_scenarios {
+        for scenario in scenario::SCENARIOS {
+            println!("{:<10} {}", scenario.name, scenario.description);
+        }
+        return;
+    }
+
+    if let Err(e) = lang::load(&config.lang) {
+        eprintln!("coldwar: invalid language: {}", e);
+        std::process::exit(2);
+    }
+    ui::set_ascii(config.ascii || !ui::locale_supports_utf8());
+    ui::set_hide_numbers(config.no_numbers);
+    ui::set_fast(config.fast);
+    ui::set_color(!config.no_color && io::stdout().is_terminal());
+    // After the catalog loads, so descriptions follow --lang.
+    if config.list_commands {
+        for line in commands::table() {
+            println!("{}", line);
+        }
+        return;
+    }
+
+    let mut engine = if config.resume {
+        match save::load(save::AUTOSAVE_PATH) {
+            Ok(engine) => engine,
+            Err(e) => {
+                eprintln!("coldwar: cannot continue: {}", e);
+                std::process::exit(2);
+            }
+        }
+    } else {
+        new_engine(&config)
+    };
+    if let Some(path) = &config.log_path {
+        match diag::DiagnosticLog::create(path) {
+            Ok(log) => engine.diagnostics = Some(log),
+            Err(e) => {
+                eprintln!("coldwar: cannot open log file '{}': {}", path, e);
+                std::process::exit(2);
+            }
+        }
+    }
+    let aliases = match &config.aliases_path {
+        Some(path) => match Aliases::load(path) {
+            Ok((aliases, warnings)) => {
+                for warning in warnings {
+                    eprintln!("coldwar: warning: {}", warning);
+                }
+                aliases
+            }
+            Err(e) => {
+                eprintln!("coldwar: invalid aliases: {}", e);
+                std::process::exit(2);
+            }
+        },
+        None => Aliases::default(),
+    };
+    let mut rng = match config.seed {
+        // Offset so the terminal's effects don't mirror the engine's stream.
+        Some(seed) => SimpleRng::with_seed(seed.wrapping_add(1)),
+        None => SimpleRng::new(),
+    };
+    if config.batch {
+        let mut script = String::new();
+        if let Err(e) = io::Read::read_to_string(&mut io::stdin(), &mut script) {
+            eprintln!("coldwar: could not read the strategy from stdin: {}", e);
+            std::process::exit(2);
+        }
+        println!("{}", batch::run(&mut engine, &script, &aliases, &mut rng));
+        return;
+    }
+    // The demo plays for nobody, so it earns nothing.
+    let mut achievements = if config.attract {
+        None
+    } else {
+        match AchievementBook::load(achievement::ACHIEVEMENTS_PATH) {
+            Ok(book) => Some(book),
+            Err(e) => {
+                eprintln!("coldwar: invalid achievements: {}", e);
+                std::process::exit(2);
+            }
+        }
+    };
+    let mut settings = Settings::from_config(&config);
+    if config.attract {
+        settings.confirm_dangerous = false;
+    }
+    let mut input_mgr = InputManager::new();
+    input_mgr.set_hotkeys(settings.hotkeys);
+    // The demo treats any typing as a request to stop, so it keeps nothing.
+    if !config.attract {
+        input_mgr.set_command_words(aliases.command_words());
+    }
+    if let Some(path) = &config.macro_path {
+        match std::fs::read_to_string(path) {
+            Ok(text) => input_mgr.queue_lines(&text),
+            Err(e) => {
+                eprintln!("coldwar: could not read macro {}: {}", path, e);
+                std::process::exit(2);
+            }
+        }
+    }
 
-    // Boot Sequence
+    // Whatever happens from here on, including a panic, the shell gets its
+    // colors and cursor back.
+    let _terminal = ui::TerminalGuard::new();
+    input::catch_interrupt();
     ui::clear_screen();
-    ui::type_text(
-        "INITIALIZING SECURE TERMINAL LINK...",
-        30,
-        ui::TEAL,
-        0.0,
-        &mut rng,
-    );
-    thread::sleep(Duration::from_millis(500));
-    ui::type_text(
-        "LOADING GEOPOLITICAL HEURISTICS...",
-        20,
-        ui::TEAL,
-        0.05,
+    if config.quiet_boot {
+        println!(
+            "{}SECURE TERMINAL LINK READY.{}",
+            ui::palette().teal,
+            ui::palette().reset
+        );
+    } else {
+        play_boot_sequence(&mut rng, &mut settings, &input_mgr);
+    }
+    if !config.start.is_empty() && !config.resume {
+        print_start_state(&engine.state);
+    }
+    if config.debug && !config.resume {
+        if let Some(role) = &config.mole_role {
+            println!(
+                "{}DEBUG: MOLE FORCED ONTO THE {} ROLE.{}",
+                ui::palette().grey_dim,
+                format!("{:?}", role).to_uppercase(),
+                ui::palette().reset
+            );
+            ui::pause(1500);
+        }
+    }
+
+    let mut ending = run_shift(
+        &mut engine,
+        &config,
+        &aliases,
+        &mut achievements,
+        &mut settings,
+        &mut input_mgr,
         &mut rng,
     );
-    thread::sleep(Duration::from_millis(500));
-    ui::type_text(
-        "ESTABLISHING NEURAL HANDSHAKE...",
-        20,
-        ui::TEAL,
-        0.1,
-        &mut rng,
+    // Attract mode deals new shifts until someone touches the keyboard.
+    while config.attract && ending.is_some() && !input::quit_requested() {
+        if input_mgr.read_line_timeout(ATTRACT_INTERMISSION).is_some() {
+            break;
+        }
+        let log = engine.diagnostics.take();
+        engine = new_engine(&config);
+        engine.diagnostics = log;
+        ending = run_shift(
+            &mut engine,
+            &config,
+            &aliases,
+            &mut achievements,
+            &mut settings,
+            &mut input_mgr,
+            &mut rng,
+        );
+    }
+
+    shut_down(&engine, &config, ending);
+}
+
+/// The one way out of a session, whether the shift ended, the turn limit ran
+/// out, or the player quit or pressed Ctrl-C: settle the autosave, write the
+/// transcript, then hand the terminal back and sign off.
+fn shut_down(engine: &GameEngine, config: &Config, ending: Option<Ending>) {
+    if ending.is_some() {
+        // The shift is over; there is nothing left to continue.
+        if config.autosave_every > 0 || config.resume {
+            let _ = std::fs::remove_file(save::AUTOSAVE_PATH);
+        }
+    } else if config.autosave_every > 0 {
+        // Left mid-shift: keep the place for `--continue`.
+        if let Err(e) = save::write(engine, save::AUTOSAVE_PATH) {
+            eprintln!("coldwar: autosave failed: {}", e);
+        }
+    }
+
+    if let Some(path) = &config.transcript_path {
+        if let Err(e) = transcript::export(engine, path) {
+            eprintln!("coldwar: could not write transcript to {}: {}", path, e);
+        }
+    }
+
+    ui::restore_terminal();
+    println!(
+        "\n{}SECURE LINK CLOSED.{}",
+        ui::palette().grey_dim,
+        ui::palette().reset
     );
+}
 
-    let mut skip_generation = false;
+/// Plays one shift from the engine's current turn until an ending, which is
+/// returned. None when the player quit, or attract mode was interrupted by a
+/// key press.
+fn run_shift(
+    engine: &mut GameEngine,
+    config: &Config,
+    aliases: &Aliases,
+    achievements: &mut Option<AchievementBook>,
+    settings: &mut Settings,
+    input_mgr: &mut InputManager,
+    rng: &mut dyn Rng,
+) -> Option<Ending> {
+    let mut stdout = io::stdout();
+    // A resumed shift picks up mid-turn, with the saved cables still pending.
+    let mut skip_generation = engine.turn_count > 0;
 
     loop {
-        // --- CRISIS CHECK: THE RED PHONE ---
-        if engine.state.red_phone_active {
-            handle_red_phone_crisis(&mut engine, &mut rng, &input_mgr);
-            if engine.state.is_terminal() {
-                break;
+        if input::quit_requested() {
+            return None;
+        }
+
+        // --- CRISIS CHECK ---
+        while let Some(crisis) = Crisis::pending(&engine.state) {
+            if settings.bell {
+                ui::bell();
+            }
+            if config.attract {
+                input_mgr.queue_lines(autopilot::crisis_reply(engine));
+            }
+            handle_crisis(engine, crisis, rng, settings, input_mgr);
+            if input::quit_requested() {
+                // Still unanswered; a resumed shift rings again.
+                return None;
+            }
+            announce_achievements(engine, achievements);
+            warn_point_of_no_return(engine, settings);
+            if let Some(ending) = engine.ending() {
+                print_game_over(engine, ending);
+                return Some(ending);
             }
-            engine.state.red_phone_active = false;
         }
 
+        // Only a fresh batch of cables gets the typewriter treatment.
+        let fresh_turn = !skip_generation;
         if !skip_generation {
             engine.start_turn();
+            if config.autosave_every > 0 && engine.turn_count.is_multiple_of(config.autosave_every)
+            {
+                if let Err(e) = save::write(engine, save::AUTOSAVE_PATH) {
+                    eprintln!("coldwar: autosave failed: {}", e);
+                }
+            }
         } else {
             skip_generation = false;
         }
 
         // --- RENDER DASHBOARD ---
         ui::clear_screen();
+        let leaks: Vec<&str> = engine.recent_leaks.iter().map(String::as_str).collect();
+        if fresh_turn && !leaks.is_empty() {
+            let headline = format!("BREAKING +++ {} +++", leaks.join(" +++ "));
+            let width = ui::terminal_width().saturating_sub(1).max(20);
+            ui::marquee(&headline, width, TICKER_DURATION_MS, ui::palette().amber);
+        }
         ui::draw_hud(
             engine.turn_count,
             engine.state.global_tension,
+            engine.state.tension_band(),
+            engine.tension_trend(),
             engine.intel_points,
             engine.max_intel_points,
+            engine.elapsed(),
         );
         println!();
 
         // WORLD METRICS
-        println!("{}SYSTEM STATUS:{}", ui::AMBER, ui::RESET);
-        ui::draw_progress_bar(
-            "STABILITY",
-            engine.state.domestic_stability,
-            40,
-            ui::TEAL,
-            &mut rng,
-        );
-        ui::draw_progress_bar(
-            "PARANOIA",
-            engine.state.foreign_paranoia,
-            40,
-            ui::ORANGE,
-            &mut rng,
-        );
+        let board_width = ui::terminal_width().saturating_sub(1).clamp(30, 60);
+        ui::draw_threat_board("THREAT BOARD", &threats(&engine.state), board_width, rng);
         ui::draw_progress_bar(
-            "SECRECY",
-            engine.state.internal_secrecy,
-            40,
-            ui::TEAL,
-            &mut rng,
+            "CONFIDENCE",
+            engine.intel_confidence(),
+            ui::BAR_ROW_WIDTH,
+            ui::palette().amber,
+            rng,
         );
 
         if engine.state.system_corruption > 0.0 {
             ui::draw_progress_bar(
                 "SYS.CORRUPTION",
                 engine.state.system_corruption,
-                40,
-                ui::RED_ALERT,
-                &mut rng,
+                ui::BAR_ROW_WIDTH,
+                ui::palette().red_alert,
+                rng,
             );
         }
 
         println!();
-        println!("{}ADVISOR LOYALTY:{}", ui::AMBER, ui::RESET);
+        println!(
+            "{}ADVISOR LOYALTY:{}",
+            ui::palette().amber,
+            ui::palette().reset
+        );
         for advisor in &engine.state.advisors {
-            let color = if advisor.suspicion > 70 {
-                ui::RED_ALERT
-            } else {
-                ui::TEAL
-            };
-            ui::draw_progress_bar(
-                &advisor.name,
-                advisor.suspicion as f64 / 100.0,
-                40,
-                color,
-                &mut rng,
-            );
+            ui::draw_advisor_suspicion(advisor, rng);
         }
 
         println!();
-        println!("{}INCOMING TRANSMISSIONS:{}", ui::WHITE_BOLD, ui::RESET);
-        println!("{}{}", ui::GREY_DIM, "─".repeat(60));
+        println!(
+            "{}INCOMING TRANSMISSIONS:{}",
+            ui::palette().white_bold,
+            ui::palette().reset
+        );
+        println!("{}{}", ui::palette().grey_dim, ui::divider(60));
 
         // Interruption Check
-        if engine.interruption_active && rng.random_bool(0.3) {
-            trigger_interruption(&mut rng, &input_mgr);
+        if fresh_turn && engine.interruption_active && rng.random_bool(0.3) {
+            if config.attract {
+                // The autopilot cannot read the carrier code; let it drift.
+                input_mgr.queue_lines("\n");
+            }
+            trigger_interruption(engine, rng, settings.bell, input_mgr);
+        }
+
+        for bulletin in &engine.bulletins {
+            println!(
+                "{}>> {}{}",
+                ui::palette().amber,
+                bulletin,
+                ui::palette().reset
+            );
         }
 
         // Display Documents
-        for doc in &engine.pending_documents {
+        if fresh_turn {
+            print_key_footer(KeyMode::Feed, settings.hotkeys);
+        }
+        let mut animate_feed = fresh_turn;
+        let drawn_width = ui::terminal_width();
+        let feed_width = drawn_width.saturating_sub(1).max(20);
+        if engine.pending_documents.is_empty() {
+            println!(
+                " {}NO INCOMING CABLES THIS CYCLE.{}",
+                ui::palette().grey_dim,
+                ui::palette().reset
+            );
+        }
+        for (position, doc) in engine.pending_documents.iter().enumerate() {
             let color = if doc.is_encrypted {
-                ui::RED_ALERT
+                ui::palette().red_alert
             } else {
-                ui::TEAL
+                ui::palette().teal
             };
+            // The position works in place of the id: `decrypt 2`.
             println!(
-                "{} [ID: {}] CLASS: {} :: {}",
-                color, doc.id, doc.clearance_level, doc.timestamp
+                "{} #{} [ID: {}] CLASS: {} :: {}",
+                color,
+                position + 1,
+                doc.id,
+                doc.clearance_level,
+                doc.timestamp
             );
 
             if doc.is_encrypted {
+                if doc.priority {
+                    println!(
+                        " {}!! PRIORITY DECRYPT WINDOW CLOSING - LOST AT END OF TURN !!{}",
+                        ui::palette().red_alert,
+                        ui::palette().reset
+                    );
+                }
                 println!(
                     " {}ENCRYPTED CONTENT - DECRYPTION REQUIRED{}",
-                    ui::RED_ALERT,
-                    ui::RESET
-                );
-                println!(
-                    " {}{}{}",
-                    ui::GREY_DIM,
-                    scramble_text(&doc.content, &mut rng),
-                    ui::RESET
+                    ui::palette().red_alert,
+                    ui::palette().reset
                 );
+                let cost = doc.decrypt_cost();
+                if cost > 1 {
+                    let g = ui::glyphs();
+                    let done = doc.decrypt_progress.min(cost) as usize;
+                    println!(
+                        " {}MULTI-STAGE CIPHER [{}{}] {}/{} (1 INTEL PER STAGE){}",
+                        ui::palette().amber,
+                        g.block_full.to_string().repeat(done),
+                        g.block_empty.to_string().repeat(cost as usize - done),
+                        done,
+                        cost,
+                        ui::palette().reset
+                    );
+                }
+                for line in ui::wrap(&doc.partial_text(rng), feed_width, " ") {
+                    println!("{}{}{}", ui::palette().grey_dim, line, ui::palette().reset);
+                }
             } else {
-                let content = corrupt_text(&doc.content, engine.turn_count, &mut rng);
-                println!(" {}{}{}", ui::TEAL, content, ui::RESET);
+                let content = corrupt_text(&doc.content, engine.turn_count, rng);
+                let lines = ui::wrap(&content, feed_width, " ");
+                if animate_feed {
+                    animate_feed = !ui::type_feed_lines(
+                        &lines,
+                        settings.feed_speed_ms,
+                        ui::palette().teal,
+                        input_mgr,
+                        &mut || pause_menu(settings, input_mgr),
+                    );
+                } else {
+                    for line in lines {
+                        println!("{}{}{}", ui::palette().teal, line, ui::palette().reset);
+                    }
+                }
             }
-            println!("{}{}", ui::GREY_DIM, "─".repeat(60));
+            println!("{}{}", ui::palette().grey_dim, ui::divider(60));
         }
-        println!("{}", ui::RESET);
+        println!("{}", ui::palette().reset);
 
         // Input Phase
         println!(
-            "\n{}AVAILABLE COMMANDS (Type 'help' for syntax):{}",
-            ui::AMBER,
-            ui::RESET
+            "\n{}{}{}",
+            ui::palette().amber,
+            lang::text("menu.header"),
+            ui::palette().reset
+        );
+        println!(
+            "  [1] {}sudo --escalate{}",
+            ui::palette().white_bold,
+            ui::palette().reset
+        );
+        println!(
+            "  [2] {}sudo --investigate{}",
+            ui::palette().white_bold,
+            ui::palette().reset
+        );
+        println!(
+            "  [3] {}sudo --contain{}",
+            ui::palette().white_bold,
+            ui::palette().reset
         );
-        println!("  [1] {}sudo --escalate{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [2] {}sudo --investigate{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [3] {}sudo --contain{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [4] {}sudo --leak{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [5] {}sudo --stand-down{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [6] {}decrypt -t [ID]{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [7] {}analyze -t [ID]{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [8] {}traceroute -t [NAME]{}", ui::WHITE_BOLD, ui::RESET);
-        println!("  [9] {}consult -n [NAME]{}", ui::WHITE_BOLD, ui::RESET);
         println!(
-            "  [10] {}interrogate -n [NAME]{}",
-            ui::WHITE_BOLD,
-            ui::RESET
+            "  [4] {}sudo --leak{}",
+            ui::palette().white_bold,
+            ui::palette().reset
         );
+        println!(
+            "  [5] {}sudo --stand-down{}",
+            ui::palette().white_bold,
+            ui::palette().reset
+        );
+        let paid = [
+            ("6", "decrypt -t [ID]", Directive::Decrypt(String::new())),
+            ("7", "analyze -t [ID]", Directive::Analyze(String::new())),
+            ("8", "traceroute -t [NAME]", Directive::Trace(String::new())),
+            ("9", "consult -n [NAME]", Directive::Consult(String::new())),
+            (
+                "10",
+                "interrogate -n [NAME]",
+                Directive::Interrogate(String::new()),
+            ),
+        ];
+        for (key, usage, directive) in paid {
+            // Dimmed when it would only come back as a FAILURE line.
+            match engine.menu_blocker(&directive) {
+                Some(reason) => println!(
+                    "  [{}] {}{} ({}){}",
+                    key,
+                    ui::palette().grey_dim,
+                    usage,
+                    reason,
+                    ui::palette().reset
+                ),
+                None => println!(
+                    "  [{}] {}{}{}",
+                    key,
+                    ui::palette().white_bold,
+                    usage,
+                    ui::palette().reset
+                ),
+            }
+        }
+        print_key_footer(KeyMode::Prompt, settings.hotkeys);
 
         let directive;
         loop {
-            print!("{}root@command:~$ {}", ui::TEAL, ui::RESET);
+            print!(
+                "{}root@command:~$ {}",
+                ui::palette().teal,
+                ui::palette().reset
+            );
             stdout.flush().unwrap();
 
-            let input = input_mgr.read_line();
-            let input = input.trim();
-
-            if input.is_empty() {
-                continue;
+            if config.attract {
+                // Any line from the keyboard hands the terminal back.
+                if input_mgr.read_line_timeout(ATTRACT_THINK).is_some() {
+                    return None;
+                }
+                let dir = autopilot::choose(engine);
+                ui::type_text(
+                    &dir.label().to_lowercase(),
+                    60,
+                    ui::palette().white_bold,
+                    0.0,
+                    rng,
+                );
+                println!("{}", ui::palette().reset);
+                directive = Some(dir);
+                break;
             }
 
-            if input == "clear" || input == "cls" {
+            let Some((input, hotkey)) = input_mgr.read_command(drawn_width) else {
+                // Resized while deciding: redraw the dashboard at the new
+                // width, then the prompt hands back the line that was typed.
+                // A quit is picked up at the top of the loop.
                 skip_generation = true;
                 directive = None;
                 break;
-            }
-            if input == "help" {
-                println!(
-                    "{}Available Commands:
-  escalate      - Increase military readiness (High Risk)
-  investigate   - Root out internal threats
-  contain       - Attempt diplomatic de-escalation
-  leak          - Release information to public
-  stand-down    - Withdraw military forces (Surrender)
-  decrypt <ID>  - Decrypt intelligence document
-  analyze <ID>  - Verify document reliability
-  consult <NAME>      - Ask advisor for counsel
-  interrogate <NAME>  - Aggressively question advisor
-  trace <NAME>        - Trace signal origin to advisor{}",
-                    ui::GREY_DIM,
-                    ui::RESET
-                );
-                continue;
-            }
-
-            let parts: Vec<&str> = input.split_whitespace().collect();
-            let cmd_base = parts.get(0).unwrap_or(&"").to_lowercase();
-            let (mut command_str, args_start_idx) = if cmd_base == "sudo" || cmd_base == "execute" {
-                (parts.get(1).unwrap_or(&"").to_lowercase(), 2)
-            } else {
-                (cmd_base.clone(), 1)
             };
+            // Player aliases win over the built-in names below.
+            let input = aliases.expand(input.trim());
+            let input = input.as_str();
 
-            // Handle flags (strip leading dashes)
-            let cleaned_cmd = command_str.trim_start_matches("-").to_string();
-            command_str = cleaned_cmd;
-
-            let mut arg_id = None;
-            if parts.len() > args_start_idx {
-                arg_id = Some(parts[args_start_idx].to_string());
-            } else if parts.len() > 1 {
-                // Fallback for consult [name] where name is second part
-                arg_id = Some(parts[parts.len() - 1].to_string());
+            if input.is_empty() {
+                continue;
             }
 
-            let d = match command_str.as_str() {
-                "1" | "escalate" | "esc" => Some(Directive::Escalate),
-                "2" | "investigate" | "inv" => Some(Directive::Investigate),
-                "3" | "contain" | "con" => Some(Directive::Contain),
-                "4" | "leak" => Some(Directive::Leak),
-                "5" | "stand-down" | "standdown" | "sd" => Some(Directive::StandDown),
-                "6" | "decrypt" | "dec" => {
-                    if let Some(id) = arg_id {
-                        Some(Directive::Decrypt(id))
-                    } else {
-                        println!("usage: decrypt -t <id>");
-                        continue;
+            // Free commands that never touch the turn.
+            let (first, rest) = match input.split_once(char::is_whitespace) {
+                Some((first, rest)) => (first, rest.trim()),
+                None => (input, ""),
+            };
+            match commands::canonical(first) {
+                Some("clear") => {
+                    skip_generation = true;
+                    directive = None;
+                    break;
+                }
+                Some("achievements") => {
+                    print_achievements(achievements.as_ref());
+                    continue;
+                }
+                Some("commands") => {
+                    print_commands();
+                    continue;
+                }
+                Some("keys") => {
+                    print_keys(settings.hotkeys);
+                    continue;
+                }
+                Some("read") => {
+                    let target = commands::target_arg(rest);
+                    match engine.find_document(target) {
+                        _ if target.is_empty() => println!(
+                            "{}USAGE: READ -t <ID|#>{}",
+                            ui::palette().amber,
+                            ui::palette().reset
+                        ),
+                        Some(doc) => replay_document(doc, settings, input_mgr, rng),
+                        None => println!(
+                            "{}ERROR: DOCUMENT {} NOT FOUND.{}",
+                            ui::palette().red_alert,
+                            target.to_uppercase(),
+                            ui::palette().reset
+                        ),
                     }
+                    continue;
                 }
-                "7" | "analyze" | "ana" => {
-                    if let Some(id) = arg_id {
-                        Some(Directive::Analyze(id))
-                    } else {
-                        println!("usage: analyze -t <id>");
-                        continue;
+                Some("sitrep") => {
+                    for line in engine.sitrep() {
+                        println!("{}{}{}", ui::palette().amber, line, ui::palette().reset);
                     }
+                    continue;
                 }
-                "8" | "trace" | "traceroute" => {
-                    if let Some(id) = arg_id {
-                        Some(Directive::Trace(id))
-                    } else {
-                        println!("usage: traceroute -t <advisor_name>");
-                        continue;
+                Some("stats") => {
+                    let report = engine.stats.report(
+                        engine.turn_count,
+                        engine.turns_since_crisis(),
+                        ui::numbers_hidden(),
+                    );
+                    for line in report {
+                        println!("{}{}{}", ui::palette().amber, line, ui::palette().reset);
                     }
+                    continue;
                 }
-                "9" | "consult" => {
-                    if let Some(id) = arg_id {
-                        Some(Directive::Consult(id))
-                    } else {
-                        println!("usage: consult -n <advisor_name>");
-                        continue;
+                Some("export-transcript") => {
+                    let path = match rest {
+                        "" => transcript::DEFAULT_TRANSCRIPT_PATH,
+                        path => path,
+                    };
+                    match transcript::export(engine, path) {
+                        Ok(()) => {
+                            println!(
+                                "{}TRANSCRIPT WRITTEN TO {}{}",
+                                ui::palette().teal,
+                                path,
+                                ui::palette().reset
+                            )
+                        }
+                        Err(e) => println!(
+                            "{}EXPORT FAILED: {}{}",
+                            ui::palette().red_alert,
+                            e,
+                            ui::palette().reset
+                        ),
                     }
+                    continue;
                 }
-                "10" | "interrogate" | "int" => {
-                    if let Some(id) = arg_id {
-                        Some(Directive::Interrogate(id))
-                    } else {
-                        println!("usage: interrogate -n <advisor_name>");
-                        continue;
+                Some("save") => {
+                    let path = match rest {
+                        "" => save::SAVE_PATH,
+                        path => path,
+                    };
+                    match save::write(engine, path) {
+                        Ok(()) => println!(
+                            "{}SHIFT SAVED TO {}{}",
+                            ui::palette().teal,
+                            path,
+                            ui::palette().reset
+                        ),
+                        Err(e) => println!(
+                            "{}SAVE FAILED: {}{}",
+                            ui::palette().red_alert,
+                            e,
+                            ui::palette().reset
+                        ),
                     }
+                    continue;
                 }
-                "quit" | "exit" => std::process::exit(0),
-                _ => {
-                    println!(
-                        "Unknown command: '{}'. Type 'help' for options.",
-                        command_str
-                    );
+                Some("load") => {
+                    let path = match rest {
+                        "" => save::SAVE_PATH,
+                        path => path,
+                    };
+                    match save::load(path) {
+                        Ok(loaded) => {
+                            print!(
+                                "{}ABANDON THE CURRENT SHIFT FOR {}? [y/N] {}",
+                                ui::palette().amber,
+                                path,
+                                ui::palette().reset
+                            );
+                            stdout.flush().unwrap();
+                            if !input_mgr.read_line().trim().eq_ignore_ascii_case("y") {
+                                println!(
+                                    "{}LOAD CANCELLED.{}",
+                                    ui::palette().grey_dim,
+                                    ui::palette().reset
+                                );
+                                continue;
+                            }
+                            let log = engine.diagnostics.take();
+                            *engine = loaded;
+                            engine.diagnostics = log;
+                            // Mid-turn, with the saved cables still pending.
+                            skip_generation = true;
+                            directive = None;
+                            break;
+                        }
+                        Err(e) => println!(
+                            "{}LOAD FAILED: {}{}",
+                            ui::palette().red_alert,
+                            e,
+                            ui::palette().reset
+                        ),
+                    }
+                    continue;
+                }
+                Some("preview") => {
+                    match preview_target(rest) {
+                        Some(dir) => {
+                            println!(
+                                "{}{}:{}",
+                                ui::palette().amber,
+                                dir.label().trim(),
+                                ui::palette().reset
+                            );
+                            for line in dir.effects() {
+                                println!(
+                                    "{}  - {}{}",
+                                    ui::palette().grey_dim,
+                                    line,
+                                    ui::palette().reset
+                                );
+                            }
+                        }
+                        None => println!("usage: preview <directive>"),
+                    }
+                    continue;
+                }
+                Some("settings") => {
+                    settings.run_menu(input_mgr);
+                    continue;
+                }
+                Some("help") => {
+                    print_help();
+                    continue;
+                }
+                None if config.debug && first.eq_ignore_ascii_case("peek") => {
+                    print_peek(engine);
+                    continue;
+                }
+                _ => {}
+            }
+
+            let d = match commands::parse(input) {
+                Parsed::Directive(dir) => Some(dir),
+                Parsed::Usage(usage) => {
+                    println!("usage: {}", usage);
+                    continue;
+                }
+                Parsed::Quit => return None,
+                Parsed::Unknown(word) => {
+                    println!("Unknown command: '{}'. Type 'help' for options.", word);
                     continue;
                 }
             };
 
             if let Some(dir) = d {
+                // A stray keypress should not start a war or lose one.
+                let guarded = match dir {
+                    Directive::Escalate if settings.confirm_dangerous => Some("ESCALATE"),
+                    Directive::StandDown if settings.confirm_dangerous => Some("STAND DOWN"),
+                    _ => None,
+                };
+                if let (true, Some(name)) = (hotkey, guarded) {
+                    print!(
+                        "{}CONFIRM {}? [y/N] {}",
+                        ui::palette().red_alert,
+                        name,
+                        ui::palette().reset
+                    );
+                    stdout.flush().unwrap();
+                    let answer = input_mgr.read_line();
+                    if !answer.trim().eq_ignore_ascii_case("y") {
+                        println!(
+                            "{}DIRECTIVE ABORTED.{}",
+                            ui::palette().grey_dim,
+                            ui::palette().reset
+                        );
+                        continue;
+                    }
+                }
+                // Nothing has been applied yet, so backing out here is free.
+                if let Some(name) = guarded {
+                    if abort_window(input_mgr, name, settings.hotkeys) {
+                        println!(
+                            "{}DIRECTIVE ABORTED.{}",
+                            ui::palette().grey_dim,
+                            ui::palette().reset
+                        );
+                        continue;
+                    }
+                }
                 directive = Some(dir);
                 break;
             }
@@ -309,149 +823,771 @@ fn main() {
             let (feedback, turn_ended) = engine.resolve_directive(dir);
             skip_generation = !turn_ended;
 
-            println!("\n{}EXECUTING DIRECTIVE...{}", ui::AMBER, ui::RESET);
+            println!(
+                "\n{}EXECUTING DIRECTIVE...{}",
+                ui::palette().amber,
+                ui::palette().reset
+            );
             for line in feedback {
-                ui::type_text(&line, 15, ui::TEAL, 0.02, &mut rng);
+                ui::type_text(&line, 15, ui::palette().teal, 0.02, rng);
             }
+            beat(0, settings, input_mgr);
+            announce_achievements(engine, achievements);
+            warn_point_of_no_return(engine, settings);
 
             if turn_ended {
-                println!("\n{}[PRESS ENTER TO PROCEED]{}", ui::TEAL, ui::RESET);
-                let _ = input_mgr.read_line();
+                println!(
+                    "\n{}[PRESS ENTER TO PROCEED]{}",
+                    ui::palette().teal,
+                    ui::palette().reset
+                );
+                print_key_footer(KeyMode::Pause, settings.hotkeys);
+                if config.attract {
+                    thread::sleep(ATTRACT_THINK);
+                } else if input_mgr.read_line().trim().eq_ignore_ascii_case("p") {
+                    pause_menu(settings, input_mgr);
+                }
             }
         }
 
-        if engine.state.is_terminal() {
-            ui::clear_screen();
-            println!("{}GAME OVER{}", ui::RED_ALERT, ui::RESET);
-            break;
+        if let Some(ending) = engine.ending() {
+            print_game_over(engine, ending);
+            return Some(ending);
         }
     }
 }
 
-fn handle_red_phone_crisis(
-    engine: &mut GameEngine,
-    _rng: &mut SimpleRng,
-    input_mgr: &InputManager,
-) {
-    let is_mole_reveal = engine.state.advisors.iter().any(|a| a.suspicion >= 100);
+/// A fresh shift shaped by the launch options. Exits on a bad roster or scenario.
+fn new_engine(config: &Config) -> GameEngine {
+    let mut engine = match &config.roster_path {
+        Some(path) => match roster::load_roster(path) {
+            Ok(advisors) => GameEngine::with_roster(advisors),
+            Err(e) => {
+                eprintln!("coldwar: invalid roster: {}", e);
+                std::process::exit(2);
+            }
+        },
+        None => GameEngine::new(),
+    };
+    if let Some(seed) = config.seed {
+        engine.reseed(seed);
+    }
+    if let Some(role) = &config.mole_role {
+        if let Err(e) = engine.force_mole_role(role) {
+            eprintln!("coldwar: --mole-role: {}", e);
+            std::process::exit(2);
+        }
+    }
+    if let Some(name) = &config.scenario {
+        match scenario::find(name) {
+            Some(scenario) => {
+                scenario.apply(&mut engine.state, &mut engine.generators);
+                engine.scenario = Some(scenario.name);
+            }
+            None => {
+                eprintln!(
+                    "coldwar: unknown scenario '{}' (see --list-scenarios)",
+                    name
+                );
+                std::process::exit(2);
+            }
+        }
+    }
+    // Explicit starting metrics win over the scenario's.
+    config.start.apply(&mut engine.state);
+    // No momentum to show until the first directive moves something.
+    engine.previous_tension = engine.state.global_tension;
+    engine.archive = Archive::with_capacity(config.archive_cap);
+    engine.turn_limit = config.turn_limit;
+    engine.basilisk_override = !config.no_override;
+    engine.volume = config.volume;
+    engine.set_difficulty(config.difficulty);
+    engine
+}
 
+/// Shows the opening metrics when `--start-*` overrides are in play, long
+/// enough to read before the first dashboard replaces them.
+fn print_start_state(state: &state::WorldState) {
+    println!(
+        "{}STARTING STATE OVERRIDDEN:{}",
+        ui::palette().amber,
+        ui::palette().reset
+    );
+    let metrics = [
+        ("TENSION", state.global_tension),
+        ("STABILITY", state.domestic_stability),
+        ("SECRECY", state.internal_secrecy),
+        ("PARANOIA", state.foreign_paranoia),
+        ("SECRET WEAPON", state.secret_weapon_progress),
+        ("ESCALATION RISK", state.accidental_escalation_risk),
+        ("CORRUPTION", state.system_corruption),
+    ];
+    for (label, value) in metrics {
+        println!("  {:<16} {:.2}", label, value);
+    }
+    ui::pause(2000);
+}
+
+/// The slow typewriter intro shown on a normal launch.
+fn play_boot_sequence(rng: &mut dyn Rng, settings: &mut Settings, input_mgr: &InputManager) {
+    let lines = [
+        ("INITIALIZING SECURE TERMINAL LINK...", 30, 0.0),
+        ("LOADING GEOPOLITICAL HEURISTICS...", 20, 0.05),
+        ("ESTABLISHING NEURAL HANDSHAKE...", 20, 0.1),
+    ];
+    for (i, (text, speed, glitch)) in lines.into_iter().enumerate() {
+        if i > 0 {
+            ui::pause(500);
+        }
+        // Checked between lines: the boot text is short enough to wait for.
+        if input_mgr.poll_interrupt() == Some(Interrupt::Pause) {
+            pause_menu(settings, input_mgr);
+        }
+        if input::quit_requested() {
+            return;
+        }
+        ui::type_text(text, speed, ui::palette().teal, glitch, rng);
+    }
+}
+
+/// A dramatic pause the player can break into: waits `ms` (not at all in
+/// fast mode), then opens the pause menu if `p` was typed in the meantime.
+fn beat(ms: u64, settings: &mut Settings, input_mgr: &InputManager) {
+    ui::pause(ms);
+    if input_mgr.poll_interrupt() == Some(Interrupt::Pause) {
+        pause_menu(settings, input_mgr);
+    }
+}
+
+/// Holds everything until the player resumes. Settings and quit are one
+/// choice away, for when the phone rings mid-feed.
+fn pause_menu(settings: &mut Settings, input_mgr: &InputManager) {
+    loop {
+        println!("\n{}PAUSED.{}", ui::palette().amber, ui::palette().reset);
+        println!("  [1] RESUME");
+        println!("  [2] SETTINGS");
+        println!("  [3] QUIT");
+        print!("{}pause> {}", ui::palette().teal, ui::palette().reset);
+        io::stdout().flush().unwrap();
+        match input_mgr.read_line().trim().to_lowercase().as_str() {
+            "" | "1" | "resume" | "p" => return,
+            "2" | "settings" => settings.run_menu(input_mgr),
+            "3" | "quit" | "q" => {
+                // Whatever was animating winds down, then the shift unwinds.
+                input::request_quit();
+                return;
+            }
+            other => println!("Unknown choice: '{}'.", other),
+        }
+    }
+}
+
+fn print_game_over(engine: &GameEngine, ending: Ending) {
     ui::clear_screen();
-    println!("{}INCOMING PRIORITY ONE ALERT", ui::RED_ALERT);
-    thread::sleep(Duration::from_millis(500));
-    println!("\n{}CONNECTION ESTABLISHED.{}", ui::RED_ALERT, ui::RESET);
+    if ending.is_victory() {
+        let g = ui::glyphs();
+        let title = format!("  {}  ", ending.title());
+        let width = ui::visible_width(&title);
+        println!(
+            "{}{}{}{}",
+            ui::palette().teal,
+            g.tl_corner,
+            ui::divider(width),
+            g.tr_corner
+        );
+        println!(
+            "{}{}{}{}{}",
+            g.v_line,
+            ui::palette().white_bold,
+            title,
+            ui::palette().teal,
+            g.v_line
+        );
+        println!(
+            "{}{}{}{}",
+            g.bl_corner,
+            ui::divider(width),
+            g.br_corner,
+            ui::palette().reset
+        );
+        println!(
+            "\n{}{}{}",
+            ui::palette().teal,
+            ending.summary(),
+            ui::palette().reset
+        );
+    } else {
+        println!(
+            "{}{}{}",
+            ui::palette().red_alert,
+            ending.title(),
+            ui::palette().reset
+        );
+        println!(
+            "\n{}{}{}",
+            ui::palette().amber,
+            ending.summary(),
+            ui::palette().reset
+        );
+    }
+    println!();
+    println!(
+        "{}{}: {}{}",
+        ui::palette().grey_dim,
+        lang::text("gameover.days"),
+        engine.turn_count,
+        ui::palette().reset
+    );
+    println!(
+        "{}{}: {}{}",
+        ui::palette().grey_dim,
+        lang::text("gameover.duration"),
+        ui::format_duration(engine.elapsed()),
+        ui::palette().reset
+    );
 
-    if is_mole_reveal {
+    let closings = engine.turn_closings();
+    if closings.is_empty() {
+        return;
+    }
+    println!(
+        "\n{}{}:{}",
+        ui::palette().amber,
+        lang::text("gameover.arc"),
+        ui::palette().reset
+    );
+    let arc = |metric: fn(&game::TurnRecord) -> f64| -> Vec<f64> {
+        closings.iter().map(|r| metric(r)).collect()
+    };
+    let arcs = [
+        ("TENSION", arc(|r| r.tension), ui::palette().orange),
+        ("STABILITY", arc(|r| r.stability), ui::palette().teal),
+        ("WEAPON", arc(|r| r.weapon), ui::palette().red_alert),
+    ];
+    for (label, values, color) in arcs {
         println!(
-            "{}VOICE: So... you figured it out. Smart.{}",
-            ui::AMBER,
-            ui::RESET
+            "  {}{:<10}{}{}{}",
+            ui::palette().grey_dim,
+            label,
+            color,
+            ui::sparkline(&values),
+            ui::palette().reset
         );
-        thread::sleep(Duration::from_millis(2000));
-        println!("{}VOICE: I am doing this for the greater good. The war is inevitable. I just wanted to finish it quickly.{}", ui::AMBER, ui::RESET);
-        println!("\nDECISION POINT:");
-        println!("1. EXECUTE (Silence the traitor. Immediate stability boost, high paranoia.)");
-        println!("2. TURN (Force them to double-agent. High risk, high intel reward.)");
+    }
+}
 
-        print!("\n{}YOUR ORDER >> {}", ui::RED_ALERT, ui::RESET);
-        io::stdout().flush().unwrap();
+/// Counts down before a dangerous directive is committed. Returns true if
+/// the operator pressed Enter to call it off. Only live keystrokes count;
+/// queued macro lines are left for the next prompt.
+fn abort_window(input_mgr: &InputManager, name: &str, hotkeys: bool) -> bool {
+    // A script or a log can't press Enter in time, so it gets no window.
+    if !input_mgr.is_interactive() || !io::stdout().is_terminal() {
+        return false;
+    }
+    input_mgr.set_aside_pending();
+    print_key_footer(KeyMode::Countdown, hotkeys);
+    // Without escape codes the count is not redrawn in place: it is
+    // announced once and the window runs silently.
+    let plain = ui::palette().is_plain();
+    for remaining in (1..=ABORT_WINDOW_SECS).rev() {
+        let first = remaining == ABORT_WINDOW_SECS;
+        if !plain || first {
+            print!(
+                "{}{}EXECUTING {} IN {}...{}",
+                if first { "" } else { "\r" },
+                ui::palette().red_alert,
+                name,
+                remaining,
+                ui::palette().reset
+            );
+            io::stdout().flush().unwrap();
+        }
+        for _ in 0..10 {
+            if input_mgr.poll_line() {
+                println!();
+                return true;
+            }
+            // A safety window, not an animation: it keeps time in fast mode.
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+    println!();
+    false
+}
 
-        input_mgr.flush();
-        let input = input_mgr.read_line();
-        let input = input.trim();
+/// Maps a directive name (as typed at the prompt) to a directive for `preview`.
+/// Targets are irrelevant to the effect summary, so they are left empty.
+fn preview_target(name: &str) -> Option<Directive> {
+    let name = name.trim_start_matches('-');
+    if name.eq_ignore_ascii_case("triage") {
+        return Some(Directive::Triage);
+    }
+    Some(match commands::canonical(name)? {
+        "escalate" => Directive::Escalate,
+        "investigate" => Directive::Investigate,
+        "contain" => Directive::Contain,
+        "leak" => Directive::Leak,
+        "stand-down" => Directive::StandDown,
+        "decrypt" => Directive::Decrypt(String::new()),
+        "analyze" => Directive::Analyze(String::new()),
+        "trace" => Directive::Trace(String::new()),
+        "consult" => Directive::Consult(String::new()),
+        "roundtable" => Directive::RoundTable,
+        "overclock" => Directive::Overclock,
+        "interrogate" => Directive::Interrogate(String::new()),
+        _ => return None,
+    })
+}
+
+/// The six world metrics for the threat board, each with how close it
+/// reads to disaster: high is bad for most, low for stability and secrecy.
+fn threats(state: &state::WorldState) -> [ui::Threat<'static>; 6] {
+    let threat = |label, value: f64, danger| ui::Threat {
+        label,
+        value,
+        danger,
+    };
+    [
+        threat("TENSION", state.global_tension, state.global_tension),
+        threat(
+            "STABILITY",
+            state.domestic_stability,
+            1.0 - state.domestic_stability,
+        ),
+        threat("PARANOIA", state.foreign_paranoia, state.foreign_paranoia),
+        threat(
+            "SECRECY",
+            state.internal_secrecy,
+            1.0 - state.internal_secrecy,
+        ),
+        threat(
+            "ESCALATION RISK",
+            state.accidental_escalation_risk,
+            state.accidental_escalation_risk,
+        ),
+        threat(
+            "SECRET WEAPON",
+            state.secret_weapon_progress,
+            state.secret_weapon_progress,
+        ),
+    ]
+}
 
-        match input {
-            "1" | "execute" => {
+/// The one-time alert when tension first crosses into DEFCON 1.
+fn warn_point_of_no_return(engine: &mut GameEngine, settings: &Settings) {
+    if engine.ending().is_some() || !engine.take_defcon1_warning() {
+        return;
+    }
+    if settings.bell {
+        ui::bell();
+    }
+    println!();
+    ui::flash(
+        "!!! DEFCON 1 // COCKED PISTOL !!!",
+        4,
+        ui::palette().red_alert,
+    );
+    for line in [
+        "STRATEGIC FORCES ARE ON HAIR TRIGGER. BOTH SIDES ARE READING EVERY MOVE AS THE FIRST SHOT.",
+        "ANY FURTHER ESCALATION WILL VERY LIKELY MEAN LAUNCH.",
+        "CONTAIN OR STAND DOWN WHILE THERE IS STILL A CHOICE TO MAKE.",
+    ] {
+        println!("{}{}{}", ui::palette().amber, line, ui::palette().reset);
+    }
+    ui::pause(2500);
+}
+
+/// Records what the engine awarded and flashes a notice for each first unlock.
+fn announce_achievements(engine: &mut GameEngine, book: &mut Option<AchievementBook>) {
+    let earned = std::mem::take(&mut engine.achievements);
+    let Some(book) = book else {
+        return;
+    };
+    for achievement in earned {
+        match book.unlock(achievement) {
+            Ok(true) => {
                 println!(
-                    "\n{}COMMAND: SECURITY TEAM DISPATCHED. TARGET NEUTRALIZED.{}",
-                    ui::TEAL,
-                    ui::RESET
+                    "\n{}*** ACHIEVEMENT UNLOCKED: {} ***{}",
+                    ui::palette().amber,
+                    achievement.title(),
+                    ui::palette().reset
                 );
-                engine.state.domestic_stability += 0.3;
-                engine.state.foreign_paranoia += 0.2;
-            }
-            _ => {
                 println!(
-                    "\n{}COMMAND: ASSET FLIPPED. THEY ARE FEEDING DISINFORMATION TO THE ENEMY.{}",
-                    ui::TEAL,
-                    ui::RESET
+                    "{}{}{}",
+                    ui::palette().grey_dim,
+                    achievement.description(),
+                    ui::palette().reset
                 );
-                engine.state.global_tension -= 0.3;
-                engine.state.internal_secrecy -= 0.1;
-                engine.state.accidental_escalation_risk += 0.1;
+                ui::pause(1500);
             }
+            Ok(false) => {}
+            Err(e) => eprintln!("coldwar: could not record achievement: {}", e),
+        }
+    }
+}
+
+fn print_achievements(book: Option<&AchievementBook>) {
+    let Some(book) = book else {
+        println!(
+            "{}ACHIEVEMENTS ARE NOT TRACKED IN ATTRACT MODE.{}",
+            ui::palette().grey_dim,
+            ui::palette().reset
+        );
+        return;
+    };
+    for achievement in Achievement::ALL {
+        let (mark, color) = if book.is_unlocked(achievement) {
+            ("[X]", ui::palette().amber)
+        } else {
+            ("[ ]", ui::palette().grey_dim)
+        };
+        println!(
+            "{}{} {:<12} {}{}",
+            color,
+            mark,
+            achievement.title(),
+            achievement.description(),
+            ui::palette().reset
+        );
+    }
+}
+
+fn print_help() {
+    // Command syntax stays English; only the descriptions are translated.
+    println!("{}{}", ui::palette().grey_dim, lang::text("help.header"));
+    for command in commands::COMMANDS {
+        for (syntax, id) in command.usage {
+            println!("  {:<24} - {}", syntax, lang::text(id));
         }
-        if let Some(mole_mut) = engine
-            .state
-            .advisors
-            .iter_mut()
-            .find(|a| a.suspicion >= 100)
-        {
-            mole_mut.suspicion = 0;
-            mole_mut.is_mole = false;
+    }
+    print!("{}", ui::palette().reset);
+}
+
+/// A faint line of the shortcuts live in `mode`, if there are any.
+fn print_key_footer(mode: KeyMode, hotkeys: bool) {
+    let entries: Vec<(&str, &str)> = input::shortcuts(mode, hotkeys)
+        .map(|s| (s.keys, lang::text(s.action_id)))
+        .collect();
+    let line = ui::key_footer(&entries, ui::terminal_width().saturating_sub(3));
+    if !line.is_empty() {
+        println!(
+            "{}  {}{}",
+            ui::palette().grey_dim,
+            line,
+            ui::palette().reset
+        );
+    }
+}
+
+/// Every shortcut by mode, marking the ones switched off.
+fn print_keys(hotkeys: bool) {
+    for shortcut in input::SHORTCUTS {
+        let off = if shortcut.needs_hotkeys && !hotkeys {
+            lang::text("keys.off")
+        } else {
+            ""
+        };
+        println!(
+            "{}{:<10} {:<16} {} {}{}",
+            ui::palette().grey_dim,
+            shortcut.mode.label(),
+            shortcut.keys,
+            lang::text(shortcut.action_id),
+            off,
+            ui::palette().reset
+        );
+    }
+}
+
+/// Debug `peek`: a sample of tomorrow's feed with the type mix, so content
+/// authors can see what a given state produces. Nothing is dealt.
+fn print_peek(engine: &GameEngine) {
+    let sample = engine.peek_batch();
+    println!(
+        "{}PEEK // SAMPLE FEED FOR DAY {} (NOT DEALT){}",
+        ui::palette().amber,
+        engine.turn_count + 1,
+        ui::palette().reset
+    );
+    let mut tally: Vec<(&str, usize)> = Vec::new();
+    for doc in &sample {
+        let label = doc.doc_type.label();
+        match tally.iter_mut().find(|(l, _)| *l == label) {
+            Some(entry) => entry.1 += 1,
+            None => tally.push((label, 1)),
+        }
+        let mut flags = Vec::new();
+        if doc.is_encrypted {
+            flags.push("ENCRYPTED");
+        }
+        if doc.priority {
+            flags.push("PRIORITY");
         }
+        if doc.decrypt_cost() > 1 {
+            flags.push("MULTI-STAGE");
+        }
+        println!(
+            "{} {} {} / {} / RELIABILITY {:.2} {}{}",
+            ui::palette().teal,
+            doc.id,
+            doc.doc_type.label(),
+            doc.clearance_level,
+            doc.reliability,
+            flags.join(" "),
+            ui::palette().reset
+        );
+        for line in ui::wrap(
+            &doc.content,
+            ui::terminal_width().saturating_sub(1).max(20),
+            "   ",
+        ) {
+            println!("{}{}{}", ui::palette().grey_dim, line, ui::palette().reset);
+        }
+    }
+    let mix: Vec<String> = tally
+        .iter()
+        .map(|(label, n)| format!("{} x{}", label, n))
+        .collect();
+    println!(
+        "{}MIX: {}{}",
+        ui::palette().amber,
+        mix.join(", "),
+        ui::palette().reset
+    );
+}
+
+/// The verb/alias table, for `commands` and `--list-commands`.
+fn print_commands() {
+    for line in commands::table() {
+        println!("{}{}{}", ui::palette().grey_dim, line, ui::palette().reset);
+    }
+}
+
+/// Stages `crisis`, takes the operator's answer and shows what came of it.
+fn handle_crisis(
+    engine: &mut GameEngine,
+    crisis: Crisis,
+    rng: &mut dyn Rng,
+    settings: &mut Settings,
+    input_mgr: &InputManager,
+) {
+    ui::clear_screen();
+    let mut beat = |ms| beat(ms, settings, input_mgr);
+    let sign_off = match crisis {
+        Crisis::RedPhone => stage_red_phone(engine, &mut beat),
+        Crisis::Hotline => stage_hotline(rng, &mut beat),
+        Crisis::Coup => stage_coup(&mut beat),
+        Crisis::Doppelganger => stage_doppelganger(&mut beat),
+    };
+
+    io::stdout().flush().unwrap();
+    input_mgr.flush();
+    // The Premier will not hold; the mole, caught, has nowhere to go.
+    let answer = if crisis == Crisis::RedPhone && !crisis::mole_on_the_line(&engine.state) {
+        red_phone_answer(input_mgr).unwrap_or_default()
+    } else {
+        input_mgr.read_line()
+    };
+    if input::quit_requested() {
+        // Hanging up is not an answer.
+        return;
+    }
+
+    let outcome = crisis::resolve(engine, crisis, &answer, rng);
+    let color = match outcome.tone {
+        Tone::Good => ui::palette().teal,
+        Tone::Uneasy => ui::palette().amber,
+        Tone::Dire => ui::palette().red_alert,
+    };
+    println!("\n{}{}{}", color, outcome.text, ui::palette().reset);
+
+    beat(3000);
+    println!(
+        "{}{}{}",
+        ui::palette().red_alert,
+        sign_off,
+        ui::palette().reset
+    );
+    beat(2000);
+}
+
+/// Prints the Red Phone call up to the prompt; returns the sign-off line.
+/// Each stage waits out its dramatic beats through `beat`, which can pause.
+fn stage_red_phone(engine: &GameEngine, beat: &mut dyn FnMut(u64)) -> &'static str {
+    println!("{}INCOMING PRIORITY ONE ALERT", ui::palette().red_alert);
+    beat(500);
+    println!(
+        "\n{}CONNECTION ESTABLISHED.{}",
+        ui::palette().red_alert,
+        ui::palette().reset
+    );
+
+    if crisis::mole_on_the_line(&engine.state) {
+        println!(
+            "{}VOICE: So... you figured it out. Smart.{}",
+            ui::palette().amber,
+            ui::palette().reset
+        );
+        beat(2000);
+        println!("{}VOICE: I am doing this for the greater good. The war is inevitable. I just wanted to finish it quickly.{}", ui::palette().amber, ui::palette().reset);
+        println!("\nDECISION POINT:");
+        println!("1. EXECUTE (Silence the traitor. Immediate stability boost, high paranoia.)");
+        println!("2. TURN (Force them to double-agent. High risk, high intel reward.)");
+        print!(
+            "\n{}YOUR ORDER >> {}",
+            ui::palette().red_alert,
+            ui::palette().reset
+        );
     } else {
         println!(
             "{}VOICE: PREMIER CHERNOV HERE. WE SEE YOUR BOMBERS. EXPLAIN YOURSELF OR WE LAUNCH.{}",
-            ui::AMBER,
-            ui::RESET
+            ui::palette().amber,
+            ui::palette().reset
         );
-        println!("(You have 10 seconds to respond correctly)");
+        println!("(You have {} seconds to respond correctly)", RED_PHONE_SECS);
         println!("\nDECISION POINT:");
         println!("1. DENY (Claim it's a training exercise)");
         println!("2. ADMIT (Tell the truth, ask for de-escalation)");
         println!("3. THREATEN (Tell them to back down or else)");
+        print!("\n{}", red_phone_prompt(RED_PHONE_SECS));
+    }
+    "CALL TERMINATED."
+}
 
-        print!("\n{}YOUR RESPONSE >> {}", ui::RED_ALERT, ui::RESET);
-        io::stdout().flush().unwrap();
-
-        input_mgr.flush();
-        let input = input_mgr.read_line();
-        let input = input.trim();
+/// The Chernov prompt with the seconds left. Always the same width, so it can
+/// be redrawn without disturbing what has been typed after it.
+fn red_phone_prompt(remaining: u64) -> String {
+    format!(
+        "{}[{:>2}] YOUR RESPONSE >> {}",
+        ui::palette().red_alert,
+        remaining,
+        ui::palette().reset
+    )
+}
 
-        match input {
-            "1" | "deny" => {
-                if engine.state.foreign_paranoia > 0.7 {
-                    println!(
-                        "\n{}CHERNOV: LIAR! WE ARE LAUNCHING!{}",
-                        ui::RED_ALERT,
-                        ui::RESET
-                    );
-                    engine.state.global_tension = 1.0;
-                } else {
-                    println!(
-                        "\n{}CHERNOV: ...Fine. Turn them around. Now.{}",
-                        ui::AMBER,
-                        ui::RESET
-                    );
-                    engine.state.global_tension -= 0.2;
-                }
-            }
-            "2" | "admit" => {
-                println!("\n{}CHERNOV: A bold admission. We will stand down, but there will be consequences.{}", ui::AMBER, ui::RESET);
-                engine.state.global_tension -= 0.5;
-                engine.state.domestic_stability -= 0.3;
-            }
-            "3" | "threaten" => {
-                println!("\n{}CHERNOV: THEN LET IT END!{}", ui::RED_ALERT, ui::RESET);
-                engine.state.global_tension = 1.0;
-            }
-            _ => {
-                println!(
-                    "\n{}CHERNOV: YOUR SILENCE IS DAMNING. LAUNCHING!{}",
-                    ui::RED_ALERT,
-                    ui::RESET
-                );
-                engine.state.global_tension = 1.0;
-            }
+/// Waits out the Red Phone countdown, ticking the prompt down in place.
+/// None if the time runs out (or a quit cuts the call).
+fn red_phone_answer(input_mgr: &InputManager) -> Option<String> {
+    let p = ui::palette();
+    if p.is_plain() {
+        // Nothing to tick in place; just keep the time.
+        let answer = input_mgr.read_line_timeout(Duration::from_secs(RED_PHONE_SECS));
+        if answer.is_none() && !input::quit_requested() {
+            println!("\nLINE OPEN. NO ANSWER GIVEN.");
+        }
+        return answer;
+    }
+    for remaining in (1..=RED_PHONE_SECS).rev() {
+        // Save the cursor, redraw the prompt, and put the cursor back after
+        // whatever the player has typed so far.
+        print!(
+            "{}\r{}{}",
+            p.save_cursor,
+            red_phone_prompt(remaining),
+            p.restore_cursor
+        );
+        io::stdout().flush().unwrap();
+        if let Some(line) = input_mgr.read_line_timeout(Duration::from_secs(1)) {
+            return Some(line);
+        }
+        if input::quit_requested() {
+            return None;
         }
     }
+    print!(
+        "{}\r{}{}",
+        p.save_cursor,
+        red_phone_prompt(0),
+        p.restore_cursor
+    );
+    println!("\n{}LINE OPEN. NO ANSWER GIVEN.{}", p.grey_dim, p.reset);
+    None
+}
 
-    thread::sleep(Duration::from_millis(3000));
-    println!("{}CALL TERMINATED.{}", ui::RED_ALERT, ui::RESET);
-    thread::sleep(Duration::from_millis(2000));
+/// The enemy calls with terms instead of threats. Taking them cools the
+/// crisis at some cost at home; refusing tells Moscow we want the fight.
+fn stage_hotline(rng: &mut dyn Rng, beat: &mut dyn FnMut(u64)) -> &'static str {
+    let (offer, cost) = *rng.choose(crisis::HOTLINE_OFFERS);
+    println!(
+        "{}INCOMING CALL // MOSCOW-WASHINGTON HOTLINE",
+        ui::palette().amber
+    );
+    beat(500);
+    println!(
+        "\n{}CONNECTION ESTABLISHED.{}",
+        ui::palette().amber,
+        ui::palette().reset
+    );
+    println!(
+        "{}VOICE: PREMIER CHERNOV HERE. NEITHER OF US WANTS THIS. {}{}",
+        ui::palette().amber,
+        offer,
+        ui::palette().reset
+    );
+    println!("\nDECISION POINT:");
+    println!("1. ACCEPT (Tension falls. {})", cost.to_lowercase());
+    println!("2. REJECT (They will read it as a sign we want war.)");
+    print!(
+        "\n{}YOUR RESPONSE >> {}",
+        ui::palette().red_alert,
+        ui::palette().reset
+    );
+    "CALL TERMINATED."
 }
 
-fn corrupt_text(text: &str, turn: u32, rng: &mut SimpleRng) -> String {
+fn stage_coup(beat: &mut dyn FnMut(u64)) -> &'static str {
+    println!(
+        "{}FLASH: ARMORED COLUMNS ENTERING THE CAPITAL",
+        ui::palette().red_alert
+    );
+    beat(500);
+    println!(
+        "\n{}THE GENERAL STAFF HAS SEIZED THE BROADCAST TOWERS.{}",
+        ui::palette().red_alert,
+        ui::palette().reset
+    );
+    println!(
+        "{}VOICE: The government has lost the confidence of the people. Step aside, Operator.{}",
+        ui::palette().amber,
+        ui::palette().reset
+    );
+    println!("\nDECISION POINT:");
+    println!("1. ARREST THE GENERALS (Spend secrecy on a crackdown. May fail.)");
+    println!("2. CONCEDE POWERS (Hand the junta emergency powers. Calm, but totalitarian.)");
+    print!(
+        "\n{}YOUR ORDER >> {}",
+        ui::palette().red_alert,
+        ui::palette().reset
+    );
+    "BROADCAST TERMINATED."
+}
+
+fn stage_doppelganger(beat: &mut dyn FnMut(u64)) -> &'static str {
+    println!("{}PRIORITY ONE // EYES ONLY", ui::palette().red_alert);
+    beat(500);
+    println!(
+        "\n{}SECRET SERVICE: THE MAN IN THE OVAL OFFICE FAILED A FINGERPRINT CHECK.{}",
+        ui::palette().red_alert,
+        ui::palette().reset
+    );
+    println!(
+        "{}VOICE: He's still giving orders, Operator. Half the cabinet hasn't noticed. What do we do?{}",
+        ui::palette().amber,
+        ui::palette().reset
+    );
+    println!("\nDECISION POINT:");
+    println!("1. EXPOSE (Arrest him on live television. Needs a steady home front.)");
+    println!("2. SWAP (Extract him quietly and return the real President. Needs secrecy.)");
+    println!("3. SAY NOTHING (Let the double keep signing.)");
+    print!(
+        "\n{}YOUR ORDER >> {}",
+        ui::palette().red_alert,
+        ui::palette().reset
+    );
+    "LINE SECURED."
+}
+
+fn corrupt_text(text: &str, turn: u32, rng: &mut dyn Rng) -> String {
     if turn < 8 {
         return text.to_string();
     }
@@ -481,37 +1617,129 @@ fn corrupt_text(text: &str, turn: u32, rng: &mut SimpleRng) -> String {
         .collect()
 }
 
-fn random_char(rng: &mut SimpleRng) -> char {
-    let chars = b"0123456789ABCDEFXZ@#&";
-    let idx = rng.range(0, chars.len() as u64) as usize;
-    chars[idx] as char
+fn frequency_code(rng: &mut dyn Rng) -> String {
+    (0..3)
+        .map(|_| format!("{}{}", random_char(rng), random_char(rng)))
+        .collect::<Vec<_>>()
+        .join("-")
 }
 
-fn scramble_text(text: &str, rng: &mut SimpleRng) -> String {
-    let mut s = String::new();
-    for c in text.chars() {
-        if c.is_whitespace() {
-            s.push(' ');
-        } else {
-            s.push(random_char(rng));
+/// `read`: shows a document again the way it first came in. A cracked
+/// cipher replays its decryption; plain text types out at feed speed.
+fn replay_document(
+    doc: &document::Document,
+    settings: &mut Settings,
+    input_mgr: &InputManager,
+    rng: &mut dyn Rng,
+) {
+    let width = ui::terminal_width().saturating_sub(1).max(20);
+    println!(
+        "{} [ID: {}] CLASS: {} :: {}{}",
+        ui::palette().teal,
+        doc.id,
+        doc.clearance_level,
+        doc.timestamp,
+        ui::palette().reset
+    );
+    if doc.is_encrypted {
+        println!(
+            " {}ENCRYPTED CONTENT - DECRYPTION REQUIRED{}",
+            ui::palette().red_alert,
+            ui::palette().reset
+        );
+        for line in ui::wrap(&doc.partial_text(rng), width, " ") {
+            println!("{}{}{}", ui::palette().grey_dim, line, ui::palette().reset);
+        }
+    } else if doc.was_decrypted() {
+        // animate_decryption adds its own leading space.
+        for line in ui::wrap(&doc.content, width.saturating_sub(1), "") {
+            animate_decryption(&line, rng);
         }
+    } else {
+        let lines = ui::wrap(&doc.content, width, " ");
+        ui::type_feed_lines(
+            &lines,
+            settings.feed_speed_ms,
+            ui::palette().teal,
+            input_mgr,
+            &mut || pause_menu(settings, input_mgr),
+        );
+    }
+    println!(
+        "{}{}{}",
+        ui::palette().grey_dim,
+        ui::divider(60),
+        ui::palette().reset
+    );
+}
+
+/// Resolves scrambled noise into `target` one character at a time.
+fn animate_decryption(target: &str, rng: &mut dyn Rng) {
+    let p = ui::palette();
+    if ui::is_fast() || p.is_plain() {
+        println!(" {}{}{}", p.amber, target, p.reset);
+        return;
     }
-    s
+    let _cursor = ui::TerminalGuard::hide_cursor();
+    for frame in decryption_frames(target, rng) {
+        print!("\r {}{}{}", p.amber, frame, p.reset);
+        io::stdout().flush().unwrap();
+        ui::pause(40);
+    }
+    println!();
 }
 
-fn trigger_interruption(_rng: &mut SimpleRng, _input_mgr: &InputManager) {
-    print!("\x07");
+fn trigger_interruption(
+    engine: &mut GameEngine,
+    rng: &mut dyn Rng,
+    bell: bool,
+    input_mgr: &InputManager,
+) {
+    if bell {
+        ui::bell();
+    }
     println!(
         "\n{}!!! SIGNAL INTERRUPT DETECTED !!!{}",
-        ui::RED_ALERT,
-        ui::RESET
+        ui::palette().red_alert,
+        ui::palette().reset
     );
-    thread::sleep(Duration::from_millis(500));
+    ui::pause(500);
     // ASCII Art omitted for brevity in rewrite, just a message
     println!(
         "{}INTRUDER MESSAGE: THEY ARE WATCHING.{}",
-        ui::RED_ALERT,
-        ui::RESET
+        ui::palette().red_alert,
+        ui::palette().reset
     );
-    thread::sleep(Duration::from_millis(1000));
+
+    // Trace-the-signal: echo the carrier code back before it drifts.
+    let code = frequency_code(rng);
+    println!(
+        "{}CARRIER FREQUENCY RESOLVING... TYPE IT BACK WITHIN {} SECONDS TO LOCK ON.{}",
+        ui::palette().amber,
+        INTERRUPT_WINDOW_SECS,
+        ui::palette().reset
+    );
+    animate_decryption(&code, rng);
+
+    input_mgr.flush();
+    print!("{}LOCK >> {}", ui::palette().red_alert, ui::palette().reset);
+    io::stdout().flush().unwrap();
+
+    match input_mgr.read_line_timeout(Duration::from_secs(INTERRUPT_WINDOW_SECS)) {
+        Some(line) if line.trim().eq_ignore_ascii_case(&code) => {
+            let reward = engine.resolve_signal_lock();
+            println!("{}{}{}", ui::palette().teal, reward, ui::palette().reset);
+        }
+        Some(_) => println!(
+            "{}CODE MISMATCH. SIGNAL LOST.{}",
+            ui::palette().red_alert,
+            ui::palette().reset
+        ),
+        None => println!(
+            "\n{}SIGNAL LOST.{}",
+            ui::palette().red_alert,
+            ui::palette().reset
+        ),
+    }
+    ui::pause(1000);
 }
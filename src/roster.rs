@@ -0,0 +1,54 @@
+use crate::state::{Advisor, AdvisorRole};
+use crate::toml;
+use std::fs;
+
+/// Loads an advisor roster from a TOML file of `[[advisor]]` entries:
+///
+/// ```toml
+/// [[advisor]]
+/// name = "Gen. Vance"
+/// role = "general"
+/// ```
+pub fn load_roster(path: &str) -> Result<Vec<Advisor>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("cannot read '{}': {}", path, e))?;
+    parse_roster(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
+pub fn parse_roster(text: &str) -> Result<Vec<Advisor>, String> {
+    let doc = toml::parse(text)?;
+    let mut advisors: Vec<Advisor> = Vec::new();
+
+    for (i, entry) in doc.array("advisor").iter().enumerate() {
+        let name = entry
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| format!("advisor #{} is missing a name", i + 1))?;
+        let role_str = entry
+            .get("role")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("advisor '{}' is missing a role", name))?;
+        let role = AdvisorRole::parse(role_str)
+            .ok_or_else(|| format!("advisor '{}' has unknown role '{}'", name, role_str))?;
+
+        if advisors.iter().any(|a| a.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("duplicate advisor name '{}'", name));
+        }
+
+        advisors.push(Advisor {
+            name: name.to_string(),
+            role,
+            suspicion: 0,
+            is_mole: false,
+            is_double_agent: false,
+            leads: 0,
+        });
+    }
+
+    if advisors.is_empty() {
+        return Err("roster defines no [[advisor]] entries".to_string());
+    }
+
+    Ok(advisors)
+}
@@ -0,0 +1,973 @@
+//! Save files: a snapshot of the engine written in the same TOML subset the
+//! roster uses, so it reads back through `crate::toml`.
+//!
+//! Everything the next turn depends on is kept, including the mole's identity
+//! and the engine's place in its random stream, so resuming can never re-roll
+//! either. A scenario is kept by name: its generators cannot be written
+//! out, so loading sets them up again. Start-of-turn bulletins, the intel
+//! ledger, unannounced achievements and the diagnostics sink are not saved.
+//!
+//! Each save opens with a format `version` and a `checksum` of everything
+//! after it. A truncated or hand-edited file fails the checksum instead of
+//! loading half a game. Unversioned saves from before the header existed are
+//! migrated by filling in the fields added since; saves from a newer build
+//! are refused.
+
+use crate::archive::Archive;
+use crate::document::{Document, DocumentType, GeneratorRegistry};
+use crate::game::{AlarmContext, Counsel, Difficulty, GameEngine, TurnRecord, Volume, HISTORY_CAP};
+use crate::scenario;
+use crate::state::{Advisor, AdvisorRole, EnemyPosture, WorldState};
+use crate::stats::RunStats;
+use crate::toml::{self, Table, TomlDocument, Value};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Slot written by autosave and resumed by `--continue`.
+pub const AUTOSAVE_PATH: &str = "coldwar-autosave.toml";
+
+/// Slot for `save` and `load` without a file name. Kept apart from the
+/// autosave, so the next day's autosave never overwrites a deliberate save.
+pub const SAVE_PATH: &str = "coldwar-save.toml";
+
+/// Format written by this build. Bump it when a field is added or changes
+/// meaning, and teach `migrate` to bring the previous format forward.
+pub const SAVE_VERSION: u32 = 9;
+
+const DOCUMENT_TYPES: [DocumentType; 7] = [
+    DocumentType::IntelligenceCable,
+    DocumentType::InternalMemo,
+    DocumentType::BudgetAnomaly,
+    DocumentType::ForeignIntercept,
+    DocumentType::AnonymousLeak,
+    DocumentType::AdvisorMessage,
+    DocumentType::NewsWire,
+];
+
+/// Writes `engine` to `path`. The file is written beside the target and
+/// renamed over it, so a crash mid-write leaves the previous save intact.
+pub fn write(engine: &GameEngine, path: &str) -> io::Result<()> {
+    let tmp = format!("{}.tmp", path);
+    fs::write(&tmp, render(engine))?;
+    fs::rename(&tmp, path)
+}
+
+pub fn load(path: &str) -> Result<GameEngine, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("cannot read '{}': {}", path, e))?;
+    parse(&text).map_err(|e| format!("{}: {} (delete the file to start a new shift)", path, e))
+}
+
+pub fn render(engine: &GameEngine) -> String {
+    let body = render_body(engine);
+    format!(
+        "# Cold War Terminal save. Editing this file is treason.\nversion = {}\nchecksum = \"{:016x}\"\n{}",
+        SAVE_VERSION,
+        checksum(&body),
+        body
+    )
+}
+
+/// FNV-1a over the save body: cheap, dependency-free, and enough to notice
+/// a truncated or edited file. It is not meant to stop a determined cheat.
+fn checksum(body: &str) -> u64 {
+    body.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// The text the checksum covers: everything after the checksum line.
+fn checksummed_body(text: &str) -> Option<&str> {
+    let start = text.find("\nchecksum = ")? + 1;
+    let end = text[start..].find('\n')? + start + 1;
+    Some(&text[end..])
+}
+
+fn render_body(engine: &GameEngine) -> String {
+    let mut out = String::from("\n[engine]\n");
+    put(&mut out, "turn_count", engine.turn_count);
+    put(&mut out, "intel_points", engine.intel_points);
+    put(&mut out, "max_intel_points", engine.max_intel_points);
+    put(&mut out, "interruption_active", engine.interruption_active);
+    put(&mut out, "consult_count", engine.consult_count);
+    put(
+        &mut out,
+        "interrogations_this_turn",
+        engine.interrogations_this_turn,
+    );
+    for name in &engine.interrogated_advisors {
+        put(&mut out, "interrogated", quote(name));
+    }
+    put(&mut out, "traces_this_turn", engine.traces_this_turn);
+    put(
+        &mut out,
+        "overclocked_this_turn",
+        engine.overclocked_this_turn,
+    );
+    put(&mut out, "garble_next_feed", engine.garble_next_feed);
+    for name in &engine.traced_advisors {
+        put(&mut out, "traced", quote(name));
+    }
+    put(
+        &mut out,
+        "anomaly_chance",
+        float(engine.generators.anomaly_chance),
+    );
+    put(
+        &mut out,
+        "station_share",
+        float(engine.generators.station_share),
+    );
+    put(
+        &mut out,
+        "station_hint_chance",
+        float(engine.generators.station_hint_chance),
+    );
+    put(&mut out, "news_share", float(engine.generators.news_share));
+    put(
+        &mut out,
+        "news_clue_chance",
+        float(engine.generators.news_clue_chance),
+    );
+    if let Some(name) = engine.scenario {
+        put(&mut out, "scenario", quote(name));
+    }
+    put(&mut out, "archive_cap", engine.archive.capacity());
+    put(&mut out, "ticker_cap", engine.recent_leaks.capacity());
+    put(&mut out, "history_cap", engine.history.capacity());
+    put(&mut out, "basilisk_override", engine.basilisk_override);
+    if let Some(state) = engine.rng_state() {
+        put(&mut out, "rng_state", quote(&format!("{:016x}", state)));
+    }
+    if let Some(turn) = engine.last_crisis_turn {
+        put(&mut out, "last_crisis_turn", turn);
+    }
+    if let Some(limit) = engine.turn_limit {
+        put(&mut out, "turn_limit", limit);
+    }
+    put(&mut out, "shift_complete", engine.shift_complete);
+    put(&mut out, "defcon1_warned", engine.defcon1_warned);
+    put(&mut out, "elapsed_secs", engine.elapsed().as_secs());
+    put(
+        &mut out,
+        "volume",
+        quote(&format!("{:?}", engine.volume).to_lowercase()),
+    );
+    put(
+        &mut out,
+        "difficulty",
+        quote(&format!("{:?}", engine.difficulty).to_lowercase()),
+    );
+    put(&mut out, "previous_tension", float(engine.previous_tension));
+    for r in &engine.analyzed_reliability {
+        put(&mut out, "analyzed_reliability", float(*r));
+    }
+    put(&mut out, "mole_masked", engine.mole_masked);
+    put(&mut out, "suspicion_decay", engine.suspicion_decay);
+    put(&mut out, "mole_creep", engine.mole_creep);
+    put(
+        &mut out,
+        "standdown_penalty_turns",
+        engine.standdown_penalty_turns,
+    );
+    if let Some(alarm) = &engine.last_alarm {
+        put(&mut out, "alarm_doc", quote(&alarm.doc_id));
+        put(&mut out, "alarm_reliability", float(alarm.reliability));
+        put(&mut out, "alarm_analyzed", alarm.analyzed);
+    }
+    for stance in engine.recent_stances.iter() {
+        put(&mut out, "stance", stance);
+    }
+    for counsel in &engine.counsel {
+        put(&mut out, "counsel_advisor", quote(&counsel.advisor));
+        put(&mut out, "counsel_verb", quote(&counsel.verb));
+    }
+
+    let s = &engine.state;
+    out.push_str("\n[world]\n");
+    put(&mut out, "global_tension", float(s.global_tension));
+    put(&mut out, "internal_secrecy", float(s.internal_secrecy));
+    put(&mut out, "foreign_paranoia", float(s.foreign_paranoia));
+    put(
+        &mut out,
+        "accidental_escalation_risk",
+        float(s.accidental_escalation_risk),
+    );
+    put(&mut out, "domestic_stability", float(s.domestic_stability));
+    put(
+        &mut out,
+        "secret_weapon_progress",
+        float(s.secret_weapon_progress),
+    );
+    put(&mut out, "red_phone_active", s.red_phone_active);
+    put(&mut out, "hotline_active", s.hotline_active);
+    put(&mut out, "system_corruption", float(s.system_corruption));
+    put(&mut out, "coup_active", s.coup_active);
+    put(&mut out, "coup_attempted", s.coup_attempted);
+    put(&mut out, "double_agent_turns", s.double_agent_turns);
+    if let Some(countdown) = s.doppelganger_countdown {
+        put(&mut out, "doppelganger_countdown", countdown);
+    }
+    put(&mut out, "doppelganger_active", s.doppelganger_active);
+    put(&mut out, "doppelganger_resolved", s.doppelganger_resolved);
+    put(&mut out, "impostor_in_office", s.impostor_in_office);
+    put(
+        &mut out,
+        "enemy_posture",
+        quote(&format!("{:?}", s.enemy_posture).to_lowercase()),
+    );
+
+    let stats = &engine.stats;
+    out.push_str("\n[stats]\n");
+    put(&mut out, "intel_spent", stats.intel_spent);
+    put(&mut out, "decrypts", stats.decrypts);
+    put(&mut out, "analyses", stats.analyses);
+    put(&mut out, "traces", stats.traces);
+    put(&mut out, "interrogations", stats.interrogations);
+    put(&mut out, "leaks", stats.leaks);
+    put(&mut out, "contains", stats.contains);
+    put(&mut out, "peak_tension", float(stats.peak_tension));
+    put(&mut out, "lowest_stability", float(stats.lowest_stability));
+
+    for a in &s.advisors {
+        out.push_str("\n[[advisor]]\n");
+        put(&mut out, "name", quote(&a.name));
+        put(
+            &mut out,
+            "role",
+            quote(&format!("{:?}", a.role).to_lowercase()),
+        );
+        put(&mut out, "suspicion", a.suspicion);
+        put(&mut out, "is_mole", a.is_mole);
+        put(&mut out, "is_double_agent", a.is_double_agent);
+        put(&mut out, "leads", a.leads);
+    }
+    for doc in &engine.pending_documents {
+        render_document(&mut out, "pending", doc);
+    }
+    for doc in engine.archive.iter() {
+        render_document(&mut out, "archived", doc);
+    }
+    for leak in engine.recent_leaks.iter() {
+        out.push_str("\n[[leak]]\n");
+        put(&mut out, "text", quote(leak));
+    }
+    for record in engine.history.iter() {
+        out.push_str("\n[[turn]]\n");
+        put(&mut out, "turn", record.turn);
+        put(&mut out, "directive", quote(&record.directive));
+        for fb in &record.feedback {
+            put(&mut out, "feedback", quote(fb));
+        }
+        put(&mut out, "tension", float(record.tension));
+        put(&mut out, "stability", float(record.stability));
+        put(&mut out, "weapon", float(record.weapon));
+    }
+    out
+}
+
+fn render_document(out: &mut String, section: &str, doc: &Document) {
+    let _ = writeln!(out, "\n[[{}]]", section);
+    put(out, "id", quote(&doc.id));
+    put(out, "doc_type", quote(doc.doc_type.label()));
+    put(out, "clearance_level", quote(&doc.clearance_level));
+    put(out, "timestamp", quote(&doc.timestamp));
+    put(out, "content", quote(&doc.content));
+    put(out, "is_encrypted", doc.is_encrypted);
+    put(out, "reliability", float(doc.reliability));
+    put(out, "priority", doc.priority);
+    put(out, "decrypt_progress", doc.decrypt_progress);
+    put(out, "vouched", doc.vouched);
+}
+
+fn put(out: &mut String, key: &str, value: impl std::fmt::Display) {
+    let _ = writeln!(out, "{} = {}", key, value);
+}
+
+fn quote(s: &str) -> String {
+    format!(
+        "\"{}\"",
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// `{:?}` always keeps a decimal point or exponent and round-trips exactly.
+fn float(f: f64) -> String {
+    format!("{:?}", f)
+}
+
+pub fn parse(text: &str) -> Result<GameEngine, String> {
+    // The header is checked before the rest is parsed, so a truncated file
+    // reports the damage rather than whatever syntax error it was cut at.
+    let header = toml::parse(&text[..text.find("\n[").unwrap_or(text.len())])?.root;
+    let version = match header.get("version") {
+        // Saves written before the header existed.
+        None => 1,
+        Some(v) => v
+            .as_int()
+            .and_then(|i| u32::try_from(i).ok())
+            .filter(|v| *v > 0)
+            .ok_or("invalid 'version'")?,
+    };
+    if version > SAVE_VERSION {
+        return Err(format!(
+            "save format {} is newer than this build understands (up to {}); update the game",
+            version, SAVE_VERSION
+        ));
+    }
+    if version == SAVE_VERSION {
+        let expected = header
+            .get("checksum")
+            .and_then(Value::as_str)
+            .and_then(|c| u64::from_str_radix(c, 16).ok())
+            .ok_or("missing or invalid 'checksum'")?;
+        let body = checksummed_body(text).ok_or("missing or invalid 'checksum'")?;
+        if checksum(body) != expected {
+            return Err("save is damaged or was edited (checksum mismatch)".to_string());
+        }
+    }
+    let mut doc = toml::parse(text)?;
+    migrate(&mut doc, version);
+    let engine_t = section(&doc, "engine")?;
+    let world_t = section(&doc, "world")?;
+    let stats_t = section(&doc, "stats")?;
+
+    let mut advisors = Vec::new();
+    for t in doc.array("advisor") {
+        let role_name = string(t, "role")?;
+        advisors.push(Advisor {
+            name: string(t, "name")?,
+            role: AdvisorRole::parse(&role_name)
+                .ok_or_else(|| format!("unknown advisor role '{}'", role_name))?,
+            suspicion: uint(t, "suspicion")?,
+            is_mole: boolean(t, "is_mole")?,
+            is_double_agent: boolean(t, "is_double_agent")?,
+            leads: uint(t, "leads")?,
+        });
+    }
+    if advisors.is_empty() {
+        return Err("save has no advisors".to_string());
+    }
+    if advisors.iter().filter(|a| a.is_mole).count() > 1 {
+        return Err("save names more than one mole".to_string());
+    }
+
+    let mut state = WorldState::with_advisors(Vec::new());
+    state.advisors = advisors;
+    let w = world_t;
+    state.global_tension = real(w, "global_tension")?;
+    state.internal_secrecy = real(w, "internal_secrecy")?;
+    state.foreign_paranoia = real(w, "foreign_paranoia")?;
+    state.accidental_escalation_risk = real(w, "accidental_escalation_risk")?;
+    state.domestic_stability = real(w, "domestic_stability")?;
+    state.secret_weapon_progress = real(w, "secret_weapon_progress")?;
+    state.red_phone_active = boolean(w, "red_phone_active")?;
+    state.hotline_active = boolean(w, "hotline_active")?;
+    state.system_corruption = real(w, "system_corruption")?;
+    state.coup_active = boolean(w, "coup_active")?;
+    state.coup_attempted = boolean(w, "coup_attempted")?;
+    state.double_agent_turns = uint(w, "double_agent_turns")?;
+    state.doppelganger_countdown = optional_uint(w, "doppelganger_countdown")?;
+    state.doppelganger_active = boolean(w, "doppelganger_active")?;
+    state.doppelganger_resolved = boolean(w, "doppelganger_resolved")?;
+    state.impostor_in_office = boolean(w, "impostor_in_office")?;
+    let posture = string(w, "enemy_posture")?;
+    state.enemy_posture = EnemyPosture::parse(&posture)
+        .ok_or_else(|| format!("unknown enemy posture '{}'", posture))?;
+
+    let e = engine_t;
+    let mut engine = GameEngine::new();
+    engine.state = state;
+    engine.turn_count = uint(e, "turn_count")?;
+    engine.intel_points = uint(e, "intel_points")?;
+    engine.max_intel_points = uint(e, "max_intel_points")?;
+    engine.interruption_active = boolean(e, "interruption_active")?;
+    engine.consult_count = uint(e, "consult_count")?;
+    engine.interrogations_this_turn = uint(e, "interrogations_this_turn")?;
+    engine.interrogated_advisors = strings(e, "interrogated")?;
+    engine.traces_this_turn = uint(e, "traces_this_turn")?;
+    engine.overclocked_this_turn = boolean(e, "overclocked_this_turn")?;
+    engine.garble_next_feed = boolean(e, "garble_next_feed")?;
+    engine.traced_advisors = strings(e, "traced")?;
+    // Saves from before format 9 name no scenario and keep the stock feed.
+    // Its generators are set up again first, so the saved tunables below
+    // still win over the scenario's opening mix.
+    if let Some(v) = e.get("scenario") {
+        let name = v.as_str().ok_or("invalid 'scenario'")?;
+        let scenario =
+            scenario::find(name).ok_or_else(|| format!("unknown scenario '{}'", name))?;
+        scenario.apply_feed(&mut engine.generators);
+        engine.scenario = Some(scenario.name);
+    }
+    engine.generators.anomaly_chance = real(e, "anomaly_chance")?;
+    engine.generators.station_share = real(e, "station_share")?;
+    engine.generators.station_hint_chance = real(e, "station_hint_chance")?;
+    engine.generators.news_share = real(e, "news_share")?;
+    engine.generators.news_clue_chance = real(e, "news_clue_chance")?;
+    engine.basilisk_override = boolean(e, "basilisk_override")?;
+    // Saves from before format 7 carry no stream and go on with a fresh one.
+    if let Some(v) = e.get("rng_state") {
+        let state = v
+            .as_str()
+            .and_then(|s| u64::from_str_radix(s, 16).ok())
+            .ok_or("missing or invalid 'rng_state'")?;
+        engine.restore_rng(state);
+    }
+    engine.turn_limit = optional_uint(e, "turn_limit")?;
+    engine.last_crisis_turn = optional_uint(e, "last_crisis_turn")?;
+    engine.shift_complete = boolean(e, "shift_complete")?;
+    engine.defcon1_warned = boolean(e, "defcon1_warned")?;
+    let elapsed = Duration::from_secs(uint(e, "elapsed_secs")? as u64);
+    engine.started_at = Instant::now()
+        .checked_sub(elapsed)
+        .unwrap_or_else(Instant::now);
+    let volume = string(e, "volume")?;
+    engine.volume = Volume::parse(&volume).ok_or_else(|| format!("unknown volume '{}'", volume))?;
+    // Difficulty tunes the drift rates, which are saved on their own below.
+    let difficulty = string(e, "difficulty")?;
+    engine.difficulty = Difficulty::parse(&difficulty)
+        .ok_or_else(|| format!("unknown difficulty '{}'", difficulty))?;
+    engine.previous_tension = real(e, "previous_tension")?;
+    engine.analyzed_reliability = e
+        .get_all("analyzed_reliability")
+        .map(|v| v.as_f64().ok_or("'analyzed_reliability' must be a number"))
+        .collect::<Result<_, _>>()?;
+    engine.mole_masked = boolean(e, "mole_masked")?;
+    engine.suspicion_decay = uint(e, "suspicion_decay")?;
+    engine.mole_creep = uint(e, "mole_creep")?;
+    engine.standdown_penalty_turns = uint(e, "standdown_penalty_turns")?;
+    if e.get("alarm_doc").is_some() {
+        engine.last_alarm = Some(AlarmContext {
+            doc_id: string(e, "alarm_doc")?,
+            reliability: real(e, "alarm_reliability")?,
+            analyzed: boolean(e, "alarm_analyzed")?,
+        });
+    }
+    for v in e.get_all("stance") {
+        let stance = v.as_int().ok_or("'stance' must be an integer")?;
+        engine.recent_stances.push(stance as i32);
+    }
+    let advisors = e.get_all("counsel_advisor").map(|v| v.as_str());
+    let verbs = e.get_all("counsel_verb").map(|v| v.as_str());
+    for (advisor, verb) in advisors.zip(verbs) {
+        let (Some(advisor), Some(verb)) = (advisor, verb) else {
+            return Err("'counsel_advisor' and 'counsel_verb' must be strings".to_string());
+        };
+        engine.counsel.push(Counsel {
+            advisor: advisor.to_string(),
+            verb: verb.to_string(),
+        });
+    }
+
+    let t = stats_t;
+    engine.stats = RunStats {
+        intel_spent: uint(t, "intel_spent")?,
+        decrypts: uint(t, "decrypts")?,
+        analyses: uint(t, "analyses")?,
+        traces: uint(t, "traces")?,
+        interrogations: uint(t, "interrogations")?,
+        leaks: uint(t, "leaks")?,
+        contains: uint(t, "contains")?,
+        peak_tension: real(t, "peak_tension")?,
+        lowest_stability: real(t, "lowest_stability")?,
+    };
+
+    engine.pending_documents = doc
+        .array("pending")
+        .into_iter()
+        .map(parse_document)
+        .collect::<Result<_, _>>()?;
+    engine.archive = Archive::with_capacity(uint(e, "archive_cap")? as usize);
+    for t in doc.array("archived") {
+        engine.archive.push(parse_document(t)?);
+    }
+    engine.recent_leaks = Archive::with_capacity(uint(e, "ticker_cap")? as usize);
+    for t in doc.array("leak") {
+        engine.recent_leaks.push(string(t, "text")?);
+    }
+    engine.history = Archive::with_capacity(uint(e, "history_cap")? as usize);
+    for t in doc.array("turn") {
+        engine.history.push(TurnRecord {
+            turn: uint(t, "turn")?,
+            directive: string(t, "directive")?,
+            feedback: strings(t, "feedback")?,
+            tension: real(t, "tension")?,
+            stability: real(t, "stability")?,
+            weapon: real(t, "weapon")?,
+        });
+    }
+
+    Ok(engine)
+}
+
+fn parse_document(t: &Table) -> Result<Document, String> {
+    let label = string(t, "doc_type")?;
+    let doc_type = DOCUMENT_TYPES
+        .iter()
+        .find(|d| d.label() == label)
+        .cloned()
+        .ok_or_else(|| format!("unknown document type '{}'", label))?;
+    Ok(Document {
+        id: string(t, "id")?,
+        doc_type,
+        clearance_level: string(t, "clearance_level")?,
+        timestamp: string(t, "timestamp")?,
+        content: string(t, "content")?,
+        is_encrypted: boolean(t, "is_encrypted")?,
+        reliability: real(t, "reliability")?,
+        priority: boolean(t, "priority")?,
+        decrypt_progress: uint(t, "decrypt_progress")?,
+        vouched: boolean(t, "vouched")?,
+    })
+}
+
+/// Brings an older save up to `SAVE_VERSION` by filling in what later
+/// formats added with the value a new shift starts from.
+fn migrate(doc: &mut TomlDocument, version: u32) {
+    if version < 2 {
+        let added: &[(&str, &str, Value)] = &[
+            ("engine", "overclocked_this_turn", Value::Bool(false)),
+            ("engine", "garble_next_feed", Value::Bool(false)),
+            ("engine", "standdown_penalty_turns", Value::Int(0)),
+            ("world", "hotline_active", Value::Bool(false)),
+            ("world", "enemy_posture", Value::Str("probing".to_string())),
+            ("advisor", "leads", Value::Int(0)),
+            ("pending", "vouched", Value::Bool(false)),
+            ("pending", "decrypt_progress", Value::Int(0)),
+            ("archived", "vouched", Value::Bool(false)),
+            ("archived", "decrypt_progress", Value::Int(0)),
+        ];
+        for (section, key, value) in added {
+            for (_, table) in doc.sections.iter_mut().filter(|(n, _)| n == section) {
+                if table.get(key).is_none() {
+                    table.entries.push((key.to_string(), value.clone()));
+                }
+            }
+        }
+        // Early turn records kept no readings; the closest thing on file is
+        // where the world stands now, so the arc starts out flat.
+        let world = doc
+            .array("world")
+            .first()
+            .map(|t| (*t).clone())
+            .unwrap_or_default();
+        let readings = [
+            ("tension", "global_tension"),
+            ("stability", "domestic_stability"),
+            ("weapon", "secret_weapon_progress"),
+        ];
+        for (_, table) in doc.sections.iter_mut().filter(|(n, _)| n == "turn") {
+            for (key, metric) in readings {
+                if table.get(key).is_none() {
+                    let value = world.get(metric).and_then(Value::as_f64).unwrap_or(0.0);
+                    table.entries.push((key.to_string(), Value::Float(value)));
+                }
+            }
+        }
+    }
+    if version < 3 {
+        // Counters were not kept before; the extremes can still be read off
+        // the turn history and the current world.
+        let readings = |section: &str, key: &str| -> Vec<f64> {
+            doc.array(section)
+                .iter()
+                .filter_map(|t| t.get(key).and_then(Value::as_f64))
+                .collect()
+        };
+        let tension = readings("turn", "tension")
+            .into_iter()
+            .chain(readings("world", "global_tension"));
+        let stability = readings("turn", "stability")
+            .into_iter()
+            .chain(readings("world", "domestic_stability"));
+        let fresh = RunStats::default();
+        let peak = tension.fold(fresh.peak_tension, f64::max);
+        let lowest = stability.fold(fresh.lowest_stability, f64::min);
+        let mut table = Table::default();
+        for key in [
+            "intel_spent",
+            "decrypts",
+            "analyses",
+            "traces",
+            "interrogations",
+            "leaks",
+            "contains",
+        ] {
+            table.entries.push((key.to_string(), Value::Int(0)));
+        }
+        table
+            .entries
+            .push(("peak_tension".to_string(), Value::Float(peak)));
+        table
+            .entries
+            .push(("lowest_stability".to_string(), Value::Float(lowest)));
+        doc.sections.push(("stats".to_string(), table));
+    }
+    // Feed tunables added since: older shifts pick up the stock mix, so news
+    // wires and hinting stations start arriving from here.
+    let stock = GeneratorRegistry::default();
+    let mut tunables = Vec::new();
+    if version < 4 {
+        tunables.push(("news_share", stock.news_share));
+        tunables.push(("news_clue_chance", stock.news_clue_chance));
+    }
+    if version < 6 {
+        tunables.push(("station_share", stock.station_share));
+        tunables.push(("station_hint_chance", stock.station_hint_chance));
+    }
+    for (_, table) in doc.sections.iter_mut().filter(|(n, _)| n == "engine") {
+        for &(key, value) in &tunables {
+            if table.get(key).is_none() {
+                table.entries.push((key.to_string(), Value::Float(value)));
+            }
+        }
+    }
+    if version < 5 {
+        // A shift already past the line gets its warning on the next turn.
+        for (_, table) in doc.sections.iter_mut().filter(|(n, _)| n == "engine") {
+            if table.get("defcon1_warned").is_none() {
+                table
+                    .entries
+                    .push(("defcon1_warned".to_string(), Value::Bool(false)));
+            }
+        }
+    }
+    if version < 8 {
+        for (_, table) in doc.sections.iter_mut().filter(|(n, _)| n == "engine") {
+            if table.get("history_cap").is_none() {
+                table
+                    .entries
+                    .push(("history_cap".to_string(), Value::Int(HISTORY_CAP as i64)));
+            }
+        }
+    }
+}
+
+fn section<'a>(doc: &'a toml::TomlDocument, name: &str) -> Result<&'a Table, String> {
+    doc.array(name)
+        .first()
+        .copied()
+        .ok_or_else(|| format!("missing [{}] section", name))
+}
+
+fn string(t: &Table, key: &str) -> Result<String, String> {
+    t.get(key)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| format!("missing or invalid '{}'", key))
+}
+
+fn strings(t: &Table, key: &str) -> Result<Vec<String>, String> {
+    t.get_all(key)
+        .map(|v| {
+            v.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| format!("'{}' must be a string", key))
+        })
+        .collect()
+}
+
+fn real(t: &Table, key: &str) -> Result<f64, String> {
+    t.get(key)
+        .and_then(|v| v.as_f64())
+        .filter(|f| f.is_finite())
+        .ok_or_else(|| format!("missing or invalid '{}'", key))
+}
+
+fn boolean(t: &Table, key: &str) -> Result<bool, String> {
+    t.get(key)
+        .and_then(|v| v.as_bool())
+        .ok_or_else(|| format!("missing or invalid '{}'", key))
+}
+
+fn uint(t: &Table, key: &str) -> Result<u32, String> {
+    t.get(key)
+        .and_then(|v| v.as_int())
+        .and_then(|i| u32::try_from(i).ok())
+        .ok_or_else(|| format!("missing or invalid '{}'", key))
+}
+
+fn optional_uint(t: &Table, key: &str) -> Result<Option<u32>, String> {
+    match t.get(key) {
+        None => Ok(None),
+        Some(_) => uint(t, key).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Directive;
+
+    #[test]
+    fn round_trips_a_game_in_progress() {
+        let mut engine = GameEngine::with_mole(1);
+        engine.basilisk_override = false;
+        engine.start_turn();
+        engine.resolve_directive(Directive::Consult("vance".to_string()));
+        engine.pending_documents[0].content = "QUOTE \"# NOT A COMMENT\" \\ DONE".to_string();
+
+        let text = render(&engine);
+        let loaded = parse(&text).expect("save parses");
+        assert_eq!(render(&loaded), text);
+        assert!(loaded.state.advisors[1].is_mole);
+        assert_eq!(loaded.history.iter().count(), 1);
+    }
+
+    #[test]
+    fn keeps_the_history_cap() {
+        let mut engine = GameEngine::with_mole(1);
+        engine.basilisk_override = false;
+        engine.history = Archive::with_capacity(2);
+        engine.start_turn();
+        for _ in 0..3 {
+            engine.resolve_directive(Directive::Consult("nobody".to_string()));
+        }
+
+        let loaded = parse(&render(&engine)).expect("save parses");
+        assert_eq!(loaded.history.capacity(), 2);
+        assert_eq!(loaded.history.iter().count(), 2);
+    }
+
+    #[test]
+    fn a_loaded_game_deals_the_same_future() {
+        let mut engine = GameEngine::new();
+        engine.reseed(9);
+        engine.start_turn();
+        let mut loaded = parse(&render(&engine)).expect("save parses");
+        let future = |engine: &mut GameEngine| {
+            engine.resolve_directive(Directive::Investigate);
+            engine.start_turn();
+            let mole = engine.state.advisors.iter().position(|a| a.is_mole);
+            let feed: Vec<String> = engine
+                .pending_documents
+                .iter()
+                .map(|doc| format!("{} {}", doc.id, doc.content))
+                .collect();
+            (mole, feed)
+        };
+        assert_eq!(future(&mut loaded), future(&mut engine));
+    }
+
+    #[test]
+    fn a_loaded_scenario_keeps_its_feed() {
+        let mut engine = GameEngine::new();
+        engine.reseed(4);
+        let cuba = scenario::find("cuba").unwrap();
+        cuba.apply(&mut engine.state, &mut engine.generators);
+        engine.scenario = Some(cuba.name);
+        engine.start_turn();
+
+        let text = render(&engine);
+        let mut loaded = parse(&text).expect("save parses");
+        assert_eq!(loaded.scenario, Some("cuba"));
+        assert_eq!(render(&loaded), text);
+        // The quarantine-line intercepts are still in the mix, so both deal
+        // the same cables from here.
+        let future = |engine: &mut GameEngine| {
+            let mut feed = Vec::new();
+            for _ in 0..6 {
+                engine.resolve_directive(Directive::Contain);
+                engine.start_turn();
+                feed.extend(engine.pending_documents.iter().map(|d| d.content.clone()));
+            }
+            feed
+        };
+        let feed = future(&mut engine);
+        assert!(feed.iter().any(|c| c.contains("QUARANTINE LINE")));
+        assert_eq!(future(&mut loaded), feed);
+    }
+
+    #[test]
+    fn rejects_a_save_without_a_world() {
+        let err = parse("[engine]\nturn_count = 1\n").err().unwrap();
+        assert!(err.contains("[world]"), "{}", err);
+    }
+
+    fn saved_game() -> String {
+        let mut engine = GameEngine::new();
+        engine.start_turn();
+        render(&engine)
+    }
+
+    #[test]
+    fn rejects_a_truncated_or_edited_save() {
+        let text = saved_game();
+        let truncated = &text[..text.len() * 2 / 3];
+        let err = parse(truncated).err().unwrap();
+        assert!(err.contains("checksum"), "{}", err);
+
+        let edited = text.replacen("intel_points = ", "intel_points = 9", 1);
+        let err = parse(&edited).err().unwrap();
+        assert!(err.contains("checksum mismatch"), "{}", err);
+    }
+
+    #[test]
+    fn refuses_a_newer_format_and_migrates_an_unversioned_one() {
+        let text = saved_game();
+        let newer = text.replacen(
+            &format!("version = {}", SAVE_VERSION),
+            &format!("version = {}", SAVE_VERSION + 1),
+            1,
+        );
+        let err = parse(&newer).err().unwrap();
+        assert!(err.contains("newer than this build"), "{}", err);
+
+        // What a save looked like before versioning: no header, and none of
+        // the fields or sections added since.
+        let body = checksummed_body(&text).unwrap();
+        let stats_start = body.find("\n[stats]\n").unwrap();
+        let stats_end = stats_start + 1 + body[stats_start + 1..].find("\n[").unwrap();
+        let body = format!("{}{}", &body[..stats_start], &body[stats_end..]);
+        let legacy: String = body
+            .lines()
+            .filter(|line| {
+                ![
+                    "overclocked_this_turn",
+                    "garble_next_feed",
+                    "hotline_active",
+                    "leads",
+                    "vouched",
+                    "news_share",
+                    "news_clue_chance",
+                    "defcon1_warned",
+                    "station_share",
+                    "station_hint_chance",
+                    "rng_state",
+                    "history_cap",
+                ]
+                .iter()
+                .any(|key| line.starts_with(key))
+            })
+            .map(|line| format!("{}\n", line))
+            .collect();
+        let migrated = parse(&legacy).expect("old save migrates");
+        // Everything but the random stream, which an old save never had.
+        let settled = |text: &str| -> Vec<String> {
+            text.lines()
+                .filter(|line| !line.starts_with("rng_state") && !line.starts_with("checksum"))
+                .map(str::to_string)
+                .collect()
+        };
+        assert_eq!(settled(&render(&migrated)), settled(&text));
+    }
+
+    /// An autosave as the first saving build wrote it, trimmed to one of
+    /// each section.
+    const FIRST_FORMAT_SAVE: &str = r#"# Cold War Terminal save. Editing this file is treason.
+
+[engine]
+turn_count = 4
+intel_points = 2
+max_intel_points = 2
+interruption_active = false
+consult_count = 0
+interrogations_this_turn = 0
+traces_this_turn = 0
+anomaly_chance = 0.15
+archive_cap = 300
+ticker_cap = 4
+basilisk_override = true
+turn_limit = 20
+shift_complete = false
+elapsed_secs = 6
+volume = "normal"
+difficulty = "normal"
+previous_tension = 0.05000000000000002
+mole_masked = false
+suspicion_decay = 5
+mole_creep = 3
+
+[world]
+global_tension = 0.05000000000000002
+internal_secrecy = 0.30000000000000004
+foreign_paranoia = 0.3
+accidental_escalation_risk = 0.0
+domestic_stability = 0.7000000000000001
+secret_weapon_progress = 0.4600000000000001
+red_phone_active = false
+system_corruption = 0.0
+coup_active = false
+coup_attempted = false
+double_agent_turns = 0
+doppelganger_active = false
+doppelganger_resolved = false
+impostor_in_office = false
+
+[[advisor]]
+name = "Gen. Vance"
+role = "general"
+suspicion = 3
+is_mole = true
+is_double_agent = false
+
+[[advisor]]
+name = "Director K."
+role = "director"
+suspicion = 0
+is_mole = false
+is_double_agent = false
+
+[[advisor]]
+name = "Amb. Sterling"
+role = "ambassador"
+suspicion = 0
+is_mole = false
+is_double_agent = false
+
+[[pending]]
+id = "DOC-53A4"
+doc_type = "ANONYMOUS LEAK"
+clearance_level = "UNVERIFIED"
+timestamp = "1982-11-17 22:31Z"
+content = "RUMOR MILL: \"ENCRYPTED BROADCASTS INTERRUPTING CARTOON HOUR.\""
+is_encrypted = true
+reliability = 0.886594200675394
+priority = false
+
+[[archived]]
+id = "DOC-432E"
+doc_type = "INTERNAL MEMO"
+clearance_level = "TOP SECRET"
+timestamp = "1987-12-11 11:21Z"
+content = "RE: PROJECT BASILISK. ENERGY CONSUMPTION EXCEEDING GRID CAPACITIES. COVER STORY 'INDUSTRIAL ACCIDENT' PREPARED."
+is_encrypted = true
+reliability = 0.30812699857985193
+priority = false
+
+[[leak]]
+text = "RUMOR MILL: \"STRANGE LIGHTS SEEN OVER NEVADA TEST SITE.\""
+
+[[turn]]
+turn = 1
+directive = "INVESTIGATE"
+feedback = "Internal audit reveals deeper layers of the Project."
+feedback = "Protocols tightened. We are watching the watchers."
+
+[[turn]]
+turn = 2
+directive = "CONTAIN"
+feedback = "Tension reduced. Military leadership questions your resolve."
+"#;
+
+    #[test]
+    fn loads_a_save_from_the_first_format() {
+        let engine = parse(FIRST_FORMAT_SAVE).expect("first-format save migrates");
+        assert_eq!(engine.turn_count, 4);
+        assert!(engine.state.advisors[0].is_mole);
+        assert_eq!(engine.state.enemy_posture, EnemyPosture::Probing);
+        assert_eq!(engine.standdown_penalty_turns, 0);
+        assert_eq!(engine.pending_documents[0].decrypt_progress, 0);
+        assert_eq!(engine.archive.iter().next().unwrap().decrypt_progress, 0);
+        let history: Vec<_> = engine.history.iter().collect();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].tension, engine.state.global_tension);
+
+        // And it comes back out in the current format.
+        let again = parse(&render(&engine)).expect("re-saved game loads");
+        assert_eq!(again.turn_count, 4);
+    }
+}
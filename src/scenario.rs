@@ -0,0 +1,107 @@
+//! Hand-authored starting situations layered over the procedural engine.
+
+use crate::document::{DocumentType, GeneratorRegistry};
+use crate::rng::Rng;
+use crate::state::WorldState;
+
+/// A named opening: starting metrics plus any bias to the cable feed.
+pub struct Scenario {
+    pub name: &'static str,
+    pub description: &'static str,
+    setup: fn(&mut WorldState),
+    feed: fn(&mut GeneratorRegistry),
+}
+
+impl Scenario {
+    /// Overrides the default world and feed for this scenario.
+    pub fn apply(&self, state: &mut WorldState, generators: &mut GeneratorRegistry) {
+        (self.setup)(state);
+        self.apply_feed(generators);
+    }
+
+    /// Only the feed half, for a loaded shift: the world has moved on since
+    /// the opening, but the scenario's own cables keep coming.
+    pub fn apply_feed(&self, generators: &mut GeneratorRegistry) {
+        (self.feed)(generators);
+    }
+}
+
+pub const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "cuba",
+        description: "Missiles ninety miles offshore. The quarantine line holds, for now.",
+        setup: setup_cuba,
+        feed: feed_cuba,
+    },
+    Scenario {
+        name: "basilisk",
+        description: "The Project is further along than anyone admits. The wires hum at night.",
+        setup: setup_basilisk,
+        feed: feed_basilisk,
+    },
+    Scenario {
+        name: "homefront",
+        description: "Strikes, riots and a cabinet nobody trusts. The enemy is the least of it.",
+        setup: setup_homefront,
+        feed: |_| {},
+    },
+];
+
+/// Looks up a scenario by name, ignoring case.
+pub fn find(name: &str) -> Option<&'static Scenario> {
+    SCENARIOS.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+fn setup_cuba(state: &mut WorldState) {
+    state.global_tension = 0.55;
+    state.foreign_paranoia = 0.6;
+    state.accidental_escalation_risk = 0.2;
+}
+
+fn feed_cuba(generators: &mut GeneratorRegistry) {
+    generators.register(
+        DocumentType::ForeignIntercept,
+        |_: &WorldState, rng: &mut dyn Rng, _: f64| {
+            let ships = ["POLTAVA", "KIMOVSK", "YURI GAGARIN", "ALEKSANDROVSK"];
+            let ship = *rng.choose(&ships);
+            format!(
+                "DECRYPTED: \"...FREIGHTER {} IS NOT TO STOP AT THE QUARANTINE LINE...\"",
+                ship
+            )
+        },
+    );
+}
+
+fn setup_basilisk(state: &mut WorldState) {
+    state.secret_weapon_progress = 0.45;
+    state.system_corruption = 0.1;
+}
+
+fn feed_basilisk(generators: &mut GeneratorRegistry) {
+    generators.anomaly_chance = 0.4;
+}
+
+fn setup_homefront(state: &mut WorldState) {
+    state.domestic_stability = 0.35;
+    state.internal_secrecy = 0.3;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scenarios_are_found_by_name() {
+        assert!(find("CUBA").is_some());
+        assert!(find("atlantis").is_none());
+    }
+
+    #[test]
+    fn basilisk_scenario_starts_the_project_early() {
+        let mut state = WorldState::new();
+        let mut generators = GeneratorRegistry::default();
+        find("basilisk").unwrap().apply(&mut state, &mut generators);
+        assert!(state.secret_weapon_progress > WorldState::new().secret_weapon_progress);
+        assert!(generators.anomaly_chance > GeneratorRegistry::default().anomaly_chance);
+    }
+}
@@ -0,0 +1,145 @@
+//! Options the player can change mid-game with the `settings` command.
+
+use crate::config::Config;
+use crate::input::InputManager;
+use crate::ui;
+use std::io::{self, Write};
+
+/// Feed speeds offered in the menu, per-character delay in milliseconds.
+const FEED_SPEEDS: &[(&str, u64)] = &[("NORMAL", 8), ("FAST", 2), ("INSTANT", 0), ("SLOW", 20)];
+
+/// Runtime-adjustable presentation and safety options.
+pub struct Settings {
+    /// Per-character delay for the incoming cable feed.
+    pub feed_speed_ms: u64,
+    /// Countdown (and hotkey confirmation) before escalate / stand-down.
+    pub confirm_dangerous: bool,
+    /// Single-key directive selection.
+    pub hotkeys: bool,
+    /// Ring the terminal bell when a crisis opens.
+    pub bell: bool,
+}
+
+/// Per-character delay for a named feed speed (`normal`, `fast`...).
+pub fn feed_speed(name: &str) -> Option<u64> {
+    FEED_SPEEDS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, ms)| *ms)
+}
+
+/// The speed a new session starts at.
+pub const DEFAULT_FEED_SPEED_MS: u64 = FEED_SPEEDS[0].1;
+
+impl Settings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            feed_speed_ms: config.feed_speed_ms,
+            confirm_dangerous: true,
+            hotkeys: config.hotkeys,
+            bell: config.bell,
+        }
+    }
+
+    fn feed_speed_name(&self) -> &'static str {
+        FEED_SPEEDS
+            .iter()
+            .find(|(_, ms)| *ms == self.feed_speed_ms)
+            .map(|(name, _)| *name)
+            .unwrap_or("CUSTOM")
+    }
+
+    fn next_feed_speed(&mut self) {
+        let idx = FEED_SPEEDS
+            .iter()
+            .position(|(_, ms)| *ms == self.feed_speed_ms)
+            .map_or(0, |i| (i + 1) % FEED_SPEEDS.len());
+        self.feed_speed_ms = FEED_SPEEDS[idx].1;
+    }
+
+    /// Interactive submenu. Each choice toggles or cycles one option and
+    /// takes effect straight away.
+    pub fn run_menu(&mut self, input_mgr: &InputManager) {
+        loop {
+            let on_off = |b: bool| if b { "ON" } else { "OFF" };
+            println!("\n{}SETTINGS:{}", ui::palette().amber, ui::palette().reset);
+            println!(
+                "  [1] FEED SPEED ............ {} ({} ms/char)",
+                self.feed_speed_name(),
+                self.feed_speed_ms
+            );
+            println!(
+                "  [2] GLYPHS ................ {}",
+                if ui::is_ascii() { "ASCII" } else { "UNICODE" }
+            );
+            println!(
+                "  [3] CONFIRM DANGEROUS ..... {}",
+                on_off(self.confirm_dangerous)
+            );
+            println!("  [4] HOTKEYS ............... {}", on_off(self.hotkeys));
+            println!("  [5] CRISIS BELL ........... {}", on_off(self.bell));
+            println!(
+                "  [6] COLOR ................. {}",
+                on_off(!ui::palette().is_plain())
+            );
+            println!("  [0] BACK");
+            print!("{}settings> {}", ui::palette().teal, ui::palette().reset);
+            io::stdout().flush().unwrap();
+
+            let choice = input_mgr.read_line();
+            match self.choose(choice.trim()) {
+                Some(true) => input_mgr.set_hotkeys(self.hotkeys),
+                Some(false) => return,
+                None => println!("Unknown setting: '{}'.", choice.trim()),
+            }
+        }
+    }
+
+    /// Applies one menu choice. Some(true) if an option changed, Some(false)
+    /// to leave the menu, None for a choice that isn't on it.
+    fn choose(&mut self, choice: &str) -> Option<bool> {
+        match choice {
+            "1" => self.next_feed_speed(),
+            "2" => ui::set_ascii(!ui::is_ascii()),
+            "3" => self.confirm_dangerous = !self.confirm_dangerous,
+            "4" => self.hotkeys = !self.hotkeys,
+            "5" => self.bell = !self.bell,
+            "6" => ui::set_color(ui::palette().is_plain()),
+            "" | "0" | "back" | "q" => return Some(false),
+            _ => return None,
+        }
+        Some(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_choice_toggles_its_own_option() {
+        let mut settings = Settings::from_config(&Config::default());
+        let before = (settings.confirm_dangerous, settings.hotkeys, settings.bell);
+
+        assert_eq!(settings.choose("3"), Some(true));
+        assert_eq!(settings.choose("5"), Some(true));
+        assert_eq!(
+            (settings.confirm_dangerous, settings.hotkeys, settings.bell),
+            (!before.0, before.1, !before.2)
+        );
+        assert_eq!(settings.choose("4"), Some(true));
+        assert_eq!(settings.hotkeys, !before.1);
+
+        let speed = settings.feed_speed_ms;
+        settings.choose("1");
+        assert_ne!(settings.feed_speed_ms, speed);
+        for _ in 1..FEED_SPEEDS.len() {
+            settings.choose("1");
+        }
+        assert_eq!(settings.feed_speed_ms, speed);
+
+        assert_eq!(settings.choose("0"), Some(false));
+        assert_eq!(settings.choose(""), Some(false));
+        assert_eq!(settings.choose("9"), None);
+    }
+}
@@ -1,3 +1,102 @@
+use crate::lang;
+
+/// Domestic stability below which the General Staff moves against the government.
+pub const COUP_THRESHOLD: f64 = 0.15;
+
+/// Tension must be below this when the shift ends to claim victory.
+pub const VICTORY_MAX_TENSION: f64 = 0.5;
+
+/// Secret weapon progress must be below this ("contained") to claim victory.
+pub const VICTORY_MAX_WEAPON: f64 = 0.7;
+
+/// Tension at which the world sits at DEFCON 1: one more push likely means launch.
+pub const DEFCON1_TENSION: f64 = 0.9;
+
+/// Turns a flipped mole keeps reporting before their cover starts to fray.
+pub const DOUBLE_AGENT_TENURE: u32 = 4;
+
+/// Turns between decrypting the doppelganger cable and the crisis it sets off.
+pub const DOPPELGANGER_FUSE: u32 = 3;
+
+/// How a shift ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ending {
+    /// Tension reached 1.0.
+    NuclearWar,
+    /// Stability collapsed after the coup crisis was spent.
+    Coup,
+    /// The secret weapon reached full progress.
+    Basilisk,
+    /// The President's double was left in office.
+    Impostor,
+    /// Survived the shift with the world calm, the mole gone and the Project contained.
+    Victory,
+    /// Survived the shift, but without the win conditions.
+    SimulationEnd,
+}
+
+impl Ending {
+    /// Message-catalog prefix for this ending's text.
+    fn message_key(&self) -> &'static str {
+        match self {
+            Ending::NuclearWar => "ending.nuclear_war",
+            Ending::Coup => "ending.coup",
+            Ending::Basilisk => "ending.basilisk",
+            Ending::Impostor => "ending.impostor",
+            Ending::Victory => "ending.victory",
+            Ending::SimulationEnd => "ending.simulation_end",
+        }
+    }
+
+    /// Stable lowercase name for machine-readable output, e.g. `nuclear_war`.
+    pub fn id(&self) -> &'static str {
+        self.message_key().trim_start_matches("ending.")
+    }
+
+    pub fn title(&self) -> &'static str {
+        lang::text(&format!("{}.title", self.message_key()))
+    }
+
+    pub fn summary(&self) -> &'static str {
+        lang::text(&format!("{}.summary", self.message_key()))
+    }
+
+    pub fn is_victory(&self) -> bool {
+        matches!(self, Ending::Victory)
+    }
+}
+
+/// The other side's standing intent, shaped by how we have been acting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnemyPosture {
+    /// Reads our moves as threats; diplomacy gets harder.
+    Aggressive,
+    /// Testing our resolve without committing.
+    Probing,
+    /// Looking for a way out; diplomacy lands more easily.
+    Cautious,
+}
+
+impl EnemyPosture {
+    pub fn parse(name: &str) -> Option<EnemyPosture> {
+        match name.to_lowercase().as_str() {
+            "aggressive" => Some(EnemyPosture::Aggressive),
+            "probing" => Some(EnemyPosture::Probing),
+            "cautious" => Some(EnemyPosture::Cautious),
+            _ => None,
+        }
+    }
+
+    /// Highest foreign paranoia at which a Contain still lands.
+    pub fn contain_threshold(&self) -> f64 {
+        match self {
+            EnemyPosture::Aggressive => 0.45,
+            EnemyPosture::Probing => 0.6,
+            EnemyPosture::Cautious => 0.75,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AdvisorRole {
     General,
@@ -5,12 +104,29 @@ pub enum AdvisorRole {
     Ambassador,
 }
 
+impl AdvisorRole {
+    /// Parses a role name as written in roster files ("general", "director", ...).
+    pub fn parse(s: &str) -> Option<AdvisorRole> {
+        match s.trim().to_lowercase().as_str() {
+            "general" => Some(AdvisorRole::General),
+            "director" => Some(AdvisorRole::Director),
+            "ambassador" => Some(AdvisorRole::Ambassador),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Advisor {
     pub name: String,
     pub role: AdvisorRole,
     pub suspicion: u32, // 0 to 100
     pub is_mole: bool,
+    /// A mole turned during the Red Phone reveal, now feeding us intel.
+    pub is_double_agent: bool,
+    /// Pieces of indirect evidence against them: contradicted advice,
+    /// flagged memos.
+    pub leads: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -37,37 +153,66 @@ pub struct WorldState {
     pub advisors: Vec<Advisor>,
     /// Trigger for the "Red Phone" crisis event.
     pub red_phone_active: bool,
+    /// Trigger for the enemy's hotline call offering to de-escalate.
+    pub hotline_active: bool,
     /// 0.0 (Pure) to 1.0 (Corrupted) - affects system autonomy.
     pub system_corruption: f64,
+    /// Trigger for the "Coup Attempt" crisis event.
+    pub coup_active: bool,
+    /// The coup crisis only happens once; afterwards a collapse is final.
+    pub coup_attempted: bool,
+    /// Remaining turns of reporting from a double agent (0 = cover fraying).
+    pub double_agent_turns: u32,
+    /// Turns until the doppelganger crisis; set by decrypting the EYES ONLY cable.
+    pub doppelganger_countdown: Option<u32>,
+    /// Trigger for the "Doppelganger" crisis event.
+    pub doppelganger_active: bool,
+    /// The doppelganger storyline only plays out once.
+    pub doppelganger_resolved: bool,
+    /// The impostor kept the presidency.
+    pub impostor_in_office: bool,
+    /// How the enemy reads our recent directives.
+    pub enemy_posture: EnemyPosture,
+}
+
+/// The stock cabinet used when no roster file is supplied.
+fn default_advisors() -> Vec<Advisor> {
+    vec![
+        Advisor {
+            name: "Gen. Vance".to_string(),
+            role: AdvisorRole::General,
+            suspicion: 0,
+            is_mole: false,
+            is_double_agent: false,
+            leads: 0,
+        },
+        Advisor {
+            name: "Director K.".to_string(),
+            role: AdvisorRole::Director,
+            suspicion: 0,
+            is_mole: false,
+            is_double_agent: false,
+            leads: 0,
+        },
+        Advisor {
+            name: "Amb. Sterling".to_string(),
+            role: AdvisorRole::Ambassador,
+            suspicion: 0,
+            is_mole: false,
+            is_double_agent: false,
+            leads: 0,
+        },
+    ]
 }
 
 impl WorldState {
     pub fn new() -> Self {
-        // Initialize Advisors
-        let advisors = vec![
-            Advisor {
-                name: "Gen. Vance".to_string(),
-                role: AdvisorRole::General,
-                suspicion: 0,
-                is_mole: false,
-            },
-            Advisor {
-                name: "Director K.".to_string(),
-                role: AdvisorRole::Director,
-                suspicion: 0,
-                is_mole: false,
-            },
-            Advisor {
-                name: "Amb. Sterling".to_string(),
-                role: AdvisorRole::Ambassador,
-                suspicion: 0,
-                is_mole: false,
-            },
-        ];
-
-        // Randomly assign one as the mole (logic will happen in game init since rng is there,
-        // but for now we default to false and let GameEngine set it)
+        Self::with_advisors(default_advisors())
+    }
 
+    /// Builds the opening state around a custom roster.
+    /// The mole is assigned later by GameEngine, which owns the rng.
+    pub fn with_advisors(advisors: Vec<Advisor>) -> Self {
         Self {
             global_tension: 0.2,
             internal_secrecy: 0.5,
@@ -77,11 +222,106 @@ impl WorldState {
             secret_weapon_progress: 0.1,
             advisors,
             red_phone_active: false,
+            hotline_active: false,
             system_corruption: 0.0,
+            coup_active: false,
+            coup_attempted: false,
+            double_agent_turns: 0,
+            doppelganger_countdown: None,
+            doppelganger_active: false,
+            doppelganger_resolved: false,
+            impostor_in_office: false,
+            enemy_posture: EnemyPosture::Probing,
         }
     }
 
     pub fn is_terminal(&self) -> bool {
-        self.global_tension >= 1.0 || self.domestic_stability <= 0.0
+        self.collapse().is_some()
+    }
+
+    /// The catastrophic ending the world has reached, if any.
+    pub fn collapse(&self) -> Option<Ending> {
+        if self.global_tension >= 1.0 {
+            Some(Ending::NuclearWar)
+        } else if self.domestic_stability <= 0.0 && !self.coup_active {
+            // A pending coup attempt gives the player one last chance before collapse.
+            Some(Ending::Coup)
+        } else if self.secret_weapon_progress >= 1.0 {
+            Some(Ending::Basilisk)
+        } else if self.impostor_in_office {
+            Some(Ending::Impostor)
+        } else {
+            None
+        }
+    }
+
+    /// Win predicate checked when the shift runs out: calm, mole-free and the Project contained.
+    pub fn is_secure(&self) -> bool {
+        self.global_tension < VICTORY_MAX_TENSION
+            && self.secret_weapon_progress < VICTORY_MAX_WEAPON
+            && !self.advisors.iter().any(|a| a.is_mole)
+    }
+
+    /// Narrative band for `global_tension`.
+    pub fn tension_band(&self) -> &'static str {
+        tension_band(self.global_tension)
+    }
+
+    /// Narrative band for `domestic_stability`.
+    pub fn stability_band(&self) -> &'static str {
+        stability_band(self.domestic_stability)
+    }
+
+    /// Narrative band for `secret_weapon_progress`.
+    pub fn weapon_band(&self) -> &'static str {
+        match self.secret_weapon_progress {
+            w if w >= 0.9 => "AWAKENING",
+            w if w >= 0.7 => "UNSTABLE",
+            w if w >= 0.5 => "ACTIVE",
+            w if w >= 0.3 => "STIRRING",
+            _ => "DORMANT",
+        }
+    }
+
+    /// Pulls the world metrics back into 0.0 to 1.0 once a turn or a crisis
+    /// has finished moving them. System corruption creeps up afterwards and
+    /// is clamped on its own.
+    pub fn clamp_metrics(&mut self) {
+        self.global_tension = self.global_tension.clamp(0.0, 1.0);
+        self.internal_secrecy = self.internal_secrecy.clamp(0.0, 1.0);
+        self.foreign_paranoia = self.foreign_paranoia.clamp(0.0, 1.0);
+        self.accidental_escalation_risk = self.accidental_escalation_risk.clamp(0.0, 1.0);
+        self.domestic_stability = self.domestic_stability.clamp(0.0, 1.0);
+        self.secret_weapon_progress = self.secret_weapon_progress.clamp(0.0, 1.0);
+    }
+
+    /// Arms the coup crisis the first time stability slides below the threshold.
+    pub fn check_coup_trigger(&mut self) {
+        if !self.coup_attempted && self.domestic_stability < COUP_THRESHOLD {
+            self.coup_attempted = true;
+            self.coup_active = true;
+        }
+    }
+}
+
+/// Narrative band for a tension reading.
+pub fn tension_band(tension: f64) -> &'static str {
+    match tension {
+        t if t >= 0.8 => "CRITICAL",
+        t if t >= 0.6 => "SEVERE",
+        t if t >= 0.4 => "ELEVATED",
+        t if t >= 0.2 => "GUARDED",
+        _ => "CALM",
+    }
+}
+
+/// Narrative band for a stability reading.
+pub fn stability_band(stability: f64) -> &'static str {
+    match stability {
+        s if s >= 0.7 => "UNIFIED",
+        s if s >= 0.5 => "STEADY",
+        s if s >= 0.3 => "RESTLESS",
+        s if s >= COUP_THRESHOLD => "FRACTURING",
+        _ => "COLLAPSING",
     }
 }
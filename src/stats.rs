@@ -0,0 +1,109 @@
+//! Running tallies for the `stats` command: how the shift has been played so
+//! far, as opposed to where the world stands (`sitrep`) or the final score.
+
+use crate::state::{self, WorldState};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunStats {
+    /// Intel points spent, net of refunds.
+    pub intel_spent: u32,
+    /// Decrypt attempts that cost intel, multi-stage ciphers once per stage.
+    pub decrypts: u32,
+    pub analyses: u32,
+    pub traces: u32,
+    pub interrogations: u32,
+    pub leaks: u32,
+    pub contains: u32,
+    /// Highest tension seen at the start of a turn or after a directive.
+    pub peak_tension: f64,
+    /// Lowest stability seen at the same moments.
+    pub lowest_stability: f64,
+}
+
+impl Default for RunStats {
+    fn default() -> Self {
+        Self {
+            intel_spent: 0,
+            decrypts: 0,
+            analyses: 0,
+            traces: 0,
+            interrogations: 0,
+            leaks: 0,
+            contains: 0,
+            peak_tension: 0.0,
+            lowest_stability: 1.0,
+        }
+    }
+}
+
+impl RunStats {
+    /// Folds the world's current readings into the peak and the low.
+    pub fn observe(&mut self, state: &WorldState) {
+        self.peak_tension = self.peak_tension.max(state.global_tension);
+        self.lowest_stability = self.lowest_stability.min(state.domestic_stability);
+    }
+
+    /// The `stats` readout. `since_crisis` is None before the first crisis.
+    /// With `hide_numbers` (fog of war) the extremes read as bands.
+    pub fn report(&self, day: u32, since_crisis: Option<u32>, hide_numbers: bool) -> Vec<String> {
+        let since_crisis = match since_crisis {
+            Some(turns) => turns.to_string(),
+            None => "NONE YET".to_string(),
+        };
+        let (peak, lowest) = if hide_numbers {
+            (
+                state::tension_band(self.peak_tension).to_string(),
+                state::stability_band(self.lowest_stability).to_string(),
+            )
+        } else {
+            (
+                format!("{:.2}", self.peak_tension),
+                format!("{:.2}", self.lowest_stability),
+            )
+        };
+        vec![
+            format!("RUN STATISTICS // DAY {:03}", day),
+            format!("  INTEL SPENT       : {}", self.intel_spent),
+            format!(
+                "  DECRYPTS {} // ANALYSES {} // TRACES {} // INTERROGATIONS {}",
+                self.decrypts, self.analyses, self.traces, self.interrogations
+            ),
+            format!("  LEAKED {} // CONTAINED {}", self.leaks, self.contains),
+            format!("  PEAK TENSION      : {}", peak),
+            format!("  LOWEST STABILITY  : {}", lowest),
+            format!("  TURNS SINCE CRISIS: {}", since_crisis),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observe_keeps_the_extremes() {
+        let mut stats = RunStats::default();
+        let mut state = WorldState::new();
+        stats.observe(&state);
+        state.global_tension = 0.1;
+        state.domestic_stability = 0.9;
+        stats.observe(&state);
+        assert_eq!(stats.peak_tension, 0.2);
+        assert_eq!(stats.lowest_stability, 0.8);
+    }
+
+    #[test]
+    fn fog_of_war_reports_the_extremes_as_bands() {
+        let stats = RunStats {
+            peak_tension: 0.65,
+            lowest_stability: 0.35,
+            ..RunStats::default()
+        };
+        let shown = stats.report(3, None, false);
+        assert!(shown.contains(&"  PEAK TENSION      : 0.65".to_string()));
+        let hidden = stats.report(3, None, true);
+        assert!(hidden.contains(&"  PEAK TENSION      : SEVERE".to_string()));
+        assert!(hidden.contains(&"  LOWEST STABILITY  : RESTLESS".to_string()));
+        assert!(!hidden.iter().any(|line| line.contains("0.")));
+    }
+}
@@ -0,0 +1,84 @@
+//! Plain-text record of a session for sharing or diffing between runs.
+
+use crate::game::GameEngine;
+use std::fs;
+use std::io;
+
+/// Default file written by `export-transcript` without an argument.
+pub const DEFAULT_TRANSCRIPT_PATH: &str = "coldwar-transcript.txt";
+
+/// Formats the session history. No wall-clock data is included so two runs
+/// that play out identically produce identical files.
+pub fn render(engine: &GameEngine) -> String {
+    let mut out = String::from("COLD WAR TERMINAL // SESSION TRANSCRIPT\n");
+
+    let mut last_turn = None;
+    for record in engine.history.iter() {
+        if last_turn != Some(record.turn) {
+            out.push_str(&format!("\n=== DAY {:03} ===\n", record.turn));
+            last_turn = Some(record.turn);
+        }
+        out.push_str(&format!("> {}\n", record.directive));
+        for line in &record.feedback {
+            out.push_str(&format!("  {}\n", strip_ansi(line)));
+        }
+    }
+
+    out.push('\n');
+    match engine.ending() {
+        Some(ending) => {
+            out.push_str(&format!("ENDING: {}\n", ending.title()));
+            out.push_str(&format!("{}\n", ending.summary()));
+        }
+        None => out.push_str("SESSION IN PROGRESS\n"),
+    }
+    out.push_str(&format!("DAYS ON SHIFT: {}\n", engine.turn_count));
+    out
+}
+
+/// Writes the transcript to `path`.
+pub fn export(engine: &GameEngine, path: &str) -> io::Result<()> {
+    fs::write(path, render(engine))
+}
+
+/// Removes ANSI escape sequences (`ESC [ ... letter`).
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Directive;
+
+    #[test]
+    fn strip_ansi_removes_color_codes() {
+        assert_eq!(strip_ansi("\x1b[38;5;196mALERT\x1b[0m ok"), "ALERT ok");
+    }
+
+    #[test]
+    fn transcript_lists_directives_by_day() {
+        let mut engine = GameEngine::new();
+        engine.start_turn();
+        engine.resolve_directive(Directive::Contain);
+
+        let text = render(&engine);
+        assert!(text.contains("=== DAY 001 ===\n> CONTAIN\n"));
+        assert!(!text.contains('\x1b'));
+    }
+}
@@ -1,63 +1,652 @@
+use crate::input::{InputManager, Interrupt};
+use crate::rng::Rng;
+#[cfg(test)]
 use crate::rng::SimpleRng;
+use crate::state::Advisor;
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::Duration;
 
 // --- COLORS (Extended ANSI) ---
-pub const TEAL: &str = "\x1b[38;5;14m";
-pub const AMBER: &str = "\x1b[38;5;214m";
-pub const ORANGE: &str = "\x1b[38;5;202m";
-pub const RED_ALERT: &str = "\x1b[38;5;196m";
-pub const GREY_DIM: &str = "\x1b[38;5;240m";
-pub const WHITE_BOLD: &str = "\x1b[1;37m";
-pub const RESET: &str = "\x1b[0m";
+/// Every escape code the renderer writes: the colors, plus screen and cursor
+/// control. The plain palette has none of them, so output piped to a file
+/// or shown with `--no-color` is clean text.
+pub struct Palette {
+    pub teal: &'static str,
+    pub amber: &'static str,
+    pub orange: &'static str,
+    pub red_alert: &'static str,
+    pub grey_dim: &'static str,
+    pub white_bold: &'static str,
+    pub reset: &'static str,
+    /// Clears the screen and moves the cursor to the top-left.
+    pub clear: &'static str,
+    pub hide_cursor: &'static str,
+    pub show_cursor: &'static str,
+    /// Remembers the cursor position, for redrawing part of a line in place.
+    pub save_cursor: &'static str,
+    pub restore_cursor: &'static str,
+}
+
+const ANSI_PALETTE: Palette = Palette {
+    teal: "\x1b[38;5;14m",
+    amber: "\x1b[38;5;214m",
+    orange: "\x1b[38;5;202m",
+    red_alert: "\x1b[38;5;196m",
+    grey_dim: "\x1b[38;5;240m",
+    white_bold: "\x1b[1;37m",
+    reset: "\x1b[0m",
+    clear: "\x1b[2J\x1b[1;1H",
+    hide_cursor: "\x1b[?25l",
+    show_cursor: "\x1b[?25h",
+    save_cursor: "\x1b7",
+    restore_cursor: "\x1b8",
+};
+
+const PLAIN_PALETTE: Palette = Palette {
+    teal: "",
+    amber: "",
+    orange: "",
+    red_alert: "",
+    grey_dim: "",
+    white_bold: "",
+    reset: "",
+    clear: "",
+    hide_cursor: "",
+    show_cursor: "",
+    save_cursor: "",
+    restore_cursor: "",
+};
+
+impl Palette {
+    /// No escape codes at all. Animations that redraw a line in place show
+    /// only their final frame, so a file does not fill up with them.
+    pub fn is_plain(&self) -> bool {
+        self.reset.is_empty()
+    }
+}
+
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Turns every escape code on or off for the rest of the session.
+pub fn set_color(enabled: bool) {
+    NO_COLOR.store(!enabled, Ordering::Relaxed);
+}
+
+/// The palette for the current session.
+pub fn palette() -> &'static Palette {
+    if NO_COLOR.load(Ordering::Relaxed) {
+        &PLAIN_PALETTE
+    } else {
+        &ANSI_PALETTE
+    }
+}
 
 // --- SYMBOLS ---
-const H_LINE: char = '─';
-const V_LINE: char = '│';
-const TL_CORNER: char = '┌';
-const TR_CORNER: char = '┐';
-const BL_CORNER: char = '└';
-const BR_CORNER: char = '┘';
-const BLOCK_STATUS_1: char = '█';
+/// Every box-drawing and block glyph the renderer uses, so legacy terminals
+/// can swap the whole set for plain ASCII in one place.
+pub struct Glyphs {
+    pub h_line: char,
+    pub v_line: char,
+    pub tl_corner: char,
+    pub tr_corner: char,
+    pub bl_corner: char,
+    pub br_corner: char,
+    pub block_full: char,
+    pub block_glitch: char,
+    pub block_empty: char,
+    pub rising: char,
+    pub falling: char,
+    pub steady: char,
+    /// Sparkline levels, lowest first.
+    pub spark: [char; 8],
+}
+
+const UNICODE_GLYPHS: Glyphs = Glyphs {
+    h_line: '─',
+    v_line: '│',
+    tl_corner: '┌',
+    tr_corner: '┐',
+    bl_corner: '└',
+    br_corner: '┘',
+    block_full: '█',
+    block_glitch: '▒',
+    block_empty: '░',
+    rising: '↑',
+    falling: '↓',
+    steady: '→',
+    spark: ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+};
+
+const ASCII_GLYPHS: Glyphs = Glyphs {
+    h_line: '-',
+    v_line: '|',
+    tl_corner: '+',
+    tr_corner: '+',
+    bl_corner: '+',
+    br_corner: '+',
+    block_full: '#',
+    block_glitch: ':',
+    block_empty: '.',
+    rising: '^',
+    falling: 'v',
+    steady: '=',
+    spark: ['_', '.', ',', '-', '=', '+', '*', '#'],
+};
+
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Switches all rendering to the ASCII glyph set.
+pub fn set_ascii(enabled: bool) {
+    ASCII_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the ASCII glyph set is active.
+pub fn is_ascii() -> bool {
+    ASCII_MODE.load(Ordering::Relaxed)
+}
+
+static FAST_MODE: AtomicBool = AtomicBool::new(false);
+
+/// No animation (`--fast`): text prints at once and dramatic pauses are
+/// skipped for the rest of the session.
+pub fn set_fast(enabled: bool) {
+    FAST_MODE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_fast() -> bool {
+    FAST_MODE.load(Ordering::Relaxed)
+}
+
+/// A dramatic beat of `ms` milliseconds; nothing in fast mode.
+pub fn pause(ms: u64) {
+    if !is_fast() {
+        thread::sleep(Duration::from_millis(ms));
+    }
+}
+
+static HIDE_NUMBERS: AtomicBool = AtomicBool::new(false);
+
+/// Fog of war (`--no-numbers`): metrics render as bands and bar fill only.
+pub fn set_hide_numbers(enabled: bool) {
+    HIDE_NUMBERS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn numbers_hidden() -> bool {
+    HIDE_NUMBERS.load(Ordering::Relaxed)
+}
+
+/// The glyph set for the current session.
+pub fn glyphs() -> &'static Glyphs {
+    if is_ascii() {
+        &ASCII_GLYPHS
+    } else {
+        &UNICODE_GLYPHS
+    }
+}
 
-const BLOCK_STATUS_2: char = '▒';
-const BLOCK_STATUS_3: char = '░';
+/// Guesses whether the terminal can render UTF-8 from the locale variables.
+pub fn locale_supports_utf8() -> bool {
+    for var in ["LC_ALL", "LC_CTYPE", "LANG"] {
+        if let Ok(value) = std::env::var(var) {
+            if !value.is_empty() {
+                let value = value.to_lowercase();
+                return value.contains("utf-8") || value.contains("utf8");
+            }
+        }
+    }
+    // No locale at all: modern Unix terminals are UTF-8, the classic Windows console is not.
+    !cfg!(windows)
+}
+
+/// Best-effort terminal width: the terminal's own size, then `COLUMNS`,
+/// then 80. Cheap enough to ask before every read.
+pub fn terminal_width() -> usize {
+    if let Some(cols) = tty_columns() {
+        return cols;
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse::<usize>().ok())
+        .unwrap_or(80)
+}
+
+/// Asks the controlling terminal for its width, without forking `stty`.
+#[cfg(unix)]
+fn tty_columns() -> Option<usize> {
+    use std::os::raw::{c_int, c_ulong};
+    use std::os::unix::io::AsRawFd;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct WinSize {
+        rows: u16,
+        cols: u16,
+        x_pixels: u16,
+        y_pixels: u16,
+    }
+    extern "C" {
+        fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const TIOCGWINSZ: c_ulong = 0x5413;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const TIOCGWINSZ: c_ulong = 0x4008_7468;
+
+    let tty = std::fs::File::open("/dev/tty").ok()?;
+    let mut size = WinSize::default();
+    // SAFETY: TIOCGWINSZ only writes a `struct winsize`, which `WinSize`
+    // mirrors, and the descriptor stays open for the call.
+    let status = unsafe { ioctl(tty.as_raw_fd(), TIOCGWINSZ, &mut size as *mut WinSize) };
+    (status == 0 && size.cols > 0).then_some(size.cols as usize)
+}
+
+#[cfg(not(unix))]
+fn tty_columns() -> Option<usize> {
+    None
+}
+
+/// Scrolls `text` in from the right edge of a `width`-column strip over
+/// roughly `duration_ms`, leaving the start of the text on screen.
+pub fn marquee(text: &str, width: usize, duration_ms: u64, color: &str) {
+    let _cursor = TerminalGuard::hide_cursor();
+    let track: Vec<char> = " ".repeat(width).chars().chain(text.chars()).collect();
+    let frame_ms = (duration_ms / width.max(1) as u64).max(1);
+
+    // Fast mode and plain text show only the last frame.
+    let first = if is_fast() || palette().is_plain() {
+        width
+    } else {
+        0
+    };
+    for offset in first..=width {
+        let frame: String = track.iter().skip(offset).take(width).collect();
+        print!("\r{}{}{}", color, frame, palette().reset);
+        io::stdout().flush().unwrap();
+        pause(frame_ms);
+    }
+    println!();
+}
+
+/// Word-wraps `text` into lines of at most `width` columns, each starting
+/// with `indent`. Words longer than a line are split where they overflow.
+pub fn wrap(text: &str, width: usize, indent: &str) -> Vec<String> {
+    let room = width.saturating_sub(indent.chars().count()).max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut used = 0;
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        if used > 0 && used + 1 + word.len() > room {
+            lines.push(format!("{}{}", indent, line));
+            line.clear();
+            used = 0;
+        }
+        while word.len() > room {
+            let rest = word.split_off(room);
+            line.extend(word);
+            lines.push(format!("{}{}", indent, line));
+            line.clear();
+            used = 0;
+            word = rest;
+        }
+        if used > 0 {
+            line.push(' ');
+            used += 1;
+        }
+        used += word.len();
+        line.extend(word);
+    }
+    if used > 0 || lines.is_empty() {
+        lines.push(format!("{}{}", indent, line));
+    }
+    lines
+}
+
+/// Blinks `text` on its own line `times` times, then leaves it showing.
+pub fn flash(text: &str, times: u32, color: &str) {
+    let _cursor = TerminalGuard::hide_cursor();
+    let blank = " ".repeat(text.chars().count());
+    let times = if is_fast() || palette().is_plain() {
+        0
+    } else {
+        times
+    };
+    for _ in 0..times {
+        print!("\r{}{}{}", color, text, palette().reset);
+        io::stdout().flush().unwrap();
+        thread::sleep(Duration::from_millis(250));
+        print!("\r{}", blank);
+        io::stdout().flush().unwrap();
+        thread::sleep(Duration::from_millis(150));
+    }
+    println!("\r{}{}{}", color, text, palette().reset);
+}
+
+/// A horizontal separator line.
+pub fn divider(width: usize) -> String {
+    glyphs().h_line.to_string().repeat(width)
+}
+
+/// Rings the terminal bell. Plain output stays free of control codes, so it
+/// stays silent there.
+pub fn bell() {
+    if palette().is_plain() {
+        return;
+    }
+    print!("\x07");
+    io::stdout().flush().unwrap();
+}
+
+/// Puts the terminal back the way the shell expects it: default colors and
+/// a visible cursor. Safe to call any number of times.
+pub fn restore_terminal() {
+    let p = palette();
+    print!("{}{}", p.reset, p.show_cursor);
+    let _ = io::stdout().flush();
+}
+
+/// Restores the terminal when dropped, including while a panic unwinds.
+/// `hide_cursor` also hides the cursor for the guard's lifetime, so it does
+/// not dart around during animations.
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    /// Leaves the cursor alone; only restores on drop.
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+
+    pub fn hide_cursor() -> Self {
+        print!("{}", palette().hide_cursor);
+        let _ = io::stdout().flush();
+        Self::new()
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
 
 /// Clears the terminal screen and moves cursor to top-left.
 pub fn clear_screen() {
-    print!("\x1b[2J\x1b[1;1H");
+    print!("{}", palette().clear);
+}
+
+/// Narrowest bar drawn, however little room the label leaves.
+const MIN_BAR_WIDTH: usize = 4;
+
+/// Labels are padded to at least this many columns, so a stack of bars
+/// (confidence, one per advisor) starts in the same place.
+const LABEL_COLUMN: usize = 15;
+
+/// Columns a labelled bar row takes on the dashboard.
+pub const BAR_ROW_WIDTH: usize = 48;
+
+/// Splits `width` columns between the label and the bar: the label column
+/// takes at least `LABEL_COLUMN`, a space, the brackets and the percentage
+/// take 8, the bar never drops below `MIN_BAR_WIDTH`, and the label is cut
+/// short rather than pushing the total past `width`.
+fn bar_layout(label: &str, width: usize) -> (String, usize) {
+    let max_label = width.saturating_sub(8 + MIN_BAR_WIDTH);
+    let label: String = label.chars().take(max_label).collect();
+    let column = label.chars().count().max(LABEL_COLUMN);
+    let bar_width = width.saturating_sub(column + 8).max(MIN_BAR_WIDTH);
+    (label, bar_width)
+}
+
+/// One labelled bar, `width` columns wide whatever the label's length.
+fn bar_row(label: &str, value: f64, width: usize, color: &str, rng: &mut dyn Rng) -> String {
+    let (label, bar_width) = bar_layout(label, width);
+    format!(
+        "{:<column$} {}",
+        label,
+        bar_cells(value, bar_width, color, !numbers_hidden(), rng),
+        column = LABEL_COLUMN
+    )
 }
 
 /// Renders a "glitched" progress bar.
-pub fn draw_progress_bar(label: &str, value: f64, width: usize, color: &str, rng: &mut SimpleRng) {
-    let bar_width = width - label.len() - 8; // -8 for brackets and percentage
-    let filled = (value * bar_width as f64).round() as usize;
-    let empty = bar_width.saturating_sub(filled);
+pub fn draw_progress_bar(label: &str, value: f64, width: usize, color: &str, rng: &mut dyn Rng) {
+    println!("{}", bar_row(label, value, width, color, rng));
+}
 
-    print!("{:<15} [", label);
-    print!("{}", color);
+/// The bracketed, glitching bar itself plus, with `figure`, the percentage:
+/// `bar_width + 2` columns, and 5 more for the figure.
+fn bar_cells(value: f64, bar_width: usize, color: &str, figure: bool, rng: &mut dyn Rng) -> String {
+    let filled = ((value.clamp(0.0, 1.0) * bar_width as f64).round() as usize).min(bar_width);
+    let empty = bar_width - filled;
+    let g = glyphs();
 
+    let mut cells = format!("[{}", color);
     for _i in 0..filled {
         // Occasional glitch in the bar
         if rng.random_bool(0.05) {
-            print!("{}", BLOCK_STATUS_2);
+            cells.push(g.block_glitch);
         } else {
-            print!("{}", BLOCK_STATUS_1);
+            cells.push(g.block_full);
         }
     }
-
-    print!("{}", GREY_DIM);
+    cells.push_str(palette().grey_dim);
     for _ in 0..empty {
-        print!("{}", BLOCK_STATUS_3);
+        cells.push(g.block_empty);
+    }
+    cells.push_str(palette().reset);
+    cells.push(']');
+    if figure {
+        cells.push_str(&format!(
+            "{} {:>3}%",
+            color,
+            (value.clamp(0.0, 1.0) * 100.0) as u32
+        ));
     }
+    cells.push_str(palette().reset);
+    cells
+}
 
-    print!("{}]{} {:>3}%", RESET, color, (value * 100.0) as u32);
-    println!("{}", RESET);
+/// Bar color for a metric by how dangerous its reading is, 0.0 (fine) to 1.0.
+pub fn danger_color(danger: f64) -> &'static str {
+    if danger > 0.7 {
+        palette().red_alert
+    } else if danger > 0.5 {
+        palette().orange
+    } else if danger > 0.3 {
+        palette().amber
+    } else {
+        palette().teal
+    }
+}
+
+/// Columns `text` takes on screen, ignoring color escapes.
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            width += char_width(c);
+        }
+    }
+    width
+}
+
+/// Terminal columns for one character: 2 for East Asian wide and
+/// full-width forms (and most emoji), 0 for controls and combining marks,
+/// 1 for everything else. A small table rather than full Unicode data,
+/// covering what translations and glyph themes actually use.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x00..=0x1F | 0x7F..=0x9F => 0,
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// One row of the threat board: label, reading and how dangerous it is.
+pub struct Threat<'a> {
+    pub label: &'a str,
+    pub value: f64,
+    pub danger: f64,
+}
+
+/// The threat board: every metric as a bar inside a `width`-column box,
+/// colored by danger.
+pub fn draw_threat_board(title: &str, threats: &[Threat], width: usize, rng: &mut dyn Rng) {
+    for line in threat_board(title, threats, width, rng) {
+        println!("{}", line);
+    }
+}
+
+fn threat_board(title: &str, threats: &[Threat], width: usize, rng: &mut dyn Rng) -> Vec<String> {
+    let g = glyphs();
+    let inner = width.saturating_sub(2);
+    let title: String = format!(" {} ", title).chars().take(inner).collect();
+    let mut lines = vec![format!(
+        "{}{}{}{}{}{}",
+        palette().teal,
+        g.tl_corner,
+        title,
+        divider(inner - title.chars().count()),
+        g.tr_corner,
+        palette().reset
+    )];
+    let label_width = threats
+        .iter()
+        .map(|t| t.label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let figure = !numbers_hidden();
+    // A space either side of the row, one after the label and two brackets.
+    let bar_width = inner
+        .saturating_sub(label_width + if figure { 5 } else { 0 } + 5)
+        .max(1);
+    for threat in threats {
+        let bar = format!(
+            "{:<w$} {}",
+            threat.label,
+            bar_cells(
+                threat.value,
+                bar_width,
+                danger_color(threat.danger),
+                figure,
+                rng
+            ),
+            w = label_width
+        );
+        let pad = inner.saturating_sub(visible_width(&bar) + 1);
+        lines.push(format!(
+            "{}{}{} {}{}{}{}{}",
+            palette().teal,
+            g.v_line,
+            palette().reset,
+            bar,
+            " ".repeat(pad),
+            palette().teal,
+            g.v_line,
+            palette().reset
+        ));
+    }
+    lines.push(format!(
+        "{}{}{}{}{}",
+        palette().teal,
+        g.bl_corner,
+        divider(inner),
+        g.br_corner,
+        palette().reset
+    ));
+    lines
+}
+
+/// One glyph per value, its height picked from the sparkline levels.
+/// Values are clamped to 0.0..=1.0.
+pub fn sparkline(values: &[f64]) -> String {
+    let levels = glyphs().spark;
+    let top = (levels.len() - 1) as f64;
+    values
+        .iter()
+        .map(|v| levels[(v.clamp(0.0, 1.0) * top).round() as usize])
+        .collect()
+}
+
+/// A one-line footer of `(keys, action)` pairs that fits in `width`
+/// columns. Entries that would overflow are dropped from the end.
+pub fn key_footer(entries: &[(&str, &str)], width: usize) -> String {
+    let separator = if is_ascii() { " | " } else { " · " };
+    let mut line = String::new();
+    for (keys, action) in entries {
+        let entry = format!("{} {}", keys, action);
+        let extra = if line.is_empty() {
+            0
+        } else {
+            separator.chars().count()
+        };
+        if line.chars().count() + extra + entry.chars().count() > width {
+            break;
+        }
+        if !line.is_empty() {
+            line.push_str(separator);
+        }
+        line.push_str(&entry);
+    }
+    line
+}
+
+/// Renders one advisor's loyalty row. Suspicion is nominally 0-100, but
+/// stacked interrogations push it past 100, so the fill is clamped.
+pub fn draw_advisor_suspicion(advisor: &Advisor, rng: &mut dyn Rng) {
+    let mut label = if advisor.is_double_agent {
+        format!("{} [ASSET]", advisor.name)
+    } else {
+        advisor.name.clone()
+    };
+    if advisor.leads > 0 {
+        let plural = if advisor.leads == 1 { "" } else { "S" };
+        label.push_str(&format!(" ({} LEAD{})", advisor.leads, plural));
+    }
+    let color = if advisor.suspicion > 70 {
+        palette().red_alert
+    } else {
+        palette().teal
+    };
+    let fraction = (advisor.suspicion as f64 / 100.0).clamp(0.0, 1.0);
+    draw_progress_bar(&label, fraction, BAR_ROW_WIDTH, color, rng);
 }
 
 /// Prints text with a typewriter effect, optionally glitching characters.
-pub fn type_text(text: &str, speed_ms: u64, color: &str, glitch_chance: f64, rng: &mut SimpleRng) {
+pub fn type_text(text: &str, speed_ms: u64, color: &str, glitch_chance: f64, rng: &mut dyn Rng) {
+    if is_fast() {
+        println!("{}{}{}", color, text, palette().reset);
+        return;
+    }
+    let _cursor = TerminalGuard::hide_cursor();
     print!("{}", color);
     for c in text.chars() {
         if glitch_chance > 0.0 && rng.random_bool(glitch_chance) {
@@ -71,94 +660,341 @@ pub fn type_text(text: &str, speed_ms: u64, color: &str, glitch_chance: f64, rng
         io::stdout().flush().unwrap();
         thread::sleep(Duration::from_millis(speed_ms));
     }
-    println!("{}", RESET);
+    println!("{}", palette().reset);
 }
 
-/// Draws the main HUD header.
-pub fn draw_hud(turn: u32, tension: f64, intel: u32, max_intel: u32) {
-    let width = 60;
-    let inner_width = width - 2;
+/// Typewriter for the cable feed. Takes a cable already wrapped with
+/// [`wrap`], so a line never breaks mid-word while it is being typed.
+/// Enter fast-forwards the current cable; the skip key dumps it instantly and
+/// returns true so the caller can print the remaining cables without animation.
+/// The pause key hands over to `on_pause`, then retypes the unfinished line
+/// and carries on from the same character.
+pub fn type_feed_lines(
+    lines: &[String],
+    speed_ms: u64,
+    color: &str,
+    input: &InputManager,
+    on_pause: &mut dyn FnMut(),
+) -> bool {
+    let _cursor = TerminalGuard::hide_cursor();
+    let mut delay = if is_fast() { 0 } else { speed_ms };
+    let mut skip_all = false;
+
+    print!("{}", color);
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        for (typed, c) in line.char_indices() {
+            match input.poll_interrupt() {
+                Some(Interrupt::SpeedUp) => delay = 0,
+                Some(Interrupt::SkipAll) => {
+                    delay = 0;
+                    skip_all = true;
+                }
+                Some(Interrupt::Pause) => {
+                    println!("{}", palette().reset);
+                    on_pause();
+                    print!("{}{}", color, &line[..typed]);
+                }
+                None => {}
+            }
+            print!("{}", c);
+            if delay > 0 {
+                io::stdout().flush().unwrap();
+                thread::sleep(Duration::from_millis(delay));
+            }
+        }
+    }
+    println!("{}", palette().reset);
+    skip_all
+}
 
-    let date_str = format!("DAY {:03} // 1983", turn);
-    let intel_str = format!("INTEL: {}/{}", intel, max_intel);
-    let defcon_plain_str = format!("DEFCON: {:.2}", tension);
+/// Formats a wall-clock span as HH:MM:SS.
+pub fn format_duration(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
 
-    // Calculate dynamic spacing
-    // We have 3 items: [date] [defcon] [intel]
-    // Total content length
-    let content_len = date_str.len() + defcon_plain_str.len() + intel_str.len();
+/// Draws the main HUD header.
+/// `tension_band` replaces the DEFCON figure when numbers are hidden.
+pub fn draw_hud(
+    turn: u32,
+    tension: f64,
+    tension_band: &str,
+    trend: f64,
+    intel: u32,
+    max_intel: u32,
+    elapsed: Duration,
+) {
+    let g = glyphs();
 
-    // Check if we have space (we should, ~37 chars vs 58 space)
-    let available_space = if content_len < inner_width {
-        inner_width - content_len
+    // Ignore drift too small to steer by.
+    let arrow = if trend > 0.01 {
+        g.rising
+    } else if trend < -0.01 {
+        g.falling
     } else {
-        0
+        g.steady
     };
 
-    // Distribute space:
-    // Left padding: 1 (if possible)
-    // Gap 1 (Date->Defcon): remaining / 2
-    // Gap 2 (Defcon->Intel): remaining - Gap 1
-    // Right padding: 1 (if possible) - actually included in gaps usually or just ensure spacing.
-
-    // Let's go for specific look:
-    // | DAY...   DEFCON...   INTEL... |
-    // We want at least 1 space between items.
-
-    // Simple distribution:
-    // [Date] [Gap1] [Defcon] [Gap2] [Intel]
-    // We won't put padding on far left/right edges to maximize internal spacing,
-    // or we can put 1 space left/right for aesthetics.
-    // Let's put 1 space left and 1 space right if we have enough space.
-
-    let (pad_left, pad_right, gap1, gap2) = if available_space >= 4 {
-        let internal_space = available_space - 2; // Reserve 1 left, 1 right
-        let g1 = internal_space / 2;
-        let g2 = internal_space - g1;
-        (1, 1, g1, g2)
+    let defcon_value = defcon_reading(tension, tension_band, numbers_hidden());
+    let tension_color = if tension > 0.8 {
+        palette().red_alert
+    } else if tension > 0.5 {
+        palette().orange
     } else {
-        // Not enough space for nice padding, just split between items
-        let g1 = available_space / 2;
-        let g2 = available_space - g1;
-        (0, 0, g1, g2)
+        palette().teal
     };
 
-    // Top Border
-    println!(
-        "{}{}{}{}",
-        TEAL,
-        TL_CORNER,
-        H_LINE.to_string().repeat(inner_width),
-        TR_CORNER
-    );
+    let items = [
+        format!("DAY {:03} // 1983", turn),
+        format!(
+            "DEFCON: {}{} {}{}",
+            tension_color,
+            defcon_value,
+            arrow,
+            palette().teal
+        ),
+        format!("INTEL: {}/{}", intel, max_intel),
+        format!("T+{}", format_duration(elapsed)),
+    ];
+    for line in hud_box(&items, 60) {
+        println!("{}", line);
+    }
+}
 
-    // Info Line construction
-    let tension_color = if tension > 0.8 {
-        RED_ALERT
-    } else if tension > 0.5 {
-        ORANGE
+/// The DEFCON figure on the HUD, or under fog of war its band.
+fn defcon_reading(tension: f64, tension_band: &str, hidden: bool) -> String {
+    if hidden {
+        tension_band.to_string()
     } else {
-        TEAL
-    };
+        format!("{:.2}", tension)
+    }
+}
 
-    print!("{}{}", TEAL, V_LINE); // Start border
+/// The HUD box: the four items spread across one row between the borders.
+/// Spacing is worked out in screen columns, so color codes, multibyte
+/// glyphs and wide characters all line up.
+fn hud_box(items: &[String; 4], width: usize) -> Vec<String> {
+    let inner_width = width - 2;
+    let g = glyphs();
 
-    // Content
-    print!("{}{}", " ".repeat(pad_left), date_str);
-    print!("{}", " ".repeat(gap1));
-    print!("DEFCON: {}{:.2}{}", tension_color, tension, TEAL); // Manual print to handle color
-    print!("{}", " ".repeat(gap2));
-    print!("{}{}", intel_str, " ".repeat(pad_right));
+    let content_len: usize = items.iter().map(|item| visible_width(item)).sum();
+    let available_space = inner_width.saturating_sub(content_len);
 
-    println!("{}{}{}", TEAL, V_LINE, RESET); // End border
+    // One space inside each border when there is room, the rest split into
+    // the three gaps between the items.
+    let (pad_left, pad_right, internal_space) = if available_space >= 5 {
+        (1, 1, available_space - 2)
+    } else {
+        (0, 0, available_space)
+    };
+    let gap1 = internal_space / 3;
+    let gap2 = (internal_space - gap1) / 2;
+    let gap3 = internal_space - gap1 - gap2;
 
-    // Bottom Border
-    println!(
+    let top = format!(
+        "{}{}{}{}",
+        palette().teal,
+        g.tl_corner,
+        divider(inner_width),
+        g.tr_corner
+    );
+    let row = format!(
+        "{}{}{}{}{}{}{}{}{}{}{}{}{}{}",
+        palette().teal,
+        g.v_line,
+        " ".repeat(pad_left),
+        items[0],
+        " ".repeat(gap1),
+        items[1],
+        " ".repeat(gap2),
+        items[2],
+        " ".repeat(gap3),
+        items[3],
+        " ".repeat(pad_right),
+        palette().teal,
+        g.v_line,
+        palette().reset
+    );
+    let bottom = format!(
         "{}{}{}{}{}",
-        TEAL,
-        BL_CORNER,
-        H_LINE.to_string().repeat(inner_width),
-        BR_CORNER,
-        RESET
+        palette().teal,
+        g.bl_corner,
+        divider(inner_width),
+        g.br_corner,
+        palette().reset
     );
+    vec![top, row, bottom]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_plain_palette_has_no_escape_codes() {
+        let codes = |p: &Palette| {
+            [
+                p.teal,
+                p.amber,
+                p.orange,
+                p.red_alert,
+                p.grey_dim,
+                p.white_bold,
+                p.reset,
+                p.clear,
+                p.hide_cursor,
+                p.show_cursor,
+                p.save_cursor,
+                p.restore_cursor,
+            ]
+        };
+        assert!(PLAIN_PALETTE.is_plain());
+        assert!(codes(&PLAIN_PALETTE).iter().all(|code| code.is_empty()));
+        assert!(!ANSI_PALETTE.is_plain());
+        assert!(codes(&ANSI_PALETTE)
+            .iter()
+            .all(|code| code.starts_with('\x1b')));
+    }
+
+    #[test]
+    fn wrap_breaks_at_words_and_keeps_the_indent() {
+        assert_eq!(
+            wrap("TROOPS MASSING NEAR THE BORDER", 15, " "),
+            vec![" TROOPS MASSING", " NEAR THE", " BORDER"]
+        );
+        assert_eq!(
+            wrap("ABCDEFGHIJ", 5, "> "),
+            vec!["> ABC", "> DEF", "> GHI", "> J"]
+        );
+        assert_eq!(wrap("GO NOW", 2, ""), vec!["GO", "NO", "W"]);
+        assert_eq!(wrap("", 40, " "), vec![" "]);
+    }
+
+    #[test]
+    fn bar_layout_survives_tiny_widths_and_long_labels() {
+        assert_eq!(bar_layout("STABILITY", 40), ("STABILITY".to_string(), 17));
+        assert_eq!(bar_layout("STABILITY", 0), (String::new(), MIN_BAR_WIDTH));
+        let (label, bar) = bar_layout("A VERY LONG ADVISOR NAME [ASSET]", 20);
+        assert_eq!(label, "A VERY L");
+        assert_eq!(bar, MIN_BAR_WIDTH);
+    }
+
+    #[test]
+    fn stacked_bars_end_in_the_same_column() {
+        let mut rng = SimpleRng::new();
+        for label in [
+            "CONFIDENCE",
+            "Gen. Vance",
+            "Amb. Sterling",
+            "Dr. Alexandra Petrov (2 LEADS)",
+        ] {
+            let row = bar_row(label, 0.4, BAR_ROW_WIDTH, palette().teal, &mut rng);
+            assert_eq!(visible_width(&row), BAR_ROW_WIDTH, "{:?}", row);
+        }
+    }
+
+    #[test]
+    fn draw_progress_bar_handles_pathological_input() {
+        let mut rng = SimpleRng::new();
+        draw_progress_bar("", 0.5, 0, palette().teal, &mut rng);
+        draw_progress_bar("Ambassador Extraordinary", 7.0, 3, palette().teal, &mut rng);
+        draw_progress_bar("X", -1.0, 9, palette().teal, &mut rng);
+    }
+
+    #[test]
+    fn threat_board_rows_fill_the_box_exactly() {
+        let mut rng = SimpleRng::new();
+        let threats = [
+            Threat {
+                label: "TENSION",
+                value: 0.9,
+                danger: 0.9,
+            },
+            Threat {
+                label: "ESCALATION RISK",
+                value: 0.0,
+                danger: 0.0,
+            },
+        ];
+        for width in [30, 60, 80] {
+            let lines = threat_board("THREAT BOARD", &threats, width, &mut rng);
+            assert_eq!(lines.len(), threats.len() + 2);
+            for line in &lines {
+                assert_eq!(visible_width(line), width, "{:?}", line);
+            }
+        }
+        assert!(threat_board("T", &threats, 40, &mut rng)[1].contains(palette().red_alert));
+    }
+
+    #[test]
+    fn hud_borders_line_up_with_wide_and_multibyte_labels() {
+        let items = [
+            "DÍA 003 // 1983".to_string(),
+            format!(
+                "DEFCON: {}0.20 {}{}",
+                palette().red_alert,
+                '\u{2191}',
+                palette().teal
+            ),
+            "情報: 2/3".to_string(),
+            "T+00:05".to_string(),
+        ];
+        assert_eq!(visible_width(&items[2]), 9);
+        let lines = hud_box(&items, 60);
+        for line in &lines {
+            assert_eq!(visible_width(line), 60, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn key_footer_drops_what_does_not_fit() {
+        let entries = [("ENTER", "fast-forward"), ("ESC", "skip")];
+        let full = key_footer(&entries, 80);
+        assert!(full.starts_with("ENTER fast-forward") && full.ends_with("ESC skip"));
+        assert_eq!(key_footer(&entries, 20), "ENTER fast-forward");
+        assert_eq!(key_footer(&entries, 3), "");
+    }
+
+    #[test]
+    fn fog_of_war_shows_bands_and_no_figures() {
+        let mut rng = SimpleRng::new();
+        assert_eq!(defcon_reading(0.73, "SEVERE", true), "SEVERE");
+        assert_eq!(defcon_reading(0.73, "SEVERE", false), "0.73");
+
+        let hidden = bar_cells(0.73, 10, "", false, &mut rng);
+        assert!(!hidden.contains('%'));
+        assert_eq!(visible_width(&hidden), 12);
+        let shown = bar_cells(0.73, 10, "", true, &mut rng);
+        assert!(shown.contains(" 73%"));
+        assert_eq!(visible_width(&shown), 17);
+    }
+
+    #[test]
+    fn sparkline_maps_each_value_to_a_level() {
+        let line = sparkline(&[0.0, 0.5, 1.0, 7.0, f64::NAN]);
+        let chars: Vec<char> = line.chars().collect();
+        let levels = glyphs().spark;
+        assert_eq!(chars.len(), 5);
+        assert_eq!(chars[0], levels[0]);
+        assert_eq!(chars[2], levels[7]);
+        assert_eq!(chars[3], levels[7]);
+    }
+
+    #[test]
+    fn advisor_suspicion_past_one_hundred_renders() {
+        let mut rng = SimpleRng::new();
+        let mut advisor = crate::state::WorldState::new().advisors.remove(0);
+        for suspicion in [0, 100, 130, u32::MAX] {
+            advisor.suspicion = suspicion;
+            draw_advisor_suspicion(&advisor, &mut rng);
+        }
+    }
 }
@@ -0,0 +1,35 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn running_out_of_piped_input_still_shuts_down_cleanly() {
+    let dir = std::env::temp_dir().join(format!("coldwar-eof-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let transcript = dir.join("transcript.txt");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ColdWarTerminal"))
+        .args(["--fast", "--quiet-boot", "--seed", "1", "--autosave", "1"])
+        .arg("--transcript")
+        .arg(&transcript)
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Dropping stdin after the one line closes it mid-shift.
+    child.stdin.take().unwrap().write_all(b"help\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let transcript_written = transcript.exists();
+    let autosaved = dir.join("coldwar-autosave.toml").exists();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stderr.contains("standard input closed"));
+    assert!(stdout.contains("SECURE LINK CLOSED."));
+    assert!(transcript_written);
+    assert!(autosaved);
+}